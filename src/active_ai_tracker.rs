@@ -2,13 +2,24 @@
 //!
 //! Manages which AI entities are "active" (scheduled) vs "dormant" (too far from player).
 //! Dormant entities don't get scheduled at all until the player approaches.
+//! Instead, `systems::offscreen` moves a few of them at a time at low resolution,
+//! using the player trail and roaming state kept here.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use hecs::{Entity, World};
 
 use crate::components::{ChaseAI, CompanionAI, Position};
-use crate::constants::AI_ACTIVE_RADIUS;
+use crate::constants::{AI_ACTIVE_RADIUS, OFFSCREEN_TRAIL_LENGTH};
+
+/// Coarse off-screen state for one dormant actor
+#[derive(Debug, Clone, Copy)]
+pub struct RoamState {
+    /// Where the actor is heading while idle (a room or an ally)
+    pub goal: Option<(i32, i32)>,
+    /// Game time the actor was last simulated
+    pub last_update: f32,
+}
 
 /// Tracks which AI entities are active (scheduled) vs dormant (not scheduled).
 ///
@@ -22,6 +33,18 @@ pub struct ActiveAITracker {
 
     /// Entities that are dormant (too far from player, not scheduled)
     dormant_entities: HashSet<Entity>,
+
+    /// Recent player positions, oldest first (hunters follow it off-screen)
+    player_trail: VecDeque<(i32, i32)>,
+
+    /// Dormant entities waiting for their next off-screen update (round-robin)
+    roam_queue: VecDeque<Entity>,
+
+    /// Off-screen state of dormant entities that have been simulated
+    roamers: HashMap<Entity, RoamState>,
+
+    /// Game time of the last off-screen tick
+    last_offscreen_tick: f32,
 }
 
 impl ActiveAITracker {
@@ -30,6 +53,10 @@ impl ActiveAITracker {
         Self {
             active_entities: HashSet::new(),
             dormant_entities: HashSet::new(),
+            player_trail: VecDeque::new(),
+            roam_queue: VecDeque::new(),
+            roamers: HashMap::new(),
+            last_offscreen_tick: 0.0,
         }
     }
 
@@ -41,6 +68,7 @@ impl ActiveAITracker {
     /// Mark an entity as active (scheduled).
     pub fn mark_active(&mut self, entity: Entity) {
         self.dormant_entities.remove(&entity);
+        self.roamers.remove(&entity);
         self.active_entities.insert(entity);
     }
 
@@ -54,6 +82,7 @@ impl ActiveAITracker {
     pub fn remove_entity(&mut self, entity: Entity) {
         self.active_entities.remove(&entity);
         self.dormant_entities.remove(&entity);
+        self.roamers.remove(&entity);
     }
 
     /// Check if an entity is currently active.
@@ -72,6 +101,10 @@ impl ActiveAITracker {
     pub fn clear(&mut self) {
         self.active_entities.clear();
         self.dormant_entities.clear();
+        self.player_trail.clear();
+        self.roam_queue.clear();
+        self.roamers.clear();
+        self.last_offscreen_tick = 0.0;
     }
 
     /// Update the active set based on player position.
@@ -83,6 +116,7 @@ impl ActiveAITracker {
         world: &World,
         player_pos: (i32, i32),
     ) -> Vec<Entity> {
+        self.record_player_position(player_pos);
         let mut newly_active = Vec::new();
 
         // Check all AI entities and update their status
@@ -119,6 +153,9 @@ impl ActiveAITracker {
         }
 
         // Update our sets
+        for entity in &newly_active {
+            self.roamers.remove(entity);
+        }
         self.active_entities = new_active;
         self.dormant_entities = new_dormant;
 
@@ -167,6 +204,61 @@ impl ActiveAITracker {
     pub fn get_dormant_entities(&self) -> &HashSet<Entity> {
         &self.dormant_entities
     }
+
+    // =========================================================================
+    // OFF-SCREEN SIMULATION STATE
+    // =========================================================================
+
+    /// Append the player's position to the trail, dropping the oldest entries.
+    pub fn record_player_position(&mut self, pos: (i32, i32)) {
+        if self.player_trail.back() == Some(&pos) {
+            return;
+        }
+        if self.player_trail.len() >= OFFSCREEN_TRAIL_LENGTH {
+            self.player_trail.pop_front();
+        }
+        self.player_trail.push_back(pos);
+    }
+
+    /// Recent player positions, oldest first.
+    pub fn player_trail(&self) -> &VecDeque<(i32, i32)> {
+        &self.player_trail
+    }
+
+    /// Returns true (and starts a new interval) if `interval` seconds have
+    /// passed since the last off-screen tick.
+    pub fn offscreen_tick_due(&mut self, time: f32, interval: f32) -> bool {
+        if time - self.last_offscreen_tick < interval {
+            return false;
+        }
+        self.last_offscreen_tick = time;
+        true
+    }
+
+    /// Take up to `max` dormant entities for this off-screen tick, cycling
+    /// through all of them before any is picked twice.
+    pub fn next_offscreen_batch(&mut self, max: usize) -> Vec<Entity> {
+        if self.roam_queue.is_empty() {
+            self.roam_queue.extend(self.dormant_entities.iter().copied());
+        }
+
+        let mut batch = Vec::with_capacity(max);
+        while batch.len() < max {
+            let Some(entity) = self.roam_queue.pop_front() else {
+                break;
+            };
+            if self.dormant_entities.contains(&entity) {
+                batch.push(entity);
+            }
+        }
+        batch
+    }
+
+    /// Off-screen state for a dormant entity, created on first use as if it
+    /// was last simulated at `since`.
+    pub fn roam_state(&mut self, entity: Entity, since: f32) -> &mut RoamState {
+        self.roamers.entry(entity).or_insert(RoamState { goal: None, last_update: since })
+    }
 }
 
 impl Default for ActiveAITracker {
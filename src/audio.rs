@@ -59,7 +59,7 @@ impl AudioManager {
             _stream: stream,
             stream_handle,
             sounds: HashMap::new(),
-            volume: 0.5,
+            volume: crate::constants::DEFAULT_VOLUME,
         };
 
         manager.load_sounds();
@@ -241,6 +241,11 @@ impl AudioManager {
                     let dist = Self::distance(player_pos, pos);
                     self.play_at_distance(SoundType::MeleeSwing, dist);
                 }
+                GameEvent::AttackMissed { target_pos, ranged: false, .. } => {
+                    let pos = (target_pos.0 as i32, target_pos.1 as i32);
+                    let dist = Self::distance(player_pos, pos);
+                    self.play_at_distance(SoundType::MeleeSwing, dist);
+                }
                 GameEvent::EntityDied { position, .. } => {
                     let pos = (position.0 as i32, position.1 as i32);
                     let dist = Self::distance(player_pos, pos);
@@ -297,7 +302,7 @@ impl AudioManager {
                 GameEvent::PotionDrunk { .. } => {
                     self.play(SoundType::PotionDrink);
                 }
-                GameEvent::WeaponEquipped { .. } => {
+                GameEvent::WeaponEquipped { .. } | GameEvent::ArmorEquipped { .. } => {
                     self.play(SoundType::WeaponEquip);
                 }
                 GameEvent::CleavePerformed { center, .. } => {
@@ -327,7 +332,6 @@ impl AudioManager {
     }
 
     /// Set the master volume (0.0 - 1.0)
-    #[allow(dead_code)]
     pub fn set_volume(&mut self, volume: f32) {
         self.volume = volume.clamp(0.0, 1.0);
     }
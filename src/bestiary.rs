@@ -0,0 +1,277 @@
+//! The bestiary: every monster type the player has met, across all runs.
+//!
+//! Stored as `bestiary.json` in the config directory. During a run,
+//! `MonsterNotes` picks out what matters from game events (kills by the player
+//! and their companions, who killed the player) and which monsters came into view; the engine hands the notes
+//! to the bestiary every frame and saves it when something new was learned.
+
+use std::collections::{BTreeMap, HashMap};
+
+use hecs::{Entity, World};
+use serde::{Deserialize, Serialize};
+
+use crate::components::{CompanionAI, Health, Name, Position, Projectile, TamedBy};
+use crate::config;
+use crate::constants::{BESTIARY_FOUGHT_KILLS, BESTIARY_MASTERED_KILLS, BESTIARY_STUDIED_KILLS};
+use crate::events::GameEvent;
+use crate::grid::Grid;
+use crate::spawning::enemies;
+
+const BESTIARY_FILE: &str = "bestiary.json";
+
+/// What the player has done to (and suffered from) one monster type
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BestiaryEntry {
+    /// How many the player and their companions have killed
+    pub kills: u32,
+    /// How many times one killed the player
+    pub player_deaths: u32,
+}
+
+/// How much the bestiary reveals about a monster type. Grows with kills.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Knowledge {
+    /// Met but never killed: name and looks only
+    Sighted,
+    /// Health and damage
+    Fought,
+    /// Speed, sight and ranged attack
+    Studied,
+    /// Resistances and tactics
+    Mastered,
+}
+
+impl BestiaryEntry {
+    pub fn knowledge(&self) -> Knowledge {
+        match self.kills {
+            k if k >= BESTIARY_MASTERED_KILLS => Knowledge::Mastered,
+            k if k >= BESTIARY_STUDIED_KILLS => Knowledge::Studied,
+            k if k >= BESTIARY_FOUGHT_KILLS => Knowledge::Fought,
+            _ => Knowledge::Sighted,
+        }
+    }
+
+    /// Kills still needed before the bestiary reveals more, if there's more to reveal
+    pub fn kills_to_next(&self) -> Option<u32> {
+        [BESTIARY_FOUGHT_KILLS, BESTIARY_STUDIED_KILLS, BESTIARY_MASTERED_KILLS]
+            .into_iter()
+            .find(|&threshold| threshold > self.kills)
+            .map(|threshold| threshold - self.kills)
+    }
+}
+
+/// Every monster type met, by display name
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Bestiary {
+    pub monsters: BTreeMap<String, BestiaryEntry>,
+}
+
+impl Bestiary {
+    /// The bestiary file, or an empty bestiary if there isn't one.
+    /// A broken file is reported and replaced by an empty bestiary.
+    pub fn load() -> Self {
+        match config::read(BESTIARY_FILE) {
+            Ok(Some(json)) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                eprintln!("Warning: {}: {}", BESTIARY_FILE, e);
+                Self::default()
+            }),
+            Ok(None) => Self::default(),
+            Err(e) => {
+                eprintln!("Warning: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the bestiary file
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        config::write(BESTIARY_FILE, &json)
+    }
+
+    /// The entry for a monster type, if the player has met one
+    pub fn entry(&self, name: &str) -> Option<&BestiaryEntry> {
+        self.monsters.get(name)
+    }
+
+    /// Write down what the notes say, taking the kills and deaths out of them.
+    /// Returns whether anything changed.
+    pub fn record(&mut self, notes: &mut MonsterNotes) -> bool {
+        let mut changed = false;
+        for name in &notes.met {
+            if !self.monsters.contains_key(*name) {
+                self.monsters.insert(name.to_string(), BestiaryEntry::default());
+                changed = true;
+            }
+        }
+        for name in notes.kills.drain(..) {
+            self.monsters.entry(name.to_string()).or_default().kills += 1;
+            changed = true;
+        }
+        if let Some(name) = notes.killed_player.take() {
+            self.monsters.entry(name.to_string()).or_default().player_deaths += 1;
+            changed = true;
+        }
+        changed
+    }
+}
+
+/// What happened to monsters this run, until the bestiary writes it down
+#[derive(Debug, Clone, Default)]
+pub struct MonsterNotes {
+    /// Monster types seen this run
+    pub met: Vec<&'static str>,
+    /// Monsters the player or their companions killed since the bestiary last looked
+    pub kills: Vec<&'static str>,
+    /// The monster that killed the player, if it just happened
+    pub killed_player: Option<&'static str>,
+    /// The last monster to hurt the player (cleared by other harm, like burning)
+    pub last_hurt_by: Option<&'static str>,
+    /// Who last hurt each monster, to credit its death to
+    last_hit_by: HashMap<Entity, Entity>,
+}
+
+impl MonsterNotes {
+    /// Note a kill, or who hurt or killed the player
+    pub fn handle_event(&mut self, event: &GameEvent, world: &World, player: Entity) {
+        match event {
+            GameEvent::AttackHit { attacker, target, .. } if *target == player => {
+                self.last_hurt_by = monster_type(world, *attacker);
+            }
+            GameEvent::LifeDrainTick { caster, target, .. } if *target == player => {
+                self.last_hurt_by = monster_type(world, *caster);
+            }
+            GameEvent::ProjectileHit { projectile, target: Some(target), .. } if *target == player => {
+                if let Ok(projectile) = world.get::<&Projectile>(*projectile) {
+                    self.last_hurt_by = monster_type(world, projectile.source);
+                }
+            }
+            GameEvent::BurnDamage { entity, .. } if *entity == player => {
+                self.last_hurt_by = None;
+            }
+            GameEvent::EntityDied { entity, .. } if *entity == player => {
+                self.killed_player = self.last_hurt_by;
+            }
+            GameEvent::AttackHit { attacker, target, .. } => {
+                self.last_hit_by.insert(*target, *attacker);
+            }
+            GameEvent::LifeDrainTick { caster, target, .. } => {
+                self.last_hit_by.insert(*target, *caster);
+            }
+            GameEvent::ProjectileHit { projectile, target: Some(target), .. } => {
+                if let Ok(projectile) = world.get::<&Projectile>(*projectile) {
+                    self.last_hit_by.insert(*target, projectile.source);
+                }
+            }
+            GameEvent::FireTrapTriggered { owner, victim, .. } => {
+                self.last_hit_by.insert(*victim, *owner);
+            }
+            // Only kills by the player's side count; burning keeps whoever last hurt it
+            GameEvent::EntityDied { entity, .. } => {
+                let killer = self.last_hit_by.remove(entity);
+                if killer.is_some_and(|killer| on_players_side(world, player, killer)) {
+                    if let Some(name) = monster_type(world, *entity) {
+                        self.kills.push(name);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Note every monster type in view
+    pub fn note_sightings(&mut self, world: &World, grid: &Grid) {
+        for (entity, (pos, health)) in world.query::<(&Position, &Health)>().iter() {
+            if health.is_dead() || !grid.get(pos.x, pos.y).is_some_and(|tile| tile.visible) {
+                continue;
+            }
+            if let Some(name) = monster_type(world, entity) {
+                if !self.met.contains(&name) {
+                    self.met.push(name);
+                }
+            }
+        }
+    }
+}
+
+/// The monster type an entity is, if it's a monster (tamed ones are companions)
+fn monster_type(world: &World, entity: Entity) -> Option<&'static str> {
+    if world.get::<&TamedBy>(entity).is_ok() {
+        return None;
+    }
+    let name = world.get::<&Name>(entity).ok()?;
+    enemies::by_name(name.0).map(|def| def.name)
+}
+
+/// Whether an entity is the player or one of their companions
+fn on_players_side(world: &World, player: Entity, entity: Entity) -> bool {
+    entity == player
+        || world.get::<&CompanionAI>(entity).is_ok_and(|ai| ai.owner == player)
+        || world.get::<&TamedBy>(entity).is_ok_and(|tamed| tamed.owner == player)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{DamageType, Player};
+
+    #[test]
+    fn test_records_sightings_kills_and_player_deaths() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(5, 5, true, true);
+        let player = world.spawn((Player, Position::new(0, 0)));
+        let mut rng = rand::thread_rng();
+        let rat = enemies::RAT.spawn(&mut world, 1, 0, &mut rng);
+        let skeleton = enemies::SKELETON.spawn(&mut world, 4, 4, &mut rng);
+        grid.tiles[4 * 5 + 4].visible = false;
+
+        let mut notes = MonsterNotes::default();
+        let mut bestiary = Bestiary::default();
+        notes.note_sightings(&world, &grid);
+        assert!(bestiary.record(&mut notes));
+        assert!(!bestiary.record(&mut notes), "nothing new the second time");
+        assert!(bestiary.entry("Rat").is_some());
+        assert!(bestiary.entry("Skeleton").is_none());
+
+        // Only deaths dealt by the player's side count, seen or not
+        let hit = |attacker, target| GameEvent::AttackHit {
+            attacker,
+            target,
+            target_pos: (0.5, 0.5),
+            damage: 3,
+            damage_type: DamageType::Physical,
+            crit: false,
+        };
+        let died = |entity| GameEvent::EntityDied { entity, position: (0.0, 0.0) };
+        let bat = enemies::GIANT_BAT.spawn(&mut world, 2, 2, &mut rng);
+        let companion = enemies::GIANT_SPIDER.spawn(&mut world, 3, 3, &mut rng);
+        world.insert_one(companion, TamedBy { owner: player }).unwrap();
+        for event in [hit(player, rat), died(rat), hit(companion, skeleton), died(skeleton), hit(rat, bat), died(bat)] {
+            notes.handle_event(&event, &world, player);
+        }
+
+        notes.handle_event(&hit(rat, player), &world, player);
+        notes.handle_event(&died(player), &world, player);
+        bestiary.record(&mut notes);
+
+        let rat_entry = bestiary.entry("Rat").unwrap();
+        assert_eq!((rat_entry.kills, rat_entry.player_deaths), (1, 1));
+        assert_eq!(bestiary.entry("Skeleton").unwrap().kills, 1);
+        assert!(bestiary.entry("Giant Bat").is_none());
+    }
+
+    #[test]
+    fn test_knowledge_grows_with_kills() {
+        let entry = |kills| BestiaryEntry { kills, player_deaths: 0 };
+        assert_eq!(entry(0).knowledge(), Knowledge::Sighted);
+        assert_eq!(entry(BESTIARY_FOUGHT_KILLS).knowledge(), Knowledge::Fought);
+        assert_eq!(entry(BESTIARY_STUDIED_KILLS).kills_to_next(), Some(BESTIARY_MASTERED_KILLS - BESTIARY_STUDIED_KILLS));
+        assert_eq!(entry(BESTIARY_MASTERED_KILLS).knowledge(), Knowledge::Mastered);
+        assert_eq!(entry(BESTIARY_MASTERED_KILLS).kills_to_next(), None);
+
+        let bestiary: Bestiary = serde_json::from_str(r#"{"monsters": {"Rat": {"kills": 2}}}"#).unwrap();
+        assert_eq!(bestiary.entry("Rat"), Some(&entry(2)));
+    }
+}
@@ -9,6 +9,9 @@ pub struct Camera {
     // Smooth movement
     velocity: Vec2,
     target_zoom: f32,
+    // Zoom limits (from the settings)
+    min_zoom: f32,
+    max_zoom: f32,
     last_mouse_world_pos: Option<Vec2>,
     // Auto-tracking
     tracking_target: Option<Vec2>,
@@ -27,6 +30,8 @@ impl Camera {
             viewport_height,
             velocity: Vec2::ZERO,
             target_zoom: CAMERA_DEFAULT_ZOOM,
+            min_zoom: CAMERA_MIN_ZOOM,
+            max_zoom: CAMERA_MAX_ZOOM,
             last_mouse_world_pos: None,
             tracking_target: None,
             manual_control: false,
@@ -95,7 +100,14 @@ impl Camera {
 
         // Apply zoom
         let zoom_factor = CAMERA_ZOOM_FACTOR.powf(delta);
-        self.target_zoom = (self.target_zoom * zoom_factor).clamp(CAMERA_MIN_ZOOM, CAMERA_MAX_ZOOM);
+        self.target_zoom = (self.target_zoom * zoom_factor).clamp(self.min_zoom, self.max_zoom);
+    }
+
+    /// Change how far in and out the player can zoom (`min <= max`)
+    pub fn set_zoom_limits(&mut self, min: f32, max: f32) {
+        self.min_zoom = min;
+        self.max_zoom = max;
+        self.target_zoom = self.target_zoom.clamp(min, max);
     }
 
     pub fn update(&mut self, dt: f32, is_dragging: bool) {
@@ -385,6 +385,30 @@ impl AnimatedSprite {
 #[derive(Debug, Clone, Copy)]
 pub struct Player;
 
+/// Marker: moving quietly (quieter footsteps, slower movement)
+#[derive(Debug, Clone, Copy)]
+pub struct Sneaking;
+
+/// Display name used when describing an entity in the message log
+#[derive(Debug, Clone, Copy)]
+pub struct Name(pub &'static str);
+
+/// Which side a creature is on. The player and their companions are always
+/// on the player's side; see `systems::factions` for how factions get along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Faction {
+    /// The player and tamed companions
+    Player,
+    /// Skeletons and other restless dead
+    Undead,
+    /// Rats and spiders
+    Vermin,
+    /// Goblins and their pets
+    Goblins,
+    /// Animals that mind their own business unless provoked
+    Wildlife,
+}
+
 /// Health component - pure data
 #[derive(Debug, Clone, Copy)]
 pub struct Health {
@@ -439,6 +463,57 @@ impl Stats {
     }
 }
 
+/// Kind of damage dealt, used for resistances and damage number colours
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    /// Weapons, claws and arrows (reduced by armor)
+    Physical,
+    /// Fireball, fire traps and burning
+    Fire,
+    /// Life drain
+    Necrotic,
+    #[allow(dead_code)] // Reserved for poisonous enemies/items
+    Poison,
+    #[allow(dead_code)] // Reserved for frost spells/items
+    Cold,
+}
+
+/// Damage multipliers per damage type (1.0 = normal, below 1.0 resists, above 1.0 is a vulnerability).
+/// Entities without this component take normal damage from everything.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resistances {
+    pub physical: f32,
+    pub fire: f32,
+    pub necrotic: f32,
+    pub poison: f32,
+    pub cold: f32,
+    /// Extra multiplier for arrows and other projectiles, on top of the damage type
+    pub projectile: f32,
+}
+
+impl Resistances {
+    /// No resistances or vulnerabilities
+    pub const NONE: Resistances = Resistances {
+        physical: 1.0,
+        fire: 1.0,
+        necrotic: 1.0,
+        poison: 1.0,
+        cold: 1.0,
+        projectile: 1.0,
+    };
+
+    /// Multiplier for a damage type
+    pub fn multiplier(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Fire => self.fire,
+            DamageType::Necrotic => self.necrotic,
+            DamageType::Poison => self.poison,
+            DamageType::Cold => self.cold,
+        }
+    }
+}
+
 /// Experience component - pure data for XP and level
 #[derive(Debug, Clone, Copy)]
 pub struct Experience {
@@ -483,6 +558,15 @@ pub enum ItemType {
     FireTrap,
     // Ammunition
     Arrow,
+    // Armor
+    LeatherArmor,
+    ChainMail,
+    Helmet,
+    Shield,
+    // Accessories
+    RingOfStrength,
+    RingOfAgility,
+    AmuletOfWisdom,
 }
 
 impl ItemType {
@@ -492,6 +576,47 @@ impl ItemType {
     }
 }
 
+/// Body slot an armor piece or accessory is worn in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArmorSlot {
+    Body,
+    Head,
+    Shield,
+    Ring,
+    Amulet,
+}
+
+impl ArmorSlot {
+    /// All slots, in the order they are shown in the inventory
+    pub const ALL: [ArmorSlot; 5] = [
+        ArmorSlot::Head,
+        ArmorSlot::Body,
+        ArmorSlot::Shield,
+        ArmorSlot::Ring,
+        ArmorSlot::Amulet,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArmorSlot::Body => "Body",
+            ArmorSlot::Head => "Head",
+            ArmorSlot::Shield => "Shield",
+            ArmorSlot::Ring => "Ring",
+            ArmorSlot::Amulet => "Amulet",
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            ArmorSlot::Body => 0,
+            ArmorSlot::Head => 1,
+            ArmorSlot::Shield => 2,
+            ArmorSlot::Ring => 3,
+            ArmorSlot::Amulet => 4,
+        }
+    }
+}
+
 // =============================================================================
 // STATUS EFFECTS
 // =============================================================================
@@ -658,6 +783,12 @@ pub enum ActionType {
     ShootCripplingShot { target_x: i32, target_y: i32 },
     /// Recovery after shooting (auto-queued, allows arrow to fly)
     Recover,
+    /// Support caster: restore health to an ally
+    HealAlly { target: Entity, amount: i32 },
+    /// Support caster: grant a status effect to an ally
+    BuffAlly { target: Entity, effect: EffectType, duration: f32 },
+    /// Give the entity's companions a new order (only `companion`, if set)
+    CommandCompanions { order: CompanionOrder, companion: Option<Entity> },
 }
 
 impl ActionType {
@@ -695,6 +826,9 @@ impl ActionType {
             ActionType::PlaceSnareTrap { .. } => SNARE_TRAP_ENERGY_COST,
             ActionType::ShootCripplingShot { .. } => CRIPPLING_SHOT_ENERGY_COST,
             ActionType::Recover => 0, // Free action, just takes time
+            ActionType::HealAlly { .. } => 1,
+            ActionType::BuffAlly { .. } => 1,
+            ActionType::CommandCompanions { .. } => 0, // Shouting an order is free, just takes time
         }
     }
 }
@@ -748,6 +882,18 @@ impl Actor {
     }
 }
 
+/// How alert an enemy is to intruders.
+/// Asleep and unaware enemies must notice a target before they react to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Awareness {
+    /// Dozing in place; notices very little
+    Asleep,
+    /// Awake but off guard (wandering, not expecting trouble)
+    Unaware,
+    /// On guard - sees whatever is in sight
+    Alert,
+}
+
 /// AI behavior state
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AIState {
@@ -784,6 +930,10 @@ pub struct ChaseAI {
     pub ranged_min: i32,
     /// Maximum range for ranged attack (0 = melee only)
     pub ranged_max: i32,
+    /// Where the last noise worth investigating came from
+    pub heard_noise: Option<(i32, i32)>,
+    /// Whether the enemy has noticed anyone yet
+    pub awareness: Awareness,
 }
 
 impl ChaseAI {
@@ -795,6 +945,8 @@ impl ChaseAI {
             current_target: None,
             ranged_min: 0,
             ranged_max: 0,
+            heard_noise: None,
+            awareness: Awareness::Alert,
         }
     }
 
@@ -807,9 +959,16 @@ impl ChaseAI {
             current_target: None,
             ranged_min,
             ranged_max,
+            heard_noise: None,
+            awareness: Awareness::Alert,
         }
     }
 
+    /// Asleep or unaware (open to sneak attacks)
+    pub fn is_unaware(&self) -> bool {
+        self.awareness != Awareness::Alert
+    }
+
     /// Add threat for a specific entity. Creates entry if not present.
     pub fn add_threat(&mut self, target: Entity, amount: f32) {
         if let Some(entry) = self.threat_table.iter_mut().find(|e| e.entity == target) {
@@ -857,6 +1016,55 @@ impl ChaseAI {
     }
 }
 
+/// A reusable piece of enemy behavior.
+///
+/// Enemy definitions list tactics in priority order. Each turn the first
+/// tactic with something to do picks the action; if none does, the default
+/// chase/wander logic of `ChaseAI` runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tactic {
+    /// Step away from a visible target that comes closer than `min_distance`
+    KeepDistance { min_distance: i32 },
+    /// Hide in tall grass and ignore targets until one comes within `reveal_range`
+    Ambush { reveal_range: i32 },
+    /// While idle, walk a loop of waypoints around the spawn point instead of wandering
+    Patrol { radius: i32 },
+    /// Hang back out of reach until `min_allies` allies within `radius` are also chasing
+    WaitForAllies { min_allies: usize, radius: i32 },
+    /// Heal the most wounded ally (or self) in range
+    HealAllies { range: i32, amount: i32, cooldown: f32 },
+    /// Grant a status effect to an ally in combat that doesn't have it yet
+    BuffAllies { range: i32, effect: EffectType, duration: f32, cooldown: f32 },
+}
+
+/// Per-entity state for the tactics an enemy was spawned with
+#[derive(Debug, Clone)]
+pub struct Behavior {
+    pub tactics: &'static [Tactic],
+    /// Spawn point, used as the patrol centre
+    pub home: (i32, i32),
+    /// Patrol waypoints, picked from the floor layout on first use
+    pub patrol_route: Vec<(i32, i32)>,
+    pub patrol_index: usize,
+    /// An ambusher that has sprung stays in the fight until it loses its target
+    pub revealed: bool,
+    /// Remaining cooldown (seconds) before the next heal or buff
+    pub support_cooldown: f32,
+}
+
+impl Behavior {
+    pub fn new(tactics: &'static [Tactic], home: (i32, i32)) -> Self {
+        Self {
+            tactics,
+            home,
+            patrol_route: Vec::new(),
+            patrol_index: 0,
+            revealed: false,
+            support_cooldown: 0.0,
+        }
+    }
+}
+
 /// Visual position for smooth interpolation (separate from logical grid Position)
 #[derive(Debug, Clone, Copy)]
 pub struct VisualPosition {
@@ -1060,20 +1268,22 @@ pub struct Equipment {
     pub weapon: Option<EquippedWeapon>,
     /// Additional ranged weapon (used by enemies who have both melee and ranged)
     pub enemy_ranged: Option<RangedWeapon>,
+    /// Worn armor and accessories, one per slot (properties in item_defs::ARMOR_DEFS)
+    armor: [Option<ItemType>; 5],
 }
 
 impl Equipment {
     pub fn with_melee(weapon: Weapon) -> Self {
-        Self { weapon: Some(EquippedWeapon::Melee(weapon)), enemy_ranged: None }
+        Self::with_equipped(EquippedWeapon::Melee(weapon))
     }
 
     pub fn with_ranged(ranged: RangedWeapon) -> Self {
-        Self { weapon: Some(EquippedWeapon::Ranged(ranged)), enemy_ranged: None }
+        Self::with_equipped(EquippedWeapon::Ranged(ranged))
     }
 
     /// Create equipment with an already-constructed EquippedWeapon
     pub fn with_equipped(weapon: EquippedWeapon) -> Self {
-        Self { weapon: Some(weapon), enemy_ranged: None }
+        Self { weapon: Some(weapon), enemy_ranged: None, armor: [None; 5] }
     }
 
     /// Create equipment for enemies that can use both melee (claws) and ranged (bow)
@@ -1081,12 +1291,33 @@ impl Equipment {
         Self {
             weapon: Some(EquippedWeapon::Melee(melee)),
             enemy_ranged: Some(ranged),
+            armor: [None; 5],
         }
     }
 
     /// Create equipment with just a melee weapon (for melee-only enemies)
     pub fn with_weapon(weapon: Weapon) -> Self {
-        Self { weapon: Some(EquippedWeapon::Melee(weapon)), enemy_ranged: None }
+        Self::with_melee(weapon)
+    }
+
+    /// Armor piece worn in a slot, if any
+    pub fn armor_in(&self, slot: ArmorSlot) -> Option<ItemType> {
+        self.armor[slot.index()]
+    }
+
+    /// Wear an armor piece in a slot, returning whatever was worn there before
+    pub fn equip_armor(&mut self, slot: ArmorSlot, item: ItemType) -> Option<ItemType> {
+        self.armor[slot.index()].replace(item)
+    }
+
+    /// Take off the armor piece in a slot
+    pub fn unequip_armor(&mut self, slot: ArmorSlot) -> Option<ItemType> {
+        self.armor[slot.index()].take()
+    }
+
+    /// All worn armor pieces
+    pub fn worn_armor(&self) -> impl Iterator<Item = ItemType> + '_ {
+        self.armor.iter().flatten().copied()
     }
 
     /// Check if a bow is equipped (either in main slot or enemy_ranged)
@@ -1195,6 +1426,8 @@ pub struct Projectile {
     pub on_hit_effect: Option<(EffectType, f32)>,
     /// Whether this projectile hit an enemy (used for arrow recovery)
     pub hit_enemy: bool,
+    /// Shooter's Agility for hit rolls against each target in the path (None = always hits)
+    pub shooter_agility: Option<i32>,
 }
 
 /// Marker component for projectiles (for queries)
@@ -1426,6 +1659,37 @@ pub struct TamedBy {
     pub owner: Entity,
 }
 
+/// A standing order the owner has given a companion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompanionOrder {
+    /// Keep close to the owner and defend them (the default)
+    Follow,
+    /// Hold the current spot, only fighting what comes adjacent
+    Stay,
+    /// Go after one target, ignoring everything else
+    Attack { target: Entity },
+    /// Hold a spot and fight enemies that come near it
+    Guard { x: i32, y: i32 },
+    /// Follow the owner but never start a fight
+    Passive,
+    /// Drop everything and come straight back to the owner
+    Return,
+}
+
+impl CompanionOrder {
+    /// Short label for the party panel and messages
+    pub fn label(&self) -> &'static str {
+        match self {
+            CompanionOrder::Follow => "Follow",
+            CompanionOrder::Stay => "Stay",
+            CompanionOrder::Attack { .. } => "Attack",
+            CompanionOrder::Guard { .. } => "Guard",
+            CompanionOrder::Passive => "Passive",
+            CompanionOrder::Return => "Return",
+        }
+    }
+}
+
 /// AI for tamed companions - defensive mode, attacks enemies threatening owner
 #[derive(Debug, Clone)]
 pub struct CompanionAI {
@@ -1435,9 +1699,26 @@ pub struct CompanionAI {
     pub follow_distance: i32,
     /// Per-target threat tracking (populated by being attacked or owner combat)
     pub threat_table: Vec<ThreatEntry>,
+    /// Current order from the owner
+    pub order: CompanionOrder,
+    /// Spot to hold for Stay and Guard orders
+    pub post: Option<(i32, i32)>,
+    /// The enemy currently being fought, if any
+    pub current_target: Option<Entity>,
 }
 
 impl CompanionAI {
+    pub fn new(owner: Entity, follow_distance: i32) -> Self {
+        Self {
+            owner,
+            follow_distance,
+            threat_table: Vec::new(),
+            order: CompanionOrder::Follow,
+            post: None,
+            current_target: None,
+        }
+    }
+
     /// Add threat for a specific entity. Creates entry if not present.
     pub fn add_threat(&mut self, target: Entity, amount: f32) {
         if let Some(entry) = self.threat_table.iter_mut().find(|e| e.entity == target) {
@@ -0,0 +1,42 @@
+//! Per-user config files (key bindings and the like).
+//!
+//! Files live in the platform's config directory under `grid-roguelike/`:
+//! `$XDG_CONFIG_HOME` (or `~/.config`) on Linux, `~/Library/Application Support`
+//! on macOS and `%APPDATA%` on Windows.
+
+use std::path::PathBuf;
+
+const APP_DIR: &str = "grid-roguelike";
+
+/// The directory our config files go in, if the platform has one
+pub fn config_dir() -> Option<PathBuf> {
+    let base = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    base.map(|dir| dir.join(APP_DIR))
+}
+
+/// Read a config file. `Ok(None)` if it doesn't exist yet.
+pub fn read(file: &str) -> Result<Option<String>, String> {
+    let path = config_dir().ok_or("No config directory")?.join(file);
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => Ok(Some(contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+/// Write a config file, creating the config directory if needed
+pub fn write(file: &str, contents: &str) -> Result<(), String> {
+    let dir = config_dir().ok_or("No config directory")?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let path = dir.join(file);
+    std::fs::write(&path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
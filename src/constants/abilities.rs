@@ -17,6 +17,12 @@ pub const TAME_ENERGY_COST: i32 = 1;
 pub const TAME_RANGE: i32 = 5;
 pub const TAME_DURATION: f32 = 6.0;
 
+// Druid - Companion commands
+pub const COMPANION_COMMAND_DURATION: f32 = 0.3;
+pub const COMPANION_GUARD_RADIUS: i32 = 4;
+/// How far a following companion looks for enemies fighting its owner
+pub const COMPANION_ENGAGE_RADIUS: i32 = 12;
+
 // Druid - Barkskin
 pub const BARKSKIN_COOLDOWN: f32 = 45.0;
 pub const BARKSKIN_ENERGY_COST: i32 = 1;
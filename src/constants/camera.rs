@@ -7,6 +7,10 @@ pub const CAMERA_DEFAULT_ZOOM: f32 = 48.0;
 pub const CAMERA_MIN_ZOOM: f32 = 16.0;
 /// Maximum zoom level (384px = 12x native for very close-up view)
 pub const CAMERA_MAX_ZOOM: f32 = 384.0;
+/// Lowest zoom limit the settings accept
+pub const CAMERA_ZOOM_LIMIT_FLOOR: f32 = 8.0;
+/// Highest zoom limit the settings accept
+pub const CAMERA_ZOOM_LIMIT_CEILING: f32 = 512.0;
 /// Zoom speed multiplier per scroll unit
 pub const CAMERA_ZOOM_FACTOR: f32 = 1.1;
 /// Smoothing factor for camera tracking (lower = smoother)
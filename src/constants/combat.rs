@@ -8,3 +8,19 @@ pub const COMBAT_DAMAGE_MAX_MULT: f32 = 1.2;
 pub const COMBAT_CRIT_CHANCE: f32 = 0.1;
 /// Critical hit damage multiplier
 pub const COMBAT_CRIT_MULTIPLIER: f32 = 1.1;
+
+// Accuracy and evasion
+/// Hit chance of an attacker with 10 Agility against a target with 10 Agility
+pub const COMBAT_BASE_HIT_CHANCE: f32 = 0.8;
+/// Hit chance gained per point of attacker Agility above 10
+pub const COMBAT_ACCURACY_PER_AGILITY: f32 = 0.02;
+/// Hit chance removed per point of target Agility above 10 (clumsy targets are easier to hit)
+pub const COMBAT_EVASION_PER_AGILITY: f32 = 0.025;
+/// Lowest possible hit chance
+pub const COMBAT_MIN_HIT_CHANCE: f32 = 0.05;
+/// Highest possible hit chance
+pub const COMBAT_MAX_HIT_CHANCE: f32 = 0.95;
+/// Ranged attacks lose no accuracy up to this distance (tiles)
+pub const RANGED_ACCURACY_FALLOFF_START: i32 = 3;
+/// Hit chance lost per tile beyond the falloff start
+pub const RANGED_ACCURACY_FALLOFF_PER_TILE: f32 = 0.04;
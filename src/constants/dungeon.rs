@@ -10,5 +10,13 @@ pub const DUNGEON_ROOM_MARGIN: i32 = 1;
 pub const DUNGEON_DEFAULT_WIDTH: usize = 50;
 /// Default dungeon height
 pub const DUNGEON_DEFAULT_HEIGHT: usize = 50;
+/// Tiles added to each floor dimension per floor of depth
+pub const DUNGEON_GROWTH_PER_FLOOR: usize = 10;
+/// Largest width/height a standard floor can grow to
+pub const DUNGEON_MAX_STANDARD_SIZE: usize = 120;
+/// Width/height of the first floor when huge floors are enabled
+pub const DUNGEON_HUGE_BASE_SIZE: usize = 300;
+/// Largest width/height a huge floor can grow to
+pub const DUNGEON_MAX_HUGE_SIZE: usize = 500;
 /// Chance for a room to have a special theme (Overgrown, Flooded, etc.)
 pub const THEMED_ROOM_CHANCE: f32 = 0.25;
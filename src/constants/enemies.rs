@@ -3,6 +3,36 @@
 /// Maximum distance from player for AI to be active (Manhattan distance)
 /// Enemies further than this skip their turns entirely for performance
 pub const AI_ACTIVE_RADIUS: i32 = 25;
+/// How many steps from the player the shared navigation maps reach
+/// (enemies further along the path fall back to their own A*)
+pub const NAV_MAP_MAX_DISTANCE: i32 = 40;
+/// How strongly the flee map favours distance over the nearest escape
+/// (above 1.0, fleeing enemies will pass closer to the player to reach open space)
+pub const FLEE_MAP_COEFFICIENT: f32 = 1.2;
+
+// OFF-SCREEN SIMULATION
+/// Game seconds between coarse updates of dormant enemies
+pub const OFFSCREEN_TICK_INTERVAL: f32 = 2.0;
+/// Most dormant enemies updated per coarse tick (keeps the per-turn cost bounded)
+pub const OFFSCREEN_ACTORS_PER_TICK: usize = 24;
+/// Tiles a dormant enemy covers per game second while roaming or hunting
+pub const OFFSCREEN_TILES_PER_SECOND: f32 = 0.5;
+/// Most tiles a dormant enemy moves in a single coarse update
+pub const OFFSCREEN_MAX_STEPS: usize = 8;
+/// Most tiles a coarse pathfinding search may expand before giving up
+pub const OFFSCREEN_PATH_NODE_LIMIT: usize = 400;
+/// Number of recent player positions remembered for hunters to follow
+pub const OFFSCREEN_TRAIL_LENGTH: usize = 128;
+/// How close (Manhattan) a hunter must be to the player's trail to pick up the scent
+pub const OFFSCREEN_SCENT_RADIUS: i32 = 10;
+/// Chance per update that an idle enemy without a goal sets off somewhere
+pub const OFFSCREEN_ROAM_CHANCE: f32 = 0.4;
+/// Chance that a roaming enemy heads for an ally instead of another room
+pub const OFFSCREEN_REGROUP_CHANCE: f32 = 0.35;
+/// How far (Manhattan) a roaming enemy looks for rooms or allies to head to
+pub const OFFSCREEN_ROAM_RADIUS: i32 = 30;
+/// Radius a regrouping enemy first looks for allies in, doubling up to the roam radius
+pub const OFFSCREEN_REGROUP_FIRST_RADIUS: i32 = 4;
 
 // SKELETON
 /// Number of skeletons to spawn
@@ -23,6 +53,16 @@ pub const SKELETON_INTELLIGENCE: i32 = 1;
 pub const SKELETON_AGILITY: i32 = 3;
 /// Skeleton attack damage
 pub const SKELETON_DAMAGE: i32 = 6;
+/// Skeleton damage multiplier from arrows (bones let arrows pass through)
+pub const SKELETON_PROJECTILE_MULT: f32 = 0.5;
+/// Skeleton damage multiplier from fire (dry bones burn)
+pub const SKELETON_FIRE_MULT: f32 = 1.5;
+/// Chance for a skeleton to spawn wearing a helmet
+pub const SKELETON_HELMET_CHANCE: f32 = 0.3;
+/// Chance for a skeleton to spawn carrying a shield
+pub const SKELETON_SHIELD_CHANCE: f32 = 0.2;
+/// Chance for a skeleton to spawn wearing chain mail
+pub const SKELETON_CHAIN_MAIL_CHANCE: f32 = 0.1;
 
 // RAT
 /// Number of rats to spawn
@@ -43,6 +83,8 @@ pub const RAT_INTELLIGENCE: i32 = 1;
 pub const RAT_AGILITY: i32 = 8;
 /// Rat attack damage (weak bite)
 pub const RAT_DAMAGE: i32 = 5;
+/// Rat damage multiplier from fire (fur catches easily)
+pub const RAT_FIRE_MULT: f32 = 1.5;
 
 // SKELETON ARCHER
 /// Number of skeleton archers to spawn
@@ -69,8 +111,121 @@ pub const SKELETON_ARCHER_BOW_DAMAGE: i32 = 8;
 pub const SKELETON_ARCHER_MIN_RANGE: i32 = 2;
 /// Maximum range for skeleton archer bow
 pub const SKELETON_ARCHER_MAX_RANGE: i32 = 8;
+/// Chance for a skeleton archer to spawn wearing leather armor
+pub const SKELETON_ARCHER_LEATHER_ARMOR_CHANCE: f32 = 0.25;
 /// Cooldown between ranged attacks (seconds) - total time between shots ~3s
 pub const RANGED_ATTACK_COOLDOWN: f32 = 1.5;
+/// Longest bow range of any enemy: how far to look for archers covering a tile.
+/// Raise it when adding a longer-ranged enemy.
+pub const MAX_ENEMY_BOW_RANGE: i32 = SKELETON_ARCHER_MAX_RANGE;
+
+// GIANT SPIDER
+/// Number of giant spiders to spawn (placed in tall grass when possible)
+pub const GIANT_SPIDER_SPAWN_COUNT: usize = 8;
+/// Giant spider health
+pub const GIANT_SPIDER_HEALTH: i32 = 25;
+/// Giant spider maximum energy pool
+pub const GIANT_SPIDER_MAX_ENERGY: i32 = 3;
+/// Giant spider action speed (quick to pounce)
+pub const GIANT_SPIDER_SPEED: f32 = 1.4;
+/// Giant spider sight radius once it has sprung its ambush
+pub const GIANT_SPIDER_SIGHT_RADIUS: i32 = 7;
+/// Giant spider strength
+pub const GIANT_SPIDER_STRENGTH: i32 = 6;
+/// Giant spider intelligence
+pub const GIANT_SPIDER_INTELLIGENCE: i32 = 1;
+/// Giant spider agility
+pub const GIANT_SPIDER_AGILITY: i32 = 9;
+/// Giant spider bite damage
+pub const GIANT_SPIDER_DAMAGE: i32 = 7;
+/// Giant spider damage multiplier from fire
+pub const GIANT_SPIDER_FIRE_MULT: f32 = 1.5;
+
+// GOBLIN SHAMAN
+/// Number of goblin shamans to spawn
+pub const GOBLIN_SHAMAN_SPAWN_COUNT: usize = 4;
+/// Goblin shaman health
+pub const GOBLIN_SHAMAN_HEALTH: i32 = 25;
+/// Goblin shaman maximum energy pool
+pub const GOBLIN_SHAMAN_MAX_ENERGY: i32 = 3;
+/// Goblin shaman action speed
+pub const GOBLIN_SHAMAN_SPEED: f32 = 1.0;
+/// Goblin shaman sight radius
+pub const GOBLIN_SHAMAN_SIGHT_RADIUS: i32 = 8;
+/// Goblin shaman strength
+pub const GOBLIN_SHAMAN_STRENGTH: i32 = 4;
+/// Goblin shaman intelligence
+pub const GOBLIN_SHAMAN_INTELLIGENCE: i32 = 10;
+/// Goblin shaman agility
+pub const GOBLIN_SHAMAN_AGILITY: i32 = 6;
+/// Goblin shaman melee damage (a staff, not much of a fighter)
+pub const GOBLIN_SHAMAN_DAMAGE: i32 = 3;
+
+// GIANT BAT
+/// Number of giant bats to spawn (neutral wildlife)
+pub const GIANT_BAT_SPAWN_COUNT: usize = 6;
+/// Giant bat health
+pub const GIANT_BAT_HEALTH: i32 = 15;
+/// Giant bat maximum energy pool
+pub const GIANT_BAT_MAX_ENERGY: i32 = 3;
+/// Giant bat action speed (flits about quickly)
+pub const GIANT_BAT_SPEED: f32 = 1.6;
+/// Giant bat sight radius
+pub const GIANT_BAT_SIGHT_RADIUS: i32 = 6;
+/// Giant bat strength
+pub const GIANT_BAT_STRENGTH: i32 = 4;
+/// Giant bat intelligence
+pub const GIANT_BAT_INTELLIGENCE: i32 = 1;
+/// Giant bat agility (hard to hit)
+pub const GIANT_BAT_AGILITY: i32 = 10;
+/// Giant bat bite damage
+pub const GIANT_BAT_DAMAGE: i32 = 4;
+
+// FACTION INFIGHTING
+/// Chance per turn that a confused creature lashes out at whoever is next to it
+pub const CONFUSED_LASH_OUT_CHANCE: f32 = 0.4;
+
+// BEHAVIOR ARCHETYPES
+/// Kiters step back when a target gets closer than this (Chebyshev distance)
+pub const KITER_MIN_DISTANCE: i32 = 3;
+/// Lurking ambushers only notice targets within this range
+pub const AMBUSH_REVEAL_RANGE: i32 = 2;
+/// How far an ambusher looks for tall grass to hide in
+pub const AMBUSH_HIDE_SEARCH_RADIUS: i32 = 6;
+/// Guards patrol waypoints within this distance of their post
+pub const GUARD_PATROL_RADIUS: i32 = 6;
+/// Number of waypoints in a patrol loop (besides the post itself)
+pub const PATROL_WAYPOINT_COUNT: usize = 3;
+/// Random tiles tried when picking patrol waypoints
+pub const PATROL_WAYPOINT_ATTEMPTS: usize = 30;
+/// Swarmers hold back until this many allies are chasing nearby
+pub const SWARM_MIN_ALLIES: usize = 2;
+/// Radius in which a swarmer counts its allies
+pub const SWARM_ALLY_RADIUS: i32 = 5;
+/// Swarmers waiting for allies keep at least this far from the target
+pub const SWARM_HOLD_DISTANCE: i32 = 3;
+/// Support casters reach allies within this range
+pub const SUPPORT_RANGE: i32 = 5;
+/// Health restored per support heal
+pub const SUPPORT_HEAL_AMOUNT: i32 = 10;
+/// Seconds between support heals
+pub const SUPPORT_HEAL_COOLDOWN: f32 = 6.0;
+/// Allies below this fraction of max health are healed
+pub const SUPPORT_HEAL_THRESHOLD: f32 = 0.6;
+/// Duration of the protective ward a support caster places on allies
+pub const SUPPORT_WARD_DURATION: f32 = 8.0;
+/// Seconds between protective wards
+pub const SUPPORT_WARD_COOLDOWN: f32 = 10.0;
+
+// PACK TACTICS
+/// Idle allies within this distance are alerted when an enemy starts chasing
+pub const PACK_ALERT_RADIUS: i32 = 6;
+/// Threat an alerted ally gains on the spotted target
+pub const PACK_ALERT_THREAT: f32 = 1.0;
+/// Allies within this distance count as the pack (retreating, melee line)
+pub const PACK_REGROUP_RADIUS: i32 = 8;
+/// Enemies below this fraction of max health fall back to their pack
+pub const PACK_RETREAT_HEALTH: f32 = 0.3;
 
 /// Gold dropped by enemies (min)
 pub const ENEMY_GOLD_DROP_MIN: u32 = 1;
@@ -26,3 +26,23 @@ pub const UNARMED_DAMAGE: i32 = 2;
 pub const PLAYER_HP_REGEN_AMOUNT: i32 = 1;
 /// Seconds between each player HP regen event
 pub const PLAYER_HP_REGEN_INTERVAL: f32 = 10.0;
+/// Longest the player will rest in one go (game-time seconds)
+pub const REST_MAX_DURATION: f32 = 1000.0;
+/// Durations offered by the wait prompt (game-time seconds)
+pub const WAIT_CHOICES: [f32; 4] = [10.0, 30.0, 60.0, 300.0];
+
+/// Extra path cost (in straight steps) for walking onto a burning tile
+pub const PATH_FIRE_PENALTY: i32 = 20;
+/// Extra path cost for walking onto a known trap that would go off
+pub const PATH_TRAP_PENALTY: i32 = 20;
+/// Extra path cost for wading through deep water
+pub const PATH_WATER_PENALTY: i32 = 2;
+/// Extra path cost for each tile within range of a hostile archer
+pub const PATH_ARCHER_RANGE_PENALTY: i32 = 1;
+
+/// Kills of a monster type before the bestiary shows its health and damage
+pub const BESTIARY_FOUGHT_KILLS: u32 = 1;
+/// Kills before the bestiary shows its speed, sight and ranged attack
+pub const BESTIARY_STUDIED_KILLS: u32 = 5;
+/// Kills before the bestiary shows its resistances and tactics
+pub const BESTIARY_MASTERED_KILLS: u32 = 15;
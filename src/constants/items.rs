@@ -71,3 +71,31 @@ pub const STARTING_ARROW_COUNT: u32 = 20;
 pub const ARROW_STACK_MAX: u32 = 50;
 /// Arrows in a bundle pickup
 pub const ARROW_BUNDLE_COUNT: u32 = 10;
+
+// Armor and accessories
+/// Leather armor weight in kg
+pub const LEATHER_ARMOR_WEIGHT: f32 = 5.0;
+/// Leather armor defense (flat damage reduction per hit)
+pub const LEATHER_ARMOR_DEFENSE: i32 = 1;
+/// Chain mail weight in kg
+pub const CHAIN_MAIL_WEIGHT: f32 = 10.0;
+/// Chain mail defense (flat damage reduction per hit)
+pub const CHAIN_MAIL_DEFENSE: i32 = 2;
+/// Chain mail agility penalty while worn
+pub const CHAIN_MAIL_AGILITY_PENALTY: i32 = -1;
+/// Helmet weight in kg
+pub const HELMET_WEIGHT: f32 = 1.5;
+/// Helmet defense (flat damage reduction per hit)
+pub const HELMET_DEFENSE: i32 = 1;
+/// Shield weight in kg
+pub const SHIELD_WEIGHT: f32 = 4.0;
+/// Shield defense (flat damage reduction per hit)
+pub const SHIELD_DEFENSE: i32 = 1;
+/// Ring weight in kg
+pub const RING_WEIGHT: f32 = 0.05;
+/// Stat bonus granted by a ring
+pub const RING_STAT_BONUS: i32 = 2;
+/// Amulet weight in kg
+pub const AMULET_WEIGHT: f32 = 0.1;
+/// Stat bonus granted by an amulet
+pub const AMULET_STAT_BONUS: i32 = 2;
@@ -12,6 +12,7 @@ mod effects;
 mod enemies;
 mod gameplay;
 mod items;
+mod noise;
 mod time;
 mod ui;
 
@@ -25,5 +26,6 @@ pub use effects::*;
 pub use enemies::*;
 pub use gameplay::*;
 pub use items::*;
+pub use noise::*;
 pub use time::*;
 pub use ui::*;
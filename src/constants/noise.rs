@@ -0,0 +1,54 @@
+//! Noise and stealth constants.
+//!
+//! Loudness is measured in tiles: a noise of loudness 6 is heard up to six
+//! open tiles away. Walls and closed doors eat into it.
+//!
+//! Perception covers how sleeping and unaware enemies notice intruders.
+
+/// Footsteps while walking normally
+pub const NOISE_FOOTSTEP: i32 = 4;
+/// Footsteps while sneaking
+pub const NOISE_SNEAK_FOOTSTEP: i32 = 1;
+/// Opening or closing a door
+pub const NOISE_DOOR: i32 = 6;
+/// Forcing open a chest, coffin or barrel
+pub const NOISE_BASH: i32 = 7;
+/// A melee swing, hit or miss
+pub const NOISE_COMBAT: i32 = 9;
+/// A thrown potion shattering
+pub const NOISE_POTION_SHATTER: i32 = 6;
+/// The necromancer's Fear shriek
+pub const NOISE_FEAR: i32 = 10;
+/// A fireball going off
+pub const NOISE_EXPLOSION: i32 = 16;
+
+/// Loudness lost passing through a wall tile
+pub const NOISE_WALL_DAMPENING: i32 = 6;
+/// Loudness lost passing through a closed door
+pub const NOISE_DOOR_DAMPENING: i32 = 4;
+
+/// Movement duration multiplier while sneaking (higher = slower)
+pub const SNEAK_MOVE_DURATION_MULTIPLIER: f32 = 1.6;
+
+// =============================================================================
+// PERCEPTION
+// =============================================================================
+
+/// Chance that an unaware enemy spawns asleep
+pub const ENEMY_ASLEEP_CHANCE: f32 = 0.25;
+/// Chance that an enemy (not asleep) spawns unaware
+pub const ENEMY_UNAWARE_CHANCE: f32 = 0.35;
+/// Per-turn chance to notice a fully lit, adjacent target
+pub const PERCEPTION_BASE_CHANCE: f32 = 0.7;
+/// Illumination at which a target counts as fully lit
+pub const PERCEPTION_FULL_LIGHT: f32 = 1.5;
+/// Lowest light factor (targets in darkness can still be made out up close)
+pub const PERCEPTION_MIN_LIGHT: f32 = 0.2;
+/// Perception multiplier while asleep
+pub const PERCEPTION_ASLEEP_MULTIPLIER: f32 = 0.15;
+/// Perception multiplier against a sneaking target
+pub const PERCEPTION_SNEAK_MULTIPLIER: f32 = 0.5;
+/// Remaining loudness a noise needs to wake a sleeping enemy
+pub const NOISE_WAKE_THRESHOLD: i32 = 2;
+/// Damage multiplier for hitting an enemy that hasn't noticed you
+pub const SNEAK_ATTACK_MULTIPLIER: f32 = 2.0;
@@ -5,5 +5,26 @@ pub const WINDOW_DEFAULT_WIDTH: u32 = 1280;
 /// Default window height
 pub const WINDOW_DEFAULT_HEIGHT: u32 = 720;
 
+/// Default master volume (0.0 - 1.0)
+pub const DEFAULT_VOLUME: f32 = 0.5;
+/// Smallest UI scale the settings accept
+pub const UI_SCALE_MIN: f32 = 0.75;
+/// Largest UI scale the settings accept
+pub const UI_SCALE_MAX: f32 = 2.0;
+
 /// Click drag threshold (pixels) to distinguish click from drag
 pub const CLICK_DRAG_THRESHOLD: f32 = 5.0;
+
+/// Messages kept in the message log history
+pub const MESSAGE_LOG_CAPACITY: usize = 500;
+/// Lines shown in the compact message log panel
+pub const MESSAGE_LOG_VISIBLE_LINES: usize = 6;
+
+/// Size of the corner minimap (points)
+pub const MINIMAP_SIZE: f32 = 180.0;
+/// Tiles shown across the corner minimap (bigger floors scroll with the player)
+pub const MINIMAP_VIEW_TILES: usize = 64;
+/// Closest zoom on the full-screen map (points per tile)
+pub const MAP_MAX_ZOOM: f32 = 48.0;
+/// Furthest zoom on the full-screen map (points per tile)
+pub const MAP_MIN_ZOOM: f32 = 1.0;
@@ -0,0 +1,361 @@
+//! Dijkstra maps (flow fields) for mass pathfinding.
+//!
+//! A Dijkstra map stores, for every tile, the cost of walking to the nearest
+//! goal. Any number of entities can then read their next step from it in O(1)
+//! by looking at the neighbouring tile with the lowest value, instead of each
+//! running its own A* search.
+//!
+//! `NavigationMaps` holds the shared maps rebuilt once per player move:
+//! - toward the player, for everything chasing them
+//! - away from the player, for feared enemies (the "toward" map scaled by a
+//!   negative factor and relaxed again, so fleeing prefers open space over
+//!   running into dead ends)
+//!
+//! The shared maps are weighted for one cost profile (see `systems::hazards`);
+//! entities that path differently fall back to their own A* search.
+//!
+//! Other maps (toward items or stairs) can be built on demand with `DijkstraMap::build`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+use crate::constants::{FLEE_MAP_COEFFICIENT, NAV_MAP_MAX_DISTANCE};
+use crate::grid::Grid;
+use crate::pathfinding::{self, CostProfile, NEIGHBORS, STEP_COST};
+use crate::tile::TileType;
+
+/// Value of tiles the map didn't reach
+const UNREACHED: i32 = i32::MAX;
+
+/// Walking cost from every reached tile to the nearest goal, in the same units
+/// as `pathfinding` (moves in eight directions, no corner cutting).
+/// `DijkstraMap::default()` is an empty map (no tile reached).
+#[derive(Debug, Clone, Default)]
+pub struct DijkstraMap {
+    width: usize,
+    height: usize,
+    values: Vec<i32>,
+    /// Indices with a value, so rebuilding only resets what was reached
+    touched: Vec<usize>,
+    /// Tiles the map leads to (may be stepped onto even when occupied)
+    goals: Vec<(i32, i32)>,
+}
+
+impl DijkstraMap {
+    /// Rebuild toward `goals`, expanding no further than `max_cost`.
+    /// Tiles in `blocked` and stairs (other than goals) are never entered, and
+    /// entering a tile costs `tile_cost(tile)` on top of the step itself.
+    pub fn build(
+        &mut self,
+        grid: &Grid,
+        goals: &[(i32, i32)],
+        blocked: &HashSet<(i32, i32)>,
+        max_cost: i32,
+        tile_cost: &dyn Fn((i32, i32)) -> i32,
+    ) {
+        self.build_within(grid, goals, max_cost, |pos| !blocked.contains(&pos), tile_cost);
+    }
+
+    /// Like `build`, but only enters tiles for which `can_enter` holds
+    /// (e.g. to keep the player's maps on tiles they have seen).
+    pub fn build_within(
+        &mut self,
+        grid: &Grid,
+        goals: &[(i32, i32)],
+        max_cost: i32,
+        can_enter: impl Fn((i32, i32)) -> bool,
+        tile_cost: &dyn Fn((i32, i32)) -> i32,
+    ) {
+        self.reset(grid);
+        self.goals = goals.to_vec();
+        let seeds: Vec<((i32, i32), i32)> = goals.iter().map(|&goal| (goal, 0)).collect();
+        self.relax(grid, &seeds, max_cost, can_enter, tile_cost);
+    }
+
+    /// Rebuild as a flee map from `toward`: every tile it reached is seeded with
+    /// its distance times `-FLEE_MAP_COEFFICIENT`, then relaxed again. Rolling
+    /// downhill leads away from the goals of `toward`, around corners if need be.
+    /// `tile_cost` should match what `toward` was built with.
+    pub fn build_flee(&mut self, grid: &Grid, toward: &DijkstraMap, tile_cost: &dyn Fn((i32, i32)) -> i32) {
+        self.reset(grid);
+        let seeds: Vec<((i32, i32), i32)> = toward
+            .touched
+            .iter()
+            .map(|&idx| {
+                let pos = ((idx % toward.width) as i32, (idx / toward.width) as i32);
+                let value = -(toward.values[idx] as f32 * FLEE_MAP_COEFFICIENT) as i32;
+                (pos, value)
+            })
+            .collect();
+        // Stay on tiles the toward map reached, which keeps this as cheap as it is
+        self.relax(grid, &seeds, i32::MAX, |pos| toward.value(pos).is_some(), tile_cost);
+    }
+
+    /// Cost to the nearest goal, or None if the tile wasn't reached.
+    pub fn value(&self, pos: (i32, i32)) -> Option<i32> {
+        self.index(pos).map(|idx| self.values[idx]).filter(|&v| v != UNREACHED)
+    }
+
+    /// The neighbouring tile that gets closest to a goal, skipping `blocked` tiles
+    /// (goals themselves are allowed, e.g. to attack whoever stands there).
+    /// Returns None if no free neighbour is better than standing still.
+    pub fn next_step(&self, grid: &Grid, pos: (i32, i32), blocked: &HashSet<(i32, i32)>) -> Option<(i32, i32)> {
+        let current = self.value(pos)?;
+        NEIGHBORS
+            .iter()
+            .map(|&(dx, dy)| (pos.0 + dx, pos.1 + dy))
+            .filter(|&next| pathfinding::can_step(grid, pos, next))
+            .filter_map(|next| self.value(next).map(|v| (next, v)))
+            .filter(|&(next, v)| v < current && (!blocked.contains(&next) || self.goals.contains(&next)))
+            .min_by_key(|&(_, v)| v)
+            .map(|(next, _)| next)
+    }
+
+    fn index(&self, pos: (i32, i32)) -> Option<usize> {
+        if pos.0 < 0 || pos.1 < 0 || pos.0 >= self.width as i32 || pos.1 >= self.height as i32 {
+            return None;
+        }
+        Some(pos.1 as usize * self.width + pos.0 as usize)
+    }
+
+    /// Clear every value, resizing for a new grid if needed
+    fn reset(&mut self, grid: &Grid) {
+        if self.width != grid.width || self.height != grid.height {
+            self.width = grid.width;
+            self.height = grid.height;
+            self.values = vec![UNREACHED; grid.width * grid.height];
+        } else {
+            for &idx in &self.touched {
+                self.values[idx] = UNREACHED;
+            }
+        }
+        self.touched.clear();
+        self.goals.clear();
+    }
+
+    /// Dijkstra from the seeds, stopping at `max_value`
+    fn relax(
+        &mut self,
+        grid: &Grid,
+        seeds: &[((i32, i32), i32)],
+        max_value: i32,
+        can_enter: impl Fn((i32, i32)) -> bool,
+        tile_cost: &dyn Fn((i32, i32)) -> i32,
+    ) {
+        let mut open = BinaryHeap::new();
+        for &(pos, value) in seeds {
+            let Some(idx) = self.index(pos) else { continue };
+            if value < self.values[idx] {
+                if self.values[idx] == UNREACHED {
+                    self.touched.push(idx);
+                }
+                self.values[idx] = value;
+                open.push(Reverse((value, idx)));
+            }
+        }
+
+        while let Some(Reverse((value, idx))) = open.pop() {
+            if value > self.values[idx] || value >= max_value {
+                continue;
+            }
+            let (x, y) = ((idx % self.width) as i32, (idx / self.width) as i32);
+
+            for (dx, dy) in NEIGHBORS {
+                let next = (x + dx, y + dy);
+                if !pathfinding::can_step(grid, (x, y), next) || !can_enter(next) {
+                    continue;
+                }
+                let Some(tile) = grid.get(next.0, next.1) else { continue };
+                if matches!(tile.tile_type, TileType::StairsUp | TileType::StairsDown) {
+                    continue;
+                }
+
+                let next_idx = next.1 as usize * self.width + next.0 as usize;
+                let next_value = value + pathfinding::step_cost((x, y), next) + tile_cost(next);
+                if next_value < self.values[next_idx] {
+                    if self.values[next_idx] == UNREACHED {
+                        self.touched.push(next_idx);
+                    }
+                    self.values[next_idx] = next_value;
+                    open.push(Reverse((next_value, next_idx)));
+                }
+            }
+        }
+    }
+}
+
+/// The shared maps AI reads its movement from, rebuilt once per player move.
+#[derive(Debug, Clone, Default)]
+pub struct NavigationMaps {
+    /// Player position the maps were built for (None until the first build)
+    origin: Option<(i32, i32)>,
+    /// Cost profile the maps were weighted for
+    profile: CostProfile,
+    toward_player: DijkstraMap,
+    flee_player: DijkstraMap,
+}
+
+impl NavigationMaps {
+    /// Create empty maps.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Rebuild both maps around the player's position.
+    /// `blocked` should hold obstacles AI can't walk through (not allies or closed doors);
+    /// `tile_cost` gives the hazard penalties of `profile`.
+    pub fn rebuild(
+        &mut self,
+        grid: &Grid,
+        player_pos: (i32, i32),
+        blocked: &HashSet<(i32, i32)>,
+        profile: CostProfile,
+        tile_cost: &dyn Fn((i32, i32)) -> i32,
+    ) {
+        puffin::profile_function!();
+        self.toward_player
+            .build(grid, &[player_pos], blocked, NAV_MAP_MAX_DISTANCE * STEP_COST, tile_cost);
+        self.flee_player.build_flee(grid, &self.toward_player, tile_cost);
+        self.origin = Some(player_pos);
+        self.profile = profile;
+    }
+
+    /// Forget the maps (the floor changed).
+    pub fn clear(&mut self) {
+        self.origin = None;
+    }
+
+    /// Player position the maps were built for.
+    pub fn origin(&self) -> Option<(i32, i32)> {
+        self.origin
+    }
+
+    /// Next step toward `target`, if the maps were built for that position
+    /// and `profile`, and cover `pos`.
+    pub fn step_toward(
+        &self,
+        grid: &Grid,
+        pos: (i32, i32),
+        target: (i32, i32),
+        profile: CostProfile,
+        blocked: &HashSet<(i32, i32)>,
+    ) -> Option<(i32, i32)> {
+        if self.origin != Some(target) || self.profile != profile {
+            return None;
+        }
+        self.toward_player.next_step(grid, pos, blocked)
+    }
+
+    /// Next step away from `threat`, if the maps were built for that position
+    /// and `profile`. Returns Some(None) when `pos` is covered but cornered.
+    pub fn step_away(
+        &self,
+        grid: &Grid,
+        pos: (i32, i32),
+        threat: (i32, i32),
+        profile: CostProfile,
+        blocked: &HashSet<(i32, i32)>,
+    ) -> Option<Option<(i32, i32)>> {
+        if self.origin != Some(threat) || self.profile != profile || self.flee_player.value(pos).is_none() {
+            return None;
+        }
+        Some(self.flee_player.next_step(grid, pos, blocked))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pathfinding;
+    use crate::tile::Tile;
+
+    /// 20x10 floor with a wall at x=10 open only at y=8
+    fn make_walled_grid() -> Grid {
+        let mut grid = Grid::filled(20, 10, TileType::Floor);
+        for y in 0..8 {
+            grid.tiles[y * 20 + 10] = Tile::new(TileType::Wall);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_distances_match_a_star() {
+        let grid = make_walled_grid();
+        let blocked = HashSet::new();
+        let mut map = DijkstraMap::default();
+        map.build(&grid, &[(15, 2)], &blocked, i32::MAX, &|_| 0);
+
+        for start in [(2, 2), (9, 0), (0, 9), (12, 5)] {
+            let path = pathfinding::find_path(&grid, start, (15, 2), &blocked).unwrap();
+            assert_eq!(map.value(start), Some(pathfinding::path_cost(start, &path)));
+
+            // Rolling downhill reaches the goal in the same number of steps
+            let mut pos = start;
+            let mut steps = 0;
+            while let Some(next) = map.next_step(&grid, pos, &blocked) {
+                pos = next;
+                steps += 1;
+            }
+            assert_eq!(pos, (15, 2));
+            assert_eq!(steps, path.len());
+        }
+        assert_eq!(map.value((10, 2)), None);
+    }
+
+    #[test]
+    fn test_max_distance_and_blocked_neighbours() {
+        let grid = Grid::filled(30, 3, TileType::Floor);
+        let mut map = DijkstraMap::default();
+        map.build(&grid, &[(0, 1)], &HashSet::new(), 5 * STEP_COST, &|_| 0);
+        assert_eq!(map.value((5, 1)), Some(5 * STEP_COST));
+        assert_eq!(map.value((6, 1)), None);
+
+        // Never steps onto an occupied tile, and never uphill to get around it
+        let occupied: HashSet<(i32, i32)> = [(2, 0), (2, 1), (2, 2)].into_iter().collect();
+        assert_eq!(map.next_step(&grid, (3, 1), &occupied), None);
+        let step = map.next_step(&grid, (3, 0), &occupied).unwrap();
+        assert!(map.value(step) < map.value((3, 0)));
+
+        // The goal itself can be stepped onto even when occupied
+        let goal_occupied: HashSet<(i32, i32)> = [(0, 1)].into_iter().collect();
+        assert_eq!(map.next_step(&grid, (1, 1), &goal_occupied), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_flee_map_leads_away_and_around_walls() {
+        let grid = make_walled_grid();
+        let blocked = HashSet::new();
+        let mut maps = NavigationMaps::new();
+        maps.rebuild(&grid, (5, 2), &blocked, CostProfile::NONE, &|_| 0);
+
+        // Fleeing from next to the player increases the distance
+        let step = maps.step_away(&grid, (6, 2), (5, 2), CostProfile::NONE, &blocked).unwrap().unwrap();
+        assert!(maps.toward_player.value(step).unwrap() > maps.toward_player.value((6, 2)).unwrap());
+
+        // Rolling downhill ends further away than the start
+        let mut pos = (6, 2);
+        for _ in 0..40 {
+            match maps.step_away(&grid, pos, (5, 2), CostProfile::NONE, &blocked).unwrap() {
+                Some(next) => pos = next,
+                None => break,
+            }
+        }
+        assert!(maps.toward_player.value(pos).unwrap() > 8 * STEP_COST, "fled only to {:?}", pos);
+
+        // Maps built for another position are not used
+        assert!(maps.step_away(&grid, (6, 2), (4, 2), CostProfile::NONE, &blocked).is_none());
+        assert!(maps.step_toward(&grid, (6, 2), (4, 2), CostProfile::NONE, &blocked).is_none());
+        // Built for another profile
+        assert!(maps.step_toward(&grid, (6, 2), (5, 2), CostProfile::CREATURE, &blocked).is_none());
+    }
+
+    #[test]
+    fn test_goal_map_toward_stairs() {
+        let mut grid = Grid::filled(10, 10, TileType::Floor);
+        grid.tiles[5 * 10 + 9] = Tile::new(TileType::StairsDown);
+        let mut map = DijkstraMap::default();
+        map.build(&grid, &[(9, 5)], &HashSet::new(), i32::MAX, &|_| 0);
+        assert_eq!(map.value((0, 5)), Some(9 * STEP_COST));
+        assert_eq!(map.next_step(&grid, (8, 5), &HashSet::new()), Some((9, 5)));
+    }
+}
@@ -0,0 +1,108 @@
+//! Headless combat balance report.
+//!
+//! Pits every player class against every enemy type and reports hit rates.
+//! Run with `cargo test balance -- --nocapture` to see the table.
+
+use hecs::{Entity, World};
+
+use crate::components::{Attackable, Equipment, Health, PlayerClass, Position, Stats};
+use crate::constants::{COMBAT_MAX_HIT_CHANCE, COMBAT_MIN_HIT_CHANCE};
+use crate::events::{EventQueue, GameEvent};
+use crate::spawning::{enemies, EnemyDef};
+use crate::systems::actions::apply_attack;
+use crate::systems::combat::{melee_hit_chance, ranged_hit_chance};
+
+/// Melee swings simulated per attacker/defender pair
+const ATTACKS_PER_PAIR: u32 = 2000;
+/// Allowed gap between the simulated hit rate and the formula
+const TOLERANCE: f32 = 0.05;
+/// Distances (tiles) reported for ranged accuracy
+const RANGED_DISTANCES: [i32; 3] = [2, 5, 8];
+
+const ENEMIES: [&EnemyDef; 6] = [
+    &enemies::RAT,
+    &enemies::SKELETON,
+    &enemies::SKELETON_ARCHER,
+    &enemies::GIANT_SPIDER,
+    &enemies::GOBLIN_SHAMAN,
+    &enemies::GIANT_BAT,
+];
+
+fn spawn_class(world: &mut World, class: PlayerClass) -> Entity {
+    let (strength, intelligence, agility) = class.stats();
+    world.spawn((
+        Position::new(0, 0),
+        Stats::new(strength, intelligence, agility),
+        Equipment::with_equipped(class.starting_weapon()),
+        Health::new(i32::MAX / 2),
+        Attackable,
+    ))
+}
+
+/// Swing repeatedly and return the fraction of attacks that landed
+fn simulate_hit_rate(world: &mut World, attacker: Entity, target: Entity) -> f32 {
+    let mut events = EventQueue::new();
+    let mut hits = 0;
+    for _ in 0..ATTACKS_PER_PAIR {
+        apply_attack(world, attacker, target, &mut events);
+        hits += events
+            .drain()
+            .filter(|event| matches!(event, GameEvent::AttackHit { .. }))
+            .count();
+        if let Ok(mut health) = world.get::<&mut Health>(target) {
+            health.current = health.max;
+        }
+    }
+    hits as f32 / ATTACKS_PER_PAIR as f32
+}
+
+#[test]
+fn balance_hit_rates_per_class_vs_enemy() {
+    println!("{:<12} {:<16} {:>10} {:>10}", "class", "enemy", "player hit", "enemy hit");
+
+    for class in PlayerClass::ALL {
+        for enemy_def in ENEMIES {
+            let mut world = World::new();
+            let player = spawn_class(&mut world, class);
+            let enemy = enemy_def.spawn(&mut world, 1, 0, &mut rand::thread_rng());
+            if let Ok(mut health) = world.get::<&mut Health>(enemy) {
+                health.max = i32::MAX / 2;
+            }
+
+            let player_rate = simulate_hit_rate(&mut world, player, enemy);
+            let enemy_rate = simulate_hit_rate(&mut world, enemy, player);
+            println!("{:<12} {:<16} {:>9.0}% {:>9.0}%", class.name(), enemy_def.name, player_rate * 100.0, enemy_rate * 100.0);
+
+            let (_, _, class_agility) = class.stats();
+            let expected_player = melee_hit_chance(class_agility, enemy_def.agility);
+            let expected_enemy = melee_hit_chance(enemy_def.agility, class_agility);
+            assert!((player_rate - expected_player).abs() < TOLERANCE, "{} vs {}", class.name(), enemy_def.name);
+            assert!((enemy_rate - expected_enemy).abs() < TOLERANCE, "{} vs {}", enemy_def.name, class.name());
+
+            // Players should land most swings; enemies should never be hopeless or guaranteed
+            assert!(player_rate >= 0.5, "{} struggles to hit {}", class.name(), enemy_def.name);
+            assert!((COMBAT_MIN_HIT_CHANCE..=COMBAT_MAX_HIT_CHANCE).contains(&expected_enemy));
+        }
+    }
+
+    println!();
+    println!("ranged accuracy by distance {:?}", RANGED_DISTANCES);
+    for class in PlayerClass::ALL {
+        let (_, _, class_agility) = class.stats();
+        for enemy_def in ENEMIES {
+            let player_shots: Vec<String> = RANGED_DISTANCES
+                .iter()
+                .map(|&d| format!("{:.0}%", ranged_hit_chance(class_agility, enemy_def.agility, d) * 100.0))
+                .collect();
+            println!("{:<12} shooting {:<16} {}", class.name(), enemy_def.name, player_shots.join(" / "));
+        }
+        if let Some(ranged) = enemies::SKELETON_ARCHER.ranged {
+            let archer_shots: Vec<String> = RANGED_DISTANCES
+                .iter()
+                .filter(|&&d| d <= ranged.max_range)
+                .map(|&d| format!("{:.0}%", ranged_hit_chance(enemies::SKELETON_ARCHER.agility, class_agility, d) * 100.0))
+                .collect();
+            println!("{:<12} shot by  {:<16} {}", class.name(), enemies::SKELETON_ARCHER.name, archer_shots.join(" / "));
+        }
+    }
+}
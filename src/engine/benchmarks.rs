@@ -0,0 +1,285 @@
+//! Timing benchmarks for large floors.
+//!
+//! These are ignored by default; run them in release mode with:
+//! `cargo test --release -- --ignored --nocapture bench_`
+
+use std::time::{Duration, Instant};
+
+use rand::seq::SliceRandom;
+
+use super::game_state::GameState;
+use super::simulation::advance_until_player_ready;
+use crate::components::{ActionType, ChaseAI, Health, PlayerClass, Position};
+use crate::pathfinding::{self, CostProfile};
+use crate::queries;
+use crate::spawning;
+use crate::constants::{AI_ACTIVE_RADIUS, FOV_RADIUS, OFFSCREEN_ACTORS_PER_TICK, OFFSCREEN_TICK_INTERVAL};
+use crate::events::EventQueue;
+use crate::grid::FloorSize;
+use crate::systems;
+use crate::time_system;
+
+/// Half-size (in tiles) of the view used for render culling, roughly a 1080p screen at default zoom
+const BENCH_VIEW_HALF_SIZE: i32 = 20;
+/// Number of player turns simulated per benchmark
+const BENCH_TURNS: u32 = 200;
+
+/// Keep the player alive so every turn does the same amount of work
+fn keep_player_alive(state: &mut GameState) {
+    if let Ok(mut health) = state.world.get::<&mut Health>(state.player_entity) {
+        health.max = 1_000_000;
+        health.current = health.max;
+    }
+}
+
+fn average(total: Duration, turns: u32) -> f64 {
+    total.as_secs_f64() * 1000.0 / turns as f64
+}
+
+#[test]
+#[ignore]
+fn bench_huge_floor_turns() {
+    let setup_start = Instant::now();
+    let mut state = GameState::new(PlayerClass::Fighter, FloorSize::Huge);
+    let mut events = EventQueue::new();
+    state.initialize_ai(&mut events);
+    let setup_time = setup_start.elapsed();
+
+    let enemy_count = state.world.query::<&ChaseAI>().iter().count();
+    assert!(enemy_count >= 300, "expected hundreds of actors, got {}", enemy_count);
+
+    let mut rng = rand::thread_rng();
+    let mut simulation_time = Duration::ZERO;
+    let mut fov_time = Duration::ZERO;
+    let mut render_time = Duration::ZERO;
+
+    for _ in 0..BENCH_TURNS {
+        keep_player_alive(&mut state);
+
+        let start = Instant::now();
+        if time_system::start_action(
+            &mut state.world,
+            state.player_entity,
+            ActionType::Wait,
+            &state.game_clock,
+            &mut state.action_scheduler,
+        )
+        .is_ok()
+        {
+            advance_until_player_ready(
+                &mut state.world,
+                &state.grid,
+                state.player_entity,
+                &mut state.game_clock,
+                &mut state.action_scheduler,
+                &mut state.active_ai_tracker,
+                &mut state.spatial_cache,
+                &mut events,
+                &mut rng,
+            );
+        }
+        events.drain().for_each(drop);
+        simulation_time += start.elapsed();
+
+        let (px, py) = state
+            .world
+            .get::<&Position>(state.player_entity)
+            .map(|p| (p.x, p.y))
+            .unwrap_or((0, 0));
+        let view_bounds = (
+            px - BENCH_VIEW_HALF_SIZE,
+            px + BENCH_VIEW_HALF_SIZE,
+            py - BENCH_VIEW_HALF_SIZE,
+            py + BENCH_VIEW_HALF_SIZE,
+        );
+
+        let start = Instant::now();
+        systems::update_fov(
+            &state.world,
+            &mut state.grid,
+            state.player_entity,
+            FOV_RADIUS,
+            view_bounds,
+            state.game_clock.time,
+        );
+        systems::calculate_illumination(&state.world, &mut state.grid, state.player_entity, FOV_RADIUS, view_bounds);
+        fov_time += start.elapsed();
+
+        let start = Instant::now();
+        let renderables = systems::collect_renderables(&state.world, &state.grid, state.player_entity, 0.0, view_bounds);
+        render_time += start.elapsed();
+        assert!(!renderables.is_empty());
+    }
+
+    let start = Instant::now();
+    state.spatial_cache.rebuild_in_place(&state.world);
+    let rebuild_time = start.elapsed();
+
+    println!(
+        "huge floor {}x{}, {} enemies, {} walkable tiles",
+        state.grid.width,
+        state.grid.height,
+        enemy_count,
+        state.grid.walkable_positions.len()
+    );
+    println!("  setup:               {:.2} ms", setup_time.as_secs_f64() * 1000.0);
+    println!("  simulation per turn: {:.3} ms", average(simulation_time, BENCH_TURNS));
+    println!("  fov + lighting:      {:.3} ms", average(fov_time, BENCH_TURNS));
+    println!("  collect_renderables: {:.3} ms", average(render_time, BENCH_TURNS));
+    println!("  spatial cache rebuild: {:.3} ms", rebuild_time.as_secs_f64() * 1000.0);
+}
+
+/// Run `BENCH_TURNS` off-screen ticks, returning the average and worst tick time in ms
+fn time_offscreen_ticks(state: &mut GameState, player_pos: (i32, i32), first_tick: u32) -> (f64, f64) {
+    let mut rng = rand::thread_rng();
+    let mut total = Duration::ZERO;
+    let mut worst = Duration::ZERO;
+    for tick in first_tick..first_tick + BENCH_TURNS {
+        let start = Instant::now();
+        systems::offscreen::simulate(
+            &mut state.world,
+            &state.grid,
+            player_pos,
+            tick as f32 * OFFSCREEN_TICK_INTERVAL,
+            &mut state.active_ai_tracker,
+            &mut state.spatial_cache,
+            &mut rng,
+        );
+        let elapsed = start.elapsed();
+        total += elapsed;
+        worst = worst.max(elapsed);
+    }
+    (average(total, BENCH_TURNS), worst.as_secs_f64() * 1000.0)
+}
+
+/// Spawn dormant rats on random free walkable tiles out of the player's reach
+fn add_dormant_enemies(state: &mut GameState, player_pos: (i32, i32), count: usize) {
+    let mut rng = rand::thread_rng();
+    let mut free: Vec<(i32, i32)> = state
+        .grid
+        .walkable_positions
+        .iter()
+        .copied()
+        .filter(|&(x, y)| (x - player_pos.0).abs() + (y - player_pos.1).abs() > AI_ACTIVE_RADIUS)
+        .filter(|&pos| !state.spatial_cache.is_blocked(pos))
+        .collect();
+    free.shuffle(&mut rng);
+    for (x, y) in free.into_iter().take(count) {
+        let entity = spawning::enemies::RAT.spawn(&mut state.world, x, y, &mut rng);
+        state.spatial_cache.register_entity(entity, (x, y), true, false);
+        state.active_ai_tracker.register_entity(entity);
+    }
+}
+
+#[test]
+#[ignore]
+fn bench_huge_floor_offscreen_ticks() {
+    /// Allowed average off-screen tick cost, however many enemies the floor holds
+    const TICK_BUDGET_MS: f64 = 4.0;
+    /// How much slower a tick may get with four times the enemies.
+    /// Some growth is expected: crowded halls make more searches run to the node limit.
+    const MAX_SLOWDOWN: f64 = 3.0;
+
+    let mut state = GameState::new(PlayerClass::Fighter, FloorSize::Huge);
+    let mut events = EventQueue::new();
+    state.initialize_ai(&mut events);
+
+    let player_pos = queries::get_entity_position(&state.world, state.player_entity).unwrap_or((0, 0));
+    let start_positions: Vec<_> = state
+        .world
+        .query::<(&Position, &ChaseAI)>()
+        .iter()
+        .map(|(e, (p, _))| (e, (p.x, p.y)))
+        .collect();
+
+    println!(
+        "huge floor {}x{}, {} per tick",
+        state.grid.width, state.grid.height, OFFSCREEN_ACTORS_PER_TICK
+    );
+
+    let base_count = state.active_ai_tracker.get_dormant_entities().len();
+    let mut baseline = None;
+    let mut first_tick = 1;
+    for multiplier in [1, 2, 4] {
+        let missing = (base_count * multiplier).saturating_sub(state.active_ai_tracker.get_dormant_entities().len());
+        add_dormant_enemies(&mut state, player_pos, missing);
+        let dormant_count = state.active_ai_tracker.get_dormant_entities().len();
+        let (avg, worst) = time_offscreen_ticks(&mut state, player_pos, first_tick);
+        first_tick += BENCH_TURNS;
+
+        println!(
+            "  {:>6} dormant enemies: tick avg {:.3} ms, worst {:.3} ms",
+            dormant_count, avg, worst
+        );
+        assert!(avg < TICK_BUDGET_MS, "off-screen tick averaged {:.3} ms with {} enemies", avg, dormant_count);
+        let baseline = *baseline.get_or_insert(avg);
+        assert!(
+            avg < baseline * MAX_SLOWDOWN + 0.05,
+            "off-screen tick grew from {:.3} ms to {:.3} ms with {} enemies",
+            baseline,
+            avg,
+            dormant_count
+        );
+    }
+
+    let moved = start_positions
+        .iter()
+        .filter(|&&(e, pos)| queries::get_entity_position(&state.world, e) != Some(pos))
+        .count();
+    println!("  original enemies moved after {} ticks: {}", first_tick - 1, moved);
+}
+
+#[test]
+#[ignore]
+fn bench_huge_floor_chase_steps() {
+    let mut state = GameState::new(PlayerClass::Fighter, FloorSize::Huge);
+    let mut events = EventQueue::new();
+    state.initialize_ai(&mut events);
+
+    let player_pos = queries::get_entity_position(&state.world, state.player_entity).unwrap_or((0, 0));
+    let blocked = systems::ai::ai_pathfinding_blocked(&state.world, &state.spatial_cache);
+    let chasers: Vec<(i32, i32)> = state
+        .world
+        .query::<(&Position, &ChaseAI)>()
+        .iter()
+        .map(|(_, (p, _))| (p.x, p.y))
+        .filter(|&(x, y)| (x - player_pos.0).abs() + (y - player_pos.1).abs() <= AI_ACTIVE_RADIUS)
+        .collect();
+
+    // Every active enemy runs its own A* toward the player
+    let start = Instant::now();
+    let mut a_star_steps = 0;
+    for _ in 0..BENCH_TURNS {
+        for &pos in &chasers {
+            if pathfinding::next_step_toward(&state.grid, pos, player_pos, &blocked).is_some() {
+                a_star_steps += 1;
+            }
+        }
+    }
+    let a_star_time = start.elapsed();
+
+    // One shared map per player move, then a lookup per enemy
+    let start = Instant::now();
+    let mut map_steps = 0;
+    for _ in 0..BENCH_TURNS {
+        let maps = state.spatial_cache.nav_maps_mut();
+        maps.clear();
+        maps.rebuild(&state.grid, player_pos, &blocked, CostProfile::NONE, &|_| 0);
+        for &pos in &chasers {
+            if maps.step_toward(&state.grid, pos, player_pos, CostProfile::NONE, &blocked).is_some() {
+                map_steps += 1;
+            }
+        }
+    }
+    let map_time = start.elapsed();
+
+    println!(
+        "huge floor {}x{}, {} enemies within {} tiles of the player",
+        state.grid.width,
+        state.grid.height,
+        chasers.len(),
+        AI_ACTIVE_RADIUS
+    );
+    println!("  per-entity A*:        {:.3} ms per turn ({} steps)", average(a_star_time, BENCH_TURNS), a_star_steps / BENCH_TURNS);
+    println!("  shared Dijkstra maps: {:.3} ms per turn ({} steps)", average(map_time, BENCH_TURNS), map_steps / BENCH_TURNS);
+}
@@ -4,7 +4,6 @@ use crate::components::{
     BlocksMovement, BlocksVision, ChaseAI, Container, Door, Health, ItemType,
     Position, Sprite, VisualPosition,
 };
-use crate::constants::*;
 use crate::events::EventQueue;
 use crate::grid::Grid;
 use crate::spawning;
@@ -12,8 +11,8 @@ use crate::tile::tile_ids;
 use crate::time_system::{ActionScheduler, GameClock};
 
 use hecs::{Entity, World};
-use std::collections::HashMap;
 
+use super::game_state::GameState;
 use super::initialization::spawn_floor_entities;
 
 /// Saved state of a floor for when the player leaves and returns.
@@ -172,7 +171,7 @@ pub fn load_floor(
         let pos = Position::new(saved_entity.pos.0, saved_entity.pos.1);
         match &saved_entity.entity_type {
             SavedEntityType::Enemy { health_current, health_max } => {
-                let enemy = spawning::enemies::SKELETON.spawn(world, pos.x, pos.y);
+                let enemy = spawning::enemies::SKELETON.spawn(world, pos.x, pos.y, &mut rng);
                 if let Ok(mut health) = world.get::<&mut Health>(enemy) {
                     health.current = *health_current;
                     health.max = *health_max;
@@ -239,21 +238,30 @@ pub fn load_floor(
 }
 
 /// Handle a floor transition (going up or down stairs).
+/// Takes the current grid out of `state`; the caller puts `new_grid` back.
 pub fn handle_floor_transition(
-    world: &mut World,
-    current_grid: Grid,
-    floors: &mut HashMap<u32, SavedFloor>,
-    current_floor: u32,
+    state: &mut GameState,
     direction: crate::events::StairDirection,
-    player_entity: Entity,
-    clock: &GameClock,
-    scheduler: &mut ActionScheduler,
-    active_ai_tracker: &mut crate::active_ai_tracker::ActiveAITracker,
-    spatial_cache: &mut crate::spatial_cache::SpatialCache,
     events: &mut EventQueue,
 ) -> FloorTransitionResult {
     use crate::events::StairDirection;
 
+    let GameState {
+        world,
+        grid,
+        player_entity,
+        current_floor,
+        floor_size,
+        floors,
+        game_clock: clock,
+        action_scheduler: scheduler,
+        spatial_cache,
+        active_ai_tracker,
+        ..
+    } = state;
+    let (player_entity, current_floor) = (*player_entity, *current_floor);
+    let current_grid = std::mem::replace(grid, Grid::new(1, 1));
+
     let target_floor = match direction {
         StairDirection::Down => current_floor + 1,
         StairDirection::Up => {
@@ -291,7 +299,8 @@ pub fn handle_floor_transition(
         );
         grid
     } else {
-        let grid = Grid::new_floor(DUNGEON_DEFAULT_WIDTH, DUNGEON_DEFAULT_HEIGHT, target_floor);
+        let (width, height) = floor_size.dimensions(target_floor);
+        let grid = Grid::new_floor(width, height, target_floor);
 
         let spawn_pos = match direction {
             StairDirection::Down => grid.stairs_up_pos.unwrap_or((1, 1)),
@@ -2,9 +2,8 @@
 
 use crate::active_ai_tracker::ActiveAITracker;
 use crate::components::{PlayerClass, Position};
-use crate::constants::*;
 use crate::events::EventQueue;
-use crate::grid::Grid;
+use crate::grid::{FloorSize, Grid};
 use crate::spatial_cache::SpatialCache;
 use crate::time_system::{ActionScheduler, GameClock};
 
@@ -28,6 +27,9 @@ pub struct GameState {
     /// Current floor number
     pub current_floor: u32,
 
+    /// Floor size setting for this run (floors grow with depth from this base)
+    pub floor_size: FloorSize,
+
     /// Saved floors for multi-level dungeon
     pub floors: HashMap<u32, SavedFloor>,
 
@@ -40,6 +42,9 @@ pub struct GameState {
     /// Whether FOV needs recalculation (dirty flag for performance)
     pub fov_dirty: bool,
 
+    /// Camera bounds the last FOV update lit the view for
+    pub fov_view_bounds: (i32, i32, i32, i32),
+
     /// Spatial cache for efficient blocking position lookups
     pub spatial_cache: SpatialCache,
 
@@ -49,8 +54,9 @@ pub struct GameState {
 
 impl GameState {
     /// Create a new game state with initialized world for the given player class.
-    pub fn new(player_class: PlayerClass) -> Self {
-        let grid = Grid::new(DUNGEON_DEFAULT_WIDTH, DUNGEON_DEFAULT_HEIGHT);
+    pub fn new(player_class: PlayerClass, floor_size: FloorSize) -> Self {
+        let (width, height) = floor_size.dimensions(0);
+        let grid = Grid::new(width, height);
         let (world, player_entity, _player_start) = initialization::init_world(&grid, player_class);
 
         let game_clock = GameClock::new();
@@ -67,10 +73,12 @@ impl GameState {
             grid,
             player_entity,
             current_floor: 0,
+            floor_size,
             floors: HashMap::new(),
             game_clock,
             action_scheduler,
             fov_dirty: true, // Always calculate FOV on first frame
+            fov_view_bounds: (0, 0, 0, 0),
             spatial_cache,
             active_ai_tracker,
         }
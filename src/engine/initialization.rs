@@ -175,6 +175,8 @@ fn generate_chest_contents(rng: &mut impl Rng) -> Container {
         ItemType::ScrollOfInvisibility,
         ItemType::ScrollOfSlow,
         ItemType::ScrollOfMapping,
+        ItemType::LeatherArmor,
+        ItemType::Helmet,
     ];
 
     // Rare items
@@ -184,6 +186,9 @@ fn generate_chest_contents(rng: &mut impl Rng) -> Container {
         ItemType::ScrollOfFear,
         ItemType::ScrollOfReveal,
         ItemType::ScrollOfFireball,
+        ItemType::RingOfStrength,
+        ItemType::RingOfAgility,
+        ItemType::AmuletOfWisdom,
     ];
 
     let roll: f32 = rng.gen();
@@ -264,15 +269,8 @@ pub fn init_world(grid: &Grid, player_class: PlayerClass) -> (World, Entity, Pos
             }
         }
     } else {
-        'find_spawn: for y in 0..grid.height as i32 {
-            for x in 0..grid.width as i32 {
-                if let Some(tile) = grid.get(x, y) {
-                    if tile.tile_type.is_walkable() {
-                        player_start = Position::new(x, y);
-                        break 'find_spawn;
-                    }
-                }
-            }
+        if let Some(&(x, y)) = grid.walkable_positions.first() {
+            player_start = Position::new(x, y);
         }
     }
 
@@ -362,15 +360,10 @@ pub fn init_world(grid: &Grid, player_class: PlayerClass) -> (World, Entity, Pos
     }
 
     // Spawn enemies
-    let walkable_tiles: Vec<(i32, i32)> = (0..grid.height as i32)
-        .flat_map(|y| (0..grid.width as i32).map(move |x| (x, y)))
-        .filter(|&(x, y)| grid.is_walkable(x, y))
-        .collect();
-
-    let spawn_config = spawning::SpawnConfig::level_1();
+    let spawn_config = spawning::SpawnConfig::level_1().scaled_to_floor(grid.width, grid.height);
     spawn_config.spawn_all(
         &mut world,
-        &walkable_tiles,
+        grid,
         &[(player_start.x, player_start.y)],
         grid.starting_room.as_ref(),
         &mut rng,
@@ -463,15 +456,10 @@ pub fn spawn_floor_entities(
     spawn_vendor(world, grid, floor_num);
 
     // Spawn enemies
-    let walkable_tiles: Vec<(i32, i32)> = (0..grid.height as i32)
-        .flat_map(|y| (0..grid.width as i32).map(move |x| (x, y)))
-        .filter(|&(x, y)| grid.is_walkable(x, y))
-        .collect();
-
-    let spawn_config = spawning::SpawnConfig::level_1();
+    let spawn_config = spawning::SpawnConfig::level_1().scaled_to_floor(grid.width, grid.height);
     spawn_config.spawn_all(
         world,
-        &walkable_tiles,
+        grid,
         &[player_spawn_pos],
         grid.starting_room.as_ref(),
         &mut rng,
@@ -11,6 +11,10 @@
 //! - Forwarding events to the engine
 //! - Rendering what the engine returns
 
+#[cfg(test)]
+mod balance;
+#[cfg(test)]
+mod benchmarks;
 mod dev_spawning;
 pub mod floor_transition;
 mod game_state;
@@ -23,15 +27,19 @@ pub use initialization::initialize_single_ai_actor;
 pub use simulation::*;
 
 use crate::audio::AudioManager;
+use crate::bestiary::Bestiary;
 use crate::components::{AbilityType, ActionType, Actor, ClassAbility, PlayerClass};
 
 use crate::camera::Camera;
 use crate::events::EventQueue;
+use crate::grid::FloorSize;
 use crate::input::{self, InputState, TargetingMode};
+use crate::keybindings::{self, KeyBindings, KeyCombo};
+use crate::settings::Settings;
 use crate::spawning;
 use crate::systems;
 use crate::time_system;
-use crate::ui::{DevMenu, GameUiState, UiActions};
+use crate::ui::{DevMenu, GameUiState, ProfileScreens, RunInput, RunScreens, UiActions};
 use crate::vfx::{FireEffect, VfxManager, VisualEffect};
 
 use hecs::Entity;
@@ -42,7 +50,8 @@ use winit::keyboard::{KeyCode, PhysicalKey};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WindowAction {
     Exit,
-    ToggleFullscreen,
+    /// The settings changed - apply them to the window (fullscreen, UI scale, zoom limits)
+    ApplySettings,
 }
 
 /// Game mode - whether we're on the start screen or playing
@@ -70,6 +79,9 @@ pub struct GameEngine {
     /// Selected player class (for start screen)
     pub selected_class: Option<PlayerClass>,
 
+    /// Selected floor size (for start screen)
+    pub selected_floor_size: FloorSize,
+
     /// Core game state (world, grid, floors, time) - None on start screen
     pub state: Option<GameState>,
 
@@ -93,33 +105,55 @@ pub struct GameEngine {
 
     /// Audio manager for sound effects
     pub audio: Option<AudioManager>,
+
+    /// Player settings (volume, display, camera)
+    pub settings: Settings,
+
+    /// Options screen open (start screen or in-game)
+    pub show_options: bool,
+
+    /// Monster types met across all runs
+    pub bestiary: Bestiary,
+
+    /// Bestiary open (start screen or in-game)
+    pub show_bestiary: bool,
 }
 
 impl GameEngine {
     /// Create a new game engine on the start screen.
     pub fn new() -> Self {
-        let audio = AudioManager::new();
-        if audio.is_none() {
-            eprintln!("Warning: Could not initialize audio system");
+        let settings = Settings::load();
+        let mut audio = AudioManager::new();
+        match audio.as_mut() {
+            Some(audio) => audio.set_volume(settings.volume),
+            None => eprintln!("Warning: Could not initialize audio system"),
         }
 
+        let mut input = InputState::new();
+        input.bindings = KeyBindings::load();
+
         Self {
             game_mode: GameMode::StartScreen,
             selected_class: Some(PlayerClass::Fighter), // Default selection
+            selected_floor_size: FloorSize::Standard,
             state: None,
             vfx: VfxManager::new(),
             events: EventQueue::new(),
-            input: InputState::new(),
+            input,
             ui_state: None,
             dev_menu: DevMenu::new(),
             real_time: 0.0,
             audio,
+            settings,
+            show_options: false,
+            bestiary: Bestiary::load(),
+            show_bestiary: false,
         }
     }
 
     /// Start the game with the selected class.
     pub fn start_game(&mut self, class: PlayerClass, camera: &mut Camera) {
-        let mut state = GameState::new(class);
+        let mut state = GameState::new(class, self.selected_floor_size);
 
         // Initialize AI actors
         state.initialize_ai(&mut self.events);
@@ -160,6 +194,23 @@ impl GameEngine {
         self.game_mode == GameMode::Playing
     }
 
+    /// Check if the pause menu is open (the simulation doesn't advance).
+    pub fn is_paused(&self) -> bool {
+        self.ui_state.as_ref().is_some_and(|ui_state| ui_state.show_pause_menu)
+    }
+
+    /// Throw away the current run and go back to the start screen.
+    pub fn abandon_run(&mut self) {
+        let bindings = std::mem::take(&mut self.input.bindings);
+        self.input = InputState::new();
+        self.input.bindings = bindings;
+        self.state = None;
+        self.ui_state = None;
+        self.vfx = VfxManager::new();
+        self.events = EventQueue::new();
+        self.game_mode = GameMode::StartScreen;
+    }
+
     /// Get a reference to the UI state (panics if not playing).
     pub fn ui_state(&self) -> &GameUiState {
         self.ui_state.as_ref().expect("UI state not initialized - game not started")
@@ -184,14 +235,29 @@ impl GameEngine {
                     if let PhysicalKey::Code(key) = key_event.physical_key {
                         match key_event.state {
                             ElementState::Pressed => {
-                                if key == KeyCode::Escape {
-                                    if self.input.is_targeting() {
-                                        self.input.cancel_targeting();
-                                        return None;
-                                    } else {
-                                        return Some(WindowAction::Exit);
+                                // The rebinding screen is waiting for a key
+                                if let Some(ui_state) = self.ui_state.as_mut() {
+                                    if let Some(command) = ui_state.capturing_binding {
+                                        if key == KeyCode::Escape {
+                                            ui_state.capturing_binding = None;
+                                            return None;
+                                        }
+                                        if keybindings::is_bindable(key) {
+                                            ui_state.capturing_binding = None;
+                                            let combo = KeyCombo::with_held(key, &self.input.keys_pressed);
+                                            self.input.bindings.bind(command, combo);
+                                            self.save_key_bindings();
+                                            return None;
+                                        }
                                     }
                                 }
+                                if key == KeyCode::Escape {
+                                    return self.handle_escape();
+                                }
+                                // Paused: game keys wait until the menu is closed
+                                if self.is_paused() {
+                                    return None;
+                                }
                                 if key == KeyCode::Backquote {
                                     self.dev_menu.toggle();
                                 }
@@ -209,7 +275,7 @@ impl GameEngine {
                 self.input.mouse_pos = (position.x as f32, position.y as f32);
             }
             WindowEvent::MouseInput { state: btn_state, button, .. } => {
-                if !egui_consumed && *button == MouseButton::Left {
+                if !egui_consumed && !self.is_paused() && *button == MouseButton::Left {
                     let was_down = self.input.mouse_down;
                     self.input.mouse_down = *btn_state == ElementState::Pressed;
 
@@ -225,8 +291,17 @@ impl GameEngine {
                                 || dy.abs() > crate::constants::CLICK_DRAG_THRESHOLD);
 
                         if !was_drag && self.is_playing() {
-                            if self.input.is_targeting() {
+                            if self.input.command_menu.is_some() {
+                                // Clicking away from the command menu dismisses it
+                                self.input.command_menu = None;
+                            } else if self.input.is_targeting() {
                                 self.input.pending_left_click = true;
+                            } else if self.input.examine.is_some() {
+                                // Clicking looks at the tile under the mouse
+                                let tile = input::mouse_tile(&self.input, camera);
+                                if let (Some(examine), Some(state)) = (self.input.examine.as_mut(), &self.state) {
+                                    examine.look_at(&state.grid, tile);
+                                }
                             } else if self.dev_menu.has_active_tool() {
                                 self.handle_dev_spawn(camera);
                             } else if let Some(ref state) = self.state {
@@ -241,10 +316,12 @@ impl GameEngine {
                         }
                     }
                 }
-                if !egui_consumed && *button == MouseButton::Right {
+                if !egui_consumed && !self.is_paused() && *button == MouseButton::Right {
                     if *btn_state == ElementState::Released {
                         if self.input.is_targeting() {
                             self.input.cancel_targeting();
+                        } else if self.input.examine.is_some() {
+                            self.input.examine = None;
                         } else {
                             self.input.pending_right_click = true;
                         }
@@ -282,6 +359,22 @@ impl GameEngine {
             };
         }
 
+        // Paused: nothing moves and no time passes, but the frame still gets drawn
+        if self.is_paused() {
+            camera.update(dt, self.input.mouse_down);
+            let state = self.state.as_ref().expect("State checked above");
+            return TickResult {
+                entities: systems::collect_renderables(
+                    &state.world,
+                    &state.grid,
+                    state.player_entity,
+                    self.real_time,
+                    camera.get_visible_bounds(),
+                ),
+                window_action: None,
+            };
+        }
+
         // Handle input first (needs full &mut self access)
         let input_result = {
             puffin::profile_scope!("process_input");
@@ -289,7 +382,19 @@ impl GameEngine {
         };
         let mut window_action = None;
         if input_result.toggle_fullscreen {
-            window_action = Some(WindowAction::ToggleFullscreen);
+            self.settings.fullscreen = !self.settings.fullscreen;
+            self.save_settings();
+            window_action = Some(WindowAction::ApplySettings);
+        }
+        if input_result.toggle_grid_lines {
+            self.settings.show_grid_lines = !self.settings.show_grid_lines;
+            self.save_settings();
+        }
+        if input_result.toggle_options {
+            self.toggle_options();
+        }
+        if input_result.toggle_bestiary {
+            self.show_bestiary = !self.show_bestiary;
         }
 
         // Now extract state references for the rest
@@ -348,9 +453,9 @@ impl GameEngine {
         // Spawn skeletons from opened coffins
         if !skeleton_spawns.is_empty() {
             for (x, y) in &skeleton_spawns {
-                let skeleton = spawning::enemies::SKELETON.spawn(&mut state.world, *x, *y);
-                state.spatial_cache.register_entity(skeleton, (*x, *y), true, false);
                 let mut rng = rand::thread_rng();
+                let skeleton = spawning::enemies::SKELETON.spawn(&mut state.world, *x, *y, &mut rng);
+                state.spatial_cache.register_entity(skeleton, (*x, *y), true, false);
                 initialization::initialize_single_ai_actor(
                     &mut state.world,
                     &state.grid,
@@ -394,7 +499,8 @@ impl GameEngine {
                 true // Missed arrows always recoverable
             };
             if should_recover {
-                systems::inventory::spawn_ground_item(&mut state.world, x, y, crate::components::ItemType::Arrow);
+                let pile = systems::inventory::spawn_ground_item(&mut state.world, x, y, crate::components::ItemType::Arrow);
+                state.spatial_cache.track_item(pile, (x, y));
             }
         }
 
@@ -406,7 +512,14 @@ impl GameEngine {
         // Update camera
         camera.update(dt, self.input.mouse_down);
 
-        // Update visibility based on LOS and illumination (only when game state changed)
+        // Lights that were off screen last update may now be in view
+        let view_bounds = camera.get_visible_bounds();
+        if view_bounds != state.fov_view_bounds {
+            state.fov_view_bounds = view_bounds;
+            state.fov_dirty = true;
+        }
+
+        // Update visibility based on LOS and illumination (only when game state or view changed)
         if state.fov_dirty {
             {
                 puffin::profile_scope!("fov_update");
@@ -415,6 +528,7 @@ impl GameEngine {
                     &mut state.grid,
                     state.player_entity,
                     crate::constants::FOV_RADIUS,
+                    view_bounds,
                     state.game_clock.time,
                 );
             }
@@ -427,6 +541,7 @@ impl GameEngine {
                     &mut state.grid,
                     state.player_entity,
                     crate::constants::FOV_RADIUS,
+                    view_bounds,
                 );
             }
 
@@ -441,9 +556,12 @@ impl GameEngine {
                 &state.grid,
                 state.player_entity,
                 self.real_time,
+                view_bounds,
             )
         };
 
+        self.update_bestiary();
+
         TickResult {
             entities,
             window_action,
@@ -451,9 +569,48 @@ impl GameEngine {
     }
 
     /// Process UI actions from the UI layer.
-    /// Does nothing if not playing.
-    pub fn process_ui_actions(&mut self, actions: &UiActions) {
-        let Some(ref mut state) = self.state else { return };
+    /// Does nothing if not playing. Returns `WindowAction::Exit` when the player quit.
+    pub fn process_ui_actions(&mut self, actions: &UiActions) -> Option<WindowAction> {
+        // Options screen (start screen or in-game)
+        if let Some(settings) = &actions.settings {
+            self.settings = settings.clone();
+            if let Some(audio) = self.audio.as_mut() {
+                audio.set_volume(self.settings.volume);
+            }
+        }
+        if (actions.open_options && !self.show_options) || (actions.close_options && self.show_options) {
+            self.toggle_options();
+        }
+        if actions.open_bestiary {
+            self.show_bestiary = true;
+        }
+        if actions.close_bestiary {
+            self.show_bestiary = false;
+        }
+
+        // Key bindings edited on the rebinding screen
+        if let Some((command, combo)) = actions.unbind_key {
+            self.input.bindings.unbind(command, combo);
+        }
+        if actions.reset_key_bindings {
+            self.input.bindings = KeyBindings::default();
+        }
+        if actions.unbind_key.is_some() || actions.reset_key_bindings {
+            self.save_key_bindings();
+        }
+
+        if actions.quit {
+            return Some(WindowAction::Exit);
+        }
+        if actions.abandon_run {
+            self.abandon_run();
+        }
+        // Paused: the pause menu is the only thing that can act
+        if self.is_paused() {
+            return None;
+        }
+
+        let state = self.state.as_mut()?;
         let ui_state = self.ui_state.as_ref().expect("UI state should exist when state exists");
 
         let ui_result = process_ui_actions(
@@ -467,6 +624,16 @@ impl GameEngine {
             state.game_clock.time,
         );
 
+        // Wait picked from the wait prompt - the waiting itself happens in input processing
+        if let Some(seconds) = actions.wait_for {
+            let kind = systems::rest::RestKind::For(seconds);
+            self.input.clear_path();
+            match systems::rest::Rest::start(&state.world, &state.grid, state.player_entity, kind, state.game_clock.time) {
+                Ok(rest) => self.input.rest = Some(rest),
+                Err(reason) => self.events.push(crate::events::GameEvent::RestStopped { kind, reason }),
+            }
+        }
+
         // Handle ability button click from UI
         if actions.use_ability {
             self.try_use_class_ability();
@@ -482,11 +649,30 @@ impl GameEngine {
             self.try_use_ranger_ability(index);
         }
 
+        // Companion order picked from the command menu - issued with the next frame's input
+        if let Some(order) = actions.companion_order {
+            self.input.pending_command = Some(order);
+        }
+        if let (Some(menu), true) = (&mut self.input.command_menu, actions.next_companion) {
+            menu.next_companion();
+        }
+
+        // Tile clicked on the map - travelled to with the next frame's input
+        if let Some((x, y)) = actions.travel_to {
+            self.input.pending_travel = Some(systems::travel::TravelTarget::Tile(x, y));
+        }
+
         let ui_state = self.ui_state.as_mut().expect("UI state should exist");
         // Apply UI state changes
         if let Some(targeting) = ui_result.enter_targeting {
             self.input.targeting_mode = Some(targeting);
         }
+        if actions.wait_for.is_some() {
+            ui_state.show_wait_prompt = false;
+        }
+        if actions.travel_to.is_some() {
+            ui_state.show_map = false;
+        }
         if ui_result.close_inventory {
             ui_state.show_inventory = false;
         }
@@ -502,6 +688,7 @@ impl GameEngine {
         if ui_result.close_shop {
             ui_state.close_shop();
         }
+        None
     }
 
     /// Get the grid for rendering (returns None if not playing).
@@ -551,7 +738,7 @@ impl GameEngine {
 
     /// Should show grid lines?
     pub fn show_grid_lines(&self) -> bool {
-        self.ui_state.as_ref().map(|u| u.show_grid_lines).unwrap_or(false)
+        self.settings.show_grid_lines
     }
 
     /// Get player position for lighting (uses visual position for smooth lighting).
@@ -605,21 +792,22 @@ impl GameEngine {
         tileset: &crate::multi_tileset::MultiTileset,
         ui_icons: &crate::ui::UiIcons,
     ) -> crate::ui::UiActions {
+        let screens = ProfileScreens {
+            options: self.show_options.then_some(&self.settings),
+            bestiary: self.show_bestiary.then_some(&self.bestiary),
+        };
         match self.game_mode {
             GameMode::StartScreen => {
-                // Show class selection screen
-                let start_result = crate::ui::run_start_screen(
+                // Show class selection screen (start_game is set if player clicked Start)
+                crate::ui::run_start_screen(
                     egui_glow,
                     window,
                     tileset,
                     ui_icons,
                     &mut self.selected_class,
-                );
-
-                // Return start_game action if player clicked Start
-                let mut actions = crate::ui::UiActions::default();
-                actions.start_game = start_result;
-                actions
+                    &mut self.selected_floor_size,
+                    screens,
+                )
             }
             GameMode::Playing => {
                 let state = self.state.as_ref().expect("State should exist when playing");
@@ -644,10 +832,18 @@ impl GameEngine {
                     ui_icons,
                     &self.vfx.effects,
                     &life_drain_beams,
-                    self.input.targeting_mode.as_ref(),
-                    self.input.ability_targeting_mode.as_ref(),
-                    self.input.mouse_pos,
-                    state.game_clock.time,
+                    RunScreens {
+                        profile: screens,
+                        examine: self.input.examine.as_ref(),
+                        command_menu: self.input.command_menu.as_ref(),
+                        bindings: &self.input.bindings,
+                    },
+                    RunInput {
+                        targeting_mode: self.input.targeting_mode.as_ref(),
+                        ability_targeting_mode: self.input.ability_targeting_mode.as_ref(),
+                        mouse_pos: self.input.mouse_pos,
+                        game_time: state.game_clock.time,
+                    },
                 )
             }
         }
@@ -655,6 +851,73 @@ impl GameEngine {
 
     // --- Private methods ---
 
+    /// Write the key bindings to the config file, warning if that fails
+    fn save_key_bindings(&self) {
+        if let Err(e) = self.input.bindings.save() {
+            eprintln!("Warning: could not save key bindings: {}", e);
+        }
+    }
+
+    /// Write the bestiary to the config file, warning if that fails
+    fn save_bestiary(&self) {
+        if let Err(e) = self.bestiary.save() {
+            eprintln!("Warning: could not save the bestiary: {}", e);
+        }
+    }
+
+    /// Write the settings to the config file, warning if that fails
+    fn save_settings(&self) {
+        if let Err(e) = self.settings.save() {
+            eprintln!("Warning: could not save settings: {}", e);
+        }
+    }
+
+    /// Escape closes whatever is on top: targeting, the command menu, the options
+    /// screen, the map or the key bindings screen, then opens or closes the pause menu. On the start
+    /// screen it quits.
+    fn handle_escape(&mut self) -> Option<WindowAction> {
+        if self.input.is_targeting() {
+            self.input.cancel_targeting();
+        } else if self.input.examine.is_some() {
+            self.input.examine = None;
+        } else if self.input.command_menu.is_some() {
+            self.input.command_menu = None;
+        } else if self.show_options {
+            self.toggle_options();
+        } else if self.show_bestiary {
+            self.show_bestiary = false;
+        } else if let Some(ui_state) = self.ui_state.as_mut() {
+            if ui_state.show_map {
+                ui_state.toggle_map();
+            } else if ui_state.show_key_bindings {
+                ui_state.toggle_key_bindings();
+            } else {
+                ui_state.toggle_pause_menu();
+            }
+        } else {
+            return Some(WindowAction::Exit);
+        }
+        None
+    }
+
+    /// Write this frame's monster sightings, kills and deaths into the bestiary,
+    /// saving it if anything was new
+    fn update_bestiary(&mut self) {
+        let (Some(state), Some(ui_state)) = (self.state.as_ref(), self.ui_state.as_mut()) else { return };
+        ui_state.monster_notes.note_sightings(&state.world, &state.grid);
+        if self.bestiary.record(&mut ui_state.monster_notes) {
+            self.save_bestiary();
+        }
+    }
+
+    /// Open or close the options screen. Settings are saved on close, not on every slider move.
+    fn toggle_options(&mut self) {
+        self.show_options = !self.show_options;
+        if !self.show_options {
+            self.save_settings();
+        }
+    }
+
     /// Process input. Only called when playing (state must exist).
     fn process_input(&mut self, camera: &mut Camera) -> InputResult {
         let state = self.state.as_mut().expect("process_input called without state");
@@ -664,8 +927,10 @@ impl GameEngine {
             &mut self.input,
             &state.world,
             &state.grid,
+            &state.spatial_cache,
             camera,
             state.player_entity,
+            state.game_clock.time,
         );
 
         let mut result = InputResult::default();
@@ -675,8 +940,20 @@ impl GameEngine {
         if frame.toggle_inventory {
             ui_state.toggle_inventory();
         }
-        if frame.toggle_grid_lines {
-            ui_state.toggle_grid_lines();
+        result.toggle_grid_lines = frame.toggle_grid_lines;
+        result.toggle_options = frame.toggle_options;
+        result.toggle_bestiary = frame.toggle_bestiary;
+        if frame.toggle_message_history {
+            ui_state.toggle_message_history();
+        }
+        if frame.toggle_map {
+            ui_state.toggle_map();
+        }
+        if frame.toggle_key_bindings {
+            ui_state.toggle_key_bindings();
+        }
+        if frame.toggle_wait_prompt {
+            ui_state.toggle_wait_prompt();
         }
 
         // Enter key: container interaction (chests, bones, ground items)
@@ -714,6 +991,26 @@ impl GameEngine {
             return result;
         }
 
+        // Auto-explore picking things up on its way; exploring or travel ending early
+        if let Some(pile) = frame.explore_loot {
+            systems::exploration::loot_wanted(&mut state.world, state.player_entity, pile, &mut self.events);
+            systems::cleanup_empty_ground_piles(&mut state.world);
+        }
+        if let Some(reason) = frame.explore_stopped {
+            self.events.push(crate::events::GameEvent::ExploreStopped { reason });
+        }
+        if let Some((target, failure)) = frame.travel_failed {
+            self.events.push(crate::events::GameEvent::TravelFailed { target, failure });
+        }
+        if let Some((kind, reason)) = frame.rest_stopped {
+            self.events.push(crate::events::GameEvent::RestStopped { kind, reason });
+        }
+
+        // Sneak toggle (C key)
+        if frame.toggle_sneak {
+            systems::noise::toggle_sneak(&mut state.world, state.player_entity, &mut self.events);
+        }
+
         // Class ability activation (Q key)
         if frame.ability_pressed {
             activate_class_ability(
@@ -840,9 +1137,9 @@ impl GameEngine {
             if !skeleton_spawns.is_empty() {
                 let state = self.state.as_mut().expect("State should exist");
                 for (x, y) in &skeleton_spawns {
-                    let skeleton = spawning::enemies::SKELETON.spawn(&mut state.world, *x, *y);
-                    state.spatial_cache.register_entity(skeleton, (*x, *y), true, false);
                     let mut rng = rand::thread_rng();
+                    let skeleton = spawning::enemies::SKELETON.spawn(&mut state.world, *x, *y, &mut rng);
+                    state.spatial_cache.register_entity(skeleton, (*x, *y), true, false);
                     initialization::initialize_single_ai_actor(
                         &mut state.world,
                         &state.grid,
@@ -983,25 +1280,7 @@ impl GameEngine {
             return;
         }
 
-        // Take ownership of grid for transition
-        let current_grid = std::mem::replace(
-            &mut state.grid,
-            crate::grid::Grid::new(1, 1),
-        );
-
-        let result = handle_floor_transition(
-            &mut state.world,
-            current_grid,
-            &mut state.floors,
-            state.current_floor,
-            direction,
-            state.player_entity,
-            &state.game_clock,
-            &mut state.action_scheduler,
-            &mut state.active_ai_tracker,
-            &mut state.spatial_cache,
-            &mut self.events,
-        );
+        let result = handle_floor_transition(state, direction, &mut self.events);
 
         state.grid = result.new_grid;
         state.current_floor = result.new_floor;
@@ -1378,4 +1657,7 @@ fn activate_ranger_ability(
 #[derive(Default)]
 struct InputResult {
     toggle_fullscreen: bool,
+    toggle_grid_lines: bool,
+    toggle_options: bool,
+    toggle_bestiary: bool,
 }
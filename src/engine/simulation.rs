@@ -1,11 +1,12 @@
 //! Game simulation - turn execution, time advancement, and event processing.
 
 use crate::active_ai_tracker::ActiveAITracker;
-use crate::components::{ActionType, Actor, EffectType, Inventory, ItemType};
+use crate::components::{ActionType, Actor, ChaseAI, EffectType, Faction, Inventory, ItemType};
 use crate::constants;
 use crate::events::{EventQueue, GameEvent, StairDirection};
 use crate::grid::Grid;
 use crate::input::TargetingMode;
+use crate::pathfinding::CostProfile;
 use crate::queries;
 use crate::spatial_cache::SpatialCache;
 use crate::systems;
@@ -276,9 +277,10 @@ pub fn advance_until_player_ready(
         time_system::tick_health_regen(world, clock.time, Some(events));
         time_system::tick_energy_regen(world, clock.time, Some(events));
         time_system::tick_burn_damage(world, clock.time, events);
-        time_system::tick_status_effects(world, elapsed);
+        time_system::tick_status_effects(world, elapsed, events);
         time_system::tick_ability_cooldowns(world, elapsed);
         time_system::tick_ranged_cooldowns(world, elapsed);
+        time_system::tick_support_cooldowns(world, elapsed);
         systems::ai::tick_threat_decay(world, grid, &*spatial_cache, elapsed);
 
         time_system::complete_action(world, grid, next_entity, spatial_cache, events, clock.time, clock, scheduler);
@@ -286,7 +288,17 @@ pub fn advance_until_player_ready(
         // After player completes an action, check for dormant entities that should wake up
         if next_entity == player_entity {
             if let Some(player_pos) = queries::get_entity_position(world, player_entity) {
+                // Move a few dormant entities first so any that wander into range wake up now
+                systems::offscreen::simulate(world, grid, player_pos, clock.time, active_tracker, spatial_cache, rng);
                 let newly_active = active_tracker.update_on_player_move(world, player_pos);
+                if spatial_cache.nav_maps().origin() != Some(player_pos) {
+                    let blocked = systems::ai::ai_pathfinding_blocked(world, spatial_cache);
+                    let profile = CostProfile::CREATURE;
+                    let costs = systems::hazards::HazardCosts::new(world, grid, None, profile);
+                    spatial_cache
+                        .nav_maps_mut()
+                        .rebuild(grid, player_pos, &blocked, profile, &|pos| costs.cost(grid, pos));
+                }
                 // Schedule newly awakened entities
                 for ai_entity in newly_active {
                     scheduler.schedule(ai_entity, clock.time + 0.1);
@@ -359,6 +371,7 @@ pub fn wait_for_energy(
                 time_system::tick_energy_regen(world, clock.time, Some(events));
                 time_system::tick_ability_cooldowns(world, elapsed);
                 time_system::tick_ranged_cooldowns(world, elapsed);
+                time_system::tick_support_cooldowns(world, elapsed);
                 break;
             }
 
@@ -370,9 +383,10 @@ pub fn wait_for_energy(
             time_system::tick_health_regen(world, clock.time, Some(events));
             time_system::tick_energy_regen(world, clock.time, Some(events));
             time_system::tick_burn_damage(world, clock.time, events);
-            time_system::tick_status_effects(world, elapsed);
+            time_system::tick_status_effects(world, elapsed, events);
             time_system::tick_ability_cooldowns(world, elapsed);
             time_system::tick_ranged_cooldowns(world, elapsed);
+            time_system::tick_support_cooldowns(world, elapsed);
 
             // Complete the action
             time_system::complete_action(world, grid, next_entity, spatial_cache, events, clock.time, clock, scheduler);
@@ -453,6 +467,11 @@ pub fn process_events_with_audio(
     for event in event_list {
         vfx.handle_event(&event, grid);
         ui_state.handle_event(&event);
+        ui_state.message_log.handle_event(&event, world, grid, player_entity);
+        ui_state.monster_notes.handle_event(&event, world, player_entity);
+        if let Some(noise) = systems::noise::noise_from_event(world, &event) {
+            systems::noise::alert_listeners(world, grid, spatial_cache, noise, events);
+        }
 
         match &event {
             GameEvent::DoorOpened { door, .. } => {
@@ -468,6 +487,12 @@ pub fn process_events_with_audio(
             GameEvent::ContainerOpened { container, .. } => {
                 systems::handle_container_opened(world, *container);
             }
+            GameEvent::ItemDropped { position, .. } => {
+                // Index the pile so radius lookups (like running past items) find it
+                if let Some(pile) = systems::inventory::find_ground_items_at_position(world, position.0, position.1) {
+                    spatial_cache.track_item(pile, *position);
+                }
+            }
             GameEvent::FloorTransition { direction, .. } => {
                 result.floor_transition = Some(*direction);
             }
@@ -479,12 +504,22 @@ pub fn process_events_with_audio(
                     result.player_took_damage = true;
                 }
             }
+            // A swing at (or by) the player still interrupts path following
+            GameEvent::AttackMissed { attacker, target, .. }
+                if *attacker == player_entity || *target == player_entity =>
+            {
+                result.player_attacked = true;
+            }
             GameEvent::AIStateChanged { entity, new_state } => {
                 if *new_state == crate::components::AIState::Chasing {
                     if let Ok(pos) = world.get::<&crate::components::Position>(*entity) {
                         vfx.spawn_alert(pos.x as f32 + 0.5, pos.y as f32 + 0.5);
                     }
-                    result.enemy_spotted_player = true;
+                    // Monsters going for each other don't interrupt the player
+                    let target = world.get::<&ChaseAI>(*entity).ok().and_then(|ai| ai.current_target);
+                    if target.and_then(|t| systems::factions::faction_of(world, t)) == Some(Faction::Player) {
+                        result.enemy_spotted_player = true;
+                    }
                 }
             }
             GameEvent::CoffinSkeletonSpawn { position } => {
@@ -641,6 +676,13 @@ pub fn process_ui_actions(
                     weapon_type: item_type,
                 });
             }
+            systems::ItemUseResult::IsArmor { item_type, item_index } => {
+                systems::actions::apply_equip_armor(world, player_entity, item_index);
+                events.push(GameEvent::ArmorEquipped {
+                    entity: player_entity,
+                    armor_type: item_type,
+                });
+            }
             systems::ItemUseResult::Used { item_type } => {
                 // Emit PotionDrunk event for potions
                 if matches!(
@@ -695,6 +737,16 @@ pub fn process_ui_actions(
         systems::actions::apply_drop_equipped_weapon(world, player_entity, events);
     }
 
+    // Unequip armor (put back in inventory)
+    if let Some(slot) = actions.unequip_armor {
+        systems::actions::apply_unequip_armor(world, player_entity, slot);
+    }
+
+    // Drop equipped armor
+    if let Some(slot) = actions.drop_equipped_armor {
+        systems::actions::apply_drop_equipped_armor(world, player_entity, slot, events);
+    }
+
     result
 }
 
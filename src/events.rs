@@ -29,6 +29,29 @@ pub enum GameEvent {
         target: Entity,
         target_pos: (f32, f32),
         damage: i32,
+        damage_type: crate::components::DamageType,
+        /// True if the hit rolled a critical
+        crit: bool,
+    },
+    /// An attack failed its hit roll against the target's evasion
+    AttackMissed {
+        attacker: Entity,
+        target: Entity,
+        target_pos: (f32, f32),
+        /// True for arrows (which fly on past the target), false for melee
+        ranged: bool,
+    },
+    /// An attack caught an enemy that hadn't noticed anyone yet (bonus damage)
+    SneakAttack {
+        attacker: Entity,
+        target: Entity,
+    },
+    /// An owner gave their companions a new order
+    CompanionsOrdered {
+        owner: Entity,
+        /// The one companion ordered, or `None` for all of them
+        companion: Option<Entity>,
+        order: crate::components::CompanionOrder,
     },
     /// An entity died
     EntityDied {
@@ -69,6 +92,11 @@ pub enum GameEvent {
         entity: Entity,
         amount: i32,
     },
+    /// A timed status effect ran out
+    StatusEffectExpired {
+        entity: Entity,
+        effect_type: crate::components::EffectType,
+    },
     /// Player leveled up
     LevelUp {
         new_level: u32,
@@ -205,6 +233,8 @@ pub enum GameEvent {
     /// A fire trap was triggered
     FireTrapTriggered {
         trap: Entity,
+        /// Who placed the trap
+        owner: Entity,
         victim: Entity,
         position: (i32, i32),
     },
@@ -248,6 +278,43 @@ pub enum GameEvent {
         entity: Entity,
         weapon_type: crate::components::ItemType,
     },
+    /// An entity started or stopped sneaking
+    SneakToggled {
+        entity: Entity,
+        sneaking: bool,
+    },
+    /// A support caster healed an ally
+    HealCast {
+        caster: Entity,
+        target: Entity,
+        target_pos: (f32, f32),
+        amount: i32,
+    },
+    /// A support caster granted a status effect to an ally
+    BuffCast {
+        caster: Entity,
+        target: Entity,
+        effect_type: crate::components::EffectType,
+    },
+    /// An armor piece or accessory was put on
+    ArmorEquipped {
+        entity: Entity,
+        armor_type: crate::components::ItemType,
+    },
+    /// Auto-explore stopped by itself
+    ExploreStopped {
+        reason: crate::systems::exploration::ExploreStop,
+    },
+    /// A travel command couldn't start
+    TravelFailed {
+        target: crate::systems::travel::TravelTarget,
+        failure: crate::systems::travel::TravelFailure,
+    },
+    /// Resting or waiting stopped, or couldn't start
+    RestStopped {
+        kind: crate::systems::rest::RestKind,
+        reason: crate::systems::rest::RestStop,
+    },
 }
 
 /// Simple event queue - events are pushed during update, processed at end of frame
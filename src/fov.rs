@@ -188,28 +188,10 @@ fn cast_light<F>(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::tile::{Tile, TileType};
+    use crate::tile::TileType;
 
     fn make_grid(width: usize, height: usize, walls: &[(i32, i32)]) -> Grid {
-        let mut grid = Grid {
-            width,
-            height,
-            tiles: vec![Tile::new(TileType::Floor); width * height],
-            chest_positions: vec![],
-            door_positions: vec![],
-            brazier_positions: vec![],
-            decals: vec![],
-            stairs_up_pos: None,
-            stairs_down_pos: None,
-            starting_room: None,
-            illumination: vec![0.0; width * height],
-            themed_rooms: vec![],
-            water_positions: vec![],
-            coffin_positions: vec![],
-            barrel_positions: vec![],
-            shop_position: None,
-            shop_decor_positions: vec![],
-        };
+        let mut grid = Grid::filled(width, height, TileType::Floor);
         for &(x, y) in walls {
             if let Some(tile) = grid.get_mut(x, y) {
                 tile.tile_type = TileType::Wall;
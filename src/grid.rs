@@ -1,5 +1,48 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::constants::{
+    DUNGEON_DEFAULT_HEIGHT, DUNGEON_DEFAULT_WIDTH, DUNGEON_GROWTH_PER_FLOOR, DUNGEON_HUGE_BASE_SIZE,
+    DUNGEON_MAX_HUGE_SIZE, DUNGEON_MAX_STANDARD_SIZE,
+};
 use crate::dungeon_gen::{DungeonGenerator, Rect, RoomTheme, ThemedRoom};
 use crate::tile::{SpriteSheet, Tile};
+#[cfg(test)]
+use crate::tile::TileType;
+
+/// Floor size setting chosen at the start of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FloorSize {
+    /// Floors start at the default size and grow with depth
+    #[default]
+    Standard,
+    /// Very large floors (300x300 and up) that also grow with depth
+    Huge,
+}
+
+impl FloorSize {
+    /// Width and height of the given floor (floor 0 is the first floor)
+    pub fn dimensions(self, floor_num: u32) -> (usize, usize) {
+        let growth = floor_num as usize * DUNGEON_GROWTH_PER_FLOOR;
+        match self {
+            FloorSize::Standard => (
+                (DUNGEON_DEFAULT_WIDTH + growth).min(DUNGEON_MAX_STANDARD_SIZE),
+                (DUNGEON_DEFAULT_HEIGHT + growth).min(DUNGEON_MAX_STANDARD_SIZE),
+            ),
+            FloorSize::Huge => {
+                let size = (DUNGEON_HUGE_BASE_SIZE + growth).min(DUNGEON_MAX_HUGE_SIZE);
+                (size, size)
+            }
+        }
+    }
+}
+
+/// Source of grid revisions. Shared by every grid, so a revision taken from
+/// one floor never matches another floor's.
+static NEXT_REVISION: AtomicU64 = AtomicU64::new(1);
+
+fn next_revision() -> u64 {
+    NEXT_REVISION.fetch_add(1, Ordering::Relaxed)
+}
 
 /// A decorative decal placed on a tile
 #[derive(Debug, Clone, Copy)]
@@ -24,6 +67,18 @@ pub struct Grid {
     pub starting_room: Option<Rect>,
     /// Per-tile illumination values (computed each frame for visible tiles)
     pub illumination: Vec<f32>,
+    /// Indices of tiles currently marked visible (lets FOV updates avoid full-map clears)
+    pub visible_tiles: Vec<usize>,
+    /// Indices of tiles that received light-based illumination on the last update
+    pub lit_tiles: Vec<usize>,
+    /// Indices of tiles with an active magical reveal (Scroll of Reveal)
+    pub revealed_tiles: Vec<usize>,
+    /// Changes whenever the set of visible tiles is recomputed (see `mark_view_changed`)
+    pub view_revision: u64,
+    /// Changes when tiles anywhere on the floor may have become explored (see `mark_map_changed`)
+    pub map_revision: u64,
+    /// All walkable positions, collected once at generation for spawn placement
+    pub walkable_positions: Vec<(i32, i32)>,
     /// Themed rooms for wall/door styling
     pub themed_rooms: Vec<ThemedRoom>,
     /// Water tile positions for animated water entities
@@ -48,10 +103,22 @@ impl Grid {
         // Generate dungeon using BSP
         let result = DungeonGenerator::generate(width, height, floor_num);
 
+        let mut tiles = result.tiles;
+        for ((x, y), _) in &result.door_positions {
+            tiles[*y as usize * width + *x as usize].doorway = true;
+        }
+
+        let walkable_positions = tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, tile)| tile.tile_type.is_walkable())
+            .map(|(idx, _)| ((idx % width) as i32, (idx / width) as i32))
+            .collect();
+
         Self {
             width,
             height,
-            tiles: result.tiles,
+            tiles,
             chest_positions: result.chest_positions,
             door_positions: result.door_positions,
             brazier_positions: result.brazier_positions,
@@ -60,6 +127,12 @@ impl Grid {
             stairs_down_pos: result.stairs_down_pos,
             starting_room: result.starting_room,
             illumination: vec![0.0; width * height],
+            visible_tiles: Vec::new(),
+            lit_tiles: Vec::new(),
+            revealed_tiles: Vec::new(),
+            view_revision: next_revision(),
+            map_revision: next_revision(),
+            walkable_positions,
             themed_rooms: result.themed_rooms,
             water_positions: result.water_positions,
             coffin_positions: result.coffin_positions,
@@ -69,6 +142,65 @@ impl Grid {
         }
     }
 
+    /// A bare floor with every tile set to `tile_type` and no features, for tests
+    #[cfg(test)]
+    pub fn filled(width: usize, height: usize, tile_type: TileType) -> Self {
+        Self {
+            width,
+            height,
+            tiles: vec![Tile::new(tile_type); width * height],
+            chest_positions: Vec::new(),
+            door_positions: Vec::new(),
+            brazier_positions: Vec::new(),
+            decals: Vec::new(),
+            stairs_up_pos: None,
+            stairs_down_pos: None,
+            starting_room: None,
+            illumination: vec![0.0; width * height],
+            visible_tiles: Vec::new(),
+            lit_tiles: Vec::new(),
+            revealed_tiles: Vec::new(),
+            view_revision: next_revision(),
+            map_revision: next_revision(),
+            walkable_positions: Vec::new(),
+            themed_rooms: Vec::new(),
+            water_positions: Vec::new(),
+            coffin_positions: Vec::new(),
+            barrel_positions: Vec::new(),
+            shop_position: None,
+            shop_decor_positions: Vec::new(),
+        }
+    }
+
+    /// A bare floor whose tiles all start explored and/or visible, for tests
+    #[cfg(test)]
+    pub fn filled_known(width: usize, height: usize, explored: bool, visible: bool) -> Self {
+        let mut grid = Self::filled(width, height, TileType::Floor);
+        for tile in &mut grid.tiles {
+            tile.explored = explored;
+            tile.visible = visible;
+        }
+        grid
+    }
+
+    /// Record an FOV update. Only tiles in `visible_tiles` before or after it changed.
+    pub fn mark_view_changed(&mut self) {
+        self.view_revision = next_revision();
+    }
+
+    /// Record that tiles outside the view were explored (mapping, magical reveals)
+    pub fn mark_map_changed(&mut self) {
+        self.map_revision = next_revision();
+    }
+
+    /// Index into `tiles` for in-bounds coordinates
+    pub fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
+            return None;
+        }
+        Some(y as usize * self.width + x as usize)
+    }
+
     pub fn get(&self, x: i32, y: i32) -> Option<&Tile> {
         if x < 0 || y < 0 || x >= self.width as i32 || y >= self.height as i32 {
             return None;
@@ -87,4 +219,9 @@ impl Grid {
     pub fn is_walkable(&self, x: i32, y: i32) -> bool {
         self.get(x, y).map(|t| t.tile_type.is_walkable()).unwrap_or(false)
     }
+
+    /// Whether a door stands at this position (open or closed)
+    pub fn is_doorway(&self, x: i32, y: i32) -> bool {
+        self.get(x, y).map(|t| t.doorway).unwrap_or(false)
+    }
 }
@@ -4,9 +4,17 @@
 //! This module is purely about input state - it does NOT execute game logic.
 
 use crate::camera::Camera;
-use crate::components::{AbilityType, Attackable, BlocksMovement, Container, Door, Health, ItemType, Player, Position, TamedBy, Tameable};
+use crate::components::{AbilityType, Attackable, BlocksMovement, CompanionAI, CompanionOrder, Container, Door, Health, ItemType, Name, Player, Position, TamedBy, Tameable};
 use crate::grid::Grid;
+use crate::keybindings::{self, Command, KeyBindings, KeyCombo};
 use crate::pathfinding;
+use crate::spatial_cache::SpatialCache;
+use crate::systems::examine::Examine;
+use crate::systems::exploration::{self, AutoExplore};
+use crate::systems::hazards;
+use crate::systems::rest::{Rest, RestKind, RestStop};
+use crate::systems::running::Run;
+use crate::systems::travel::{self, TravelFailure, TravelTarget};
 use crate::queries;
 use crate::systems::player_input::{self, PlayerIntent};
 use hecs::{Entity, World};
@@ -16,6 +24,9 @@ use winit::keyboard::KeyCode;
 /// Maximum distance an enemy can move from the click origin before pursuit is abandoned
 pub const MAX_PURSUIT_DISTANCE: i32 = 8;
 
+/// Tiles the look cursor jumps per run key (Shift+direction)
+pub const EXAMINE_CURSOR_JUMP: i32 = 5;
+
 /// Targeting mode for items that require click-to-target
 #[derive(Clone, Debug)]
 pub struct TargetingMode {
@@ -38,6 +49,56 @@ pub struct AbilityTargetingMode {
     pub max_range: i32,
 }
 
+/// Radial menu for ordering companions, opened at the mouse cursor (V)
+#[derive(Clone, Debug)]
+pub struct CommandMenu {
+    /// Screen position the menu is centered on
+    pub screen_pos: (f32, f32),
+    /// Tile under the cursor when the menu was opened (Guard spot)
+    pub tile: (i32, i32),
+    /// Enemy under the cursor when the menu was opened (Attack target)
+    pub target: Option<Entity>,
+    /// The player's companions, by name, in party panel order
+    pub companions: Vec<(Entity, &'static str)>,
+    /// Index into `companions` of the one to order (`None` orders them all)
+    pub selected: Option<usize>,
+}
+
+impl CommandMenu {
+    /// The menu's entries in hotkey order (1-6). Orders that make no sense for
+    /// the spot the menu was opened on are `None` and shown disabled.
+    pub fn options(&self, grid: &Grid) -> [(&'static str, Option<CompanionOrder>); 6] {
+        let (x, y) = self.tile;
+        [
+            ("Follow", Some(CompanionOrder::Follow)),
+            ("Stay", Some(CompanionOrder::Stay)),
+            ("Attack", self.target.map(|target| CompanionOrder::Attack { target })),
+            ("Guard", grid.is_walkable(x, y).then_some(CompanionOrder::Guard { x, y })),
+            ("Passive", Some(CompanionOrder::Passive)),
+            ("Return", Some(CompanionOrder::Return)),
+        ]
+    }
+
+    /// The companion the order goes to (`None` for the whole party)
+    pub fn companion(&self) -> Option<Entity> {
+        self.selected.and_then(|i| self.companions.get(i)).map(|&(entity, _)| entity)
+    }
+
+    /// Who the order goes to, as shown in the menu
+    pub fn recipient(&self) -> &'static str {
+        self.selected.and_then(|i| self.companions.get(i)).map_or("Everyone", |&(_, name)| name)
+    }
+
+    /// Order the next companion instead, going back to everyone after the last
+    pub fn next_companion(&mut self) {
+        self.selected = match self.selected {
+            None if !self.companions.is_empty() => Some(0),
+            Some(i) if i + 1 < self.companions.len() => Some(i + 1),
+            _ => None,
+        };
+    }
+}
+
 /// What the player clicked on - determines interaction behavior
 #[derive(Debug, Clone, Copy)]
 #[allow(dead_code)] // Entity fields reserved for direct interaction
@@ -80,6 +141,22 @@ pub struct InputState {
     pub pending_right_click: bool,
     /// Pending left-click to process (for targeting confirmation)
     pub pending_left_click: bool,
+    /// Open companion command menu, if any
+    pub command_menu: Option<CommandMenu>,
+    /// Order picked from the command menu UI, to be issued this frame
+    pub pending_command: Option<CompanionOrder>,
+    /// Travel picked on the full-screen map, to be started this frame
+    pub pending_travel: Option<TravelTarget>,
+    /// Auto-explore in progress (re-plans `player_path` every step)
+    pub auto_explore: Option<AutoExplore>,
+    /// Rest or timed wait in progress (waits every turn until it stops)
+    pub rest: Option<Rest>,
+    /// Shift-run in progress (picks the next step every turn until it stops)
+    pub run: Option<Run>,
+    /// Look mode: the tile being examined, if looking around
+    pub examine: Option<Examine>,
+    /// Which keys trigger which commands
+    pub bindings: KeyBindings,
 }
 
 impl InputState {
@@ -98,15 +175,25 @@ impl InputState {
             ability_targeting_mode: None,
             pending_right_click: false,
             pending_left_click: false,
+            command_menu: None,
+            pending_command: None,
+            pending_travel: None,
+            auto_explore: None,
+            rest: None,
+            run: None,
+            examine: None,
+            bindings: KeyBindings::default(),
         }
     }
 
-    /// Clear the current path and any pursuit state
+    /// Clear the current path and any pursuit, auto-explore or run state
     pub fn clear_path(&mut self) {
         self.player_path.clear();
         self.player_path_destination = None;
         self.pursuit_target = None;
         self.pursuit_origin = None;
+        self.auto_explore = None;
+        self.run = None;
     }
 
     /// Get the next step in the path, if any
@@ -160,11 +247,27 @@ pub struct InputResult {
     pub toggle_inventory: bool,
     /// Player wants to toggle grid lines
     pub toggle_grid_lines: bool,
+    /// Player wants to toggle the message history window
+    pub toggle_message_history: bool,
+    /// Player wants to open or close the full-screen map
+    pub toggle_map: bool,
+    /// Player wants to start or stop looking around
+    pub toggle_examine: bool,
+    /// Player wants to open or close the key bindings screen
+    pub toggle_key_bindings: bool,
+    /// Player wants to open or close the options screen
+    pub toggle_options: bool,
+    /// Player wants to open or close the bestiary
+    pub toggle_bestiary: bool,
+    /// Player wants to start or stop sneaking
+    pub toggle_sneak: bool,
     /// Player pressed Enter (take all / loot)
     pub enter_pressed: bool,
     /// Movement intent (dx, dy)
     pub movement: Option<(i32, i32)>,
-    /// Attack direction intent (dx, dy) - Shift+movement
+    /// Run direction (dx, dy) - Shift+movement
+    pub run: Option<(i32, i32)>,
+    /// Attack direction intent (dx, dy) - Alt+movement
     pub attack_direction: Option<(i32, i32)>,
     /// Interact direction intent (dx, dy) - Ctrl+movement
     pub interact_direction: Option<(i32, i32)>,
@@ -176,6 +279,20 @@ pub struct InputResult {
     pub secondary_ability_pressed: bool,
     /// Ranger ability key pressed (0-3 for keys 1-4)
     pub ranger_ability: Option<usize>,
+    /// Player wants to open or close the companion command menu
+    pub toggle_command_menu: bool,
+    /// Player wants the command menu to order the next companion
+    pub next_companion: bool,
+    /// Command menu entry picked by hotkey (0-5 for keys 1-6)
+    pub command_choice: Option<usize>,
+    /// Player wants to start or stop auto-exploring
+    pub toggle_auto_explore: bool,
+    /// Travel command (> and < for stairs, G for the shop, P for items)
+    pub travel: Option<TravelTarget>,
+    /// Player wants to start or stop resting (R)
+    pub rest: bool,
+    /// Player wants to open or close the wait prompt (Z)
+    pub toggle_wait_prompt: bool,
 }
 
 impl Default for InputResult {
@@ -184,14 +301,29 @@ impl Default for InputResult {
             toggle_fullscreen: false,
             toggle_inventory: false,
             toggle_grid_lines: false,
+            toggle_message_history: false,
+            toggle_map: false,
+            toggle_examine: false,
+            toggle_key_bindings: false,
+            toggle_options: false,
+            toggle_bestiary: false,
+            toggle_sneak: false,
             enter_pressed: false,
             movement: None,
+            run: None,
             attack_direction: None,
             interact_direction: None,
             wait: false,
             ability_pressed: false,
             secondary_ability_pressed: false,
             ranger_ability: None,
+            toggle_command_menu: false,
+            next_companion: false,
+            command_choice: None,
+            toggle_auto_explore: false,
+            travel: None,
+            rest: false,
+            toggle_wait_prompt: false,
         }
     }
 }
@@ -201,93 +333,76 @@ impl Default for InputResult {
 pub fn process_keyboard(input: &mut InputState) -> InputResult {
     let mut result = InputResult::default();
 
-    // Toggle fullscreen
-    if input.keys_pressed.remove(&KeyCode::F11) {
-        result.toggle_fullscreen = true;
-    }
-
-    // Toggle inventory
-    if input.keys_pressed.remove(&KeyCode::KeyI) {
-        result.toggle_inventory = true;
-    }
-
-    // Toggle grid lines
-    if input.keys_pressed.remove(&KeyCode::BracketRight) {
-        result.toggle_grid_lines = true;
-    }
-
-    // Enter key
-    if input.keys_pressed.remove(&KeyCode::Enter) {
-        result.enter_pressed = true;
-    }
-
-    // Wait (skip turn) - period key
-    if input.keys_pressed.remove(&KeyCode::Period) {
-        result.wait = true;
-    }
-
-    // Ability hotkey (Q)
-    if input.keys_pressed.remove(&KeyCode::KeyQ) {
-        result.ability_pressed = true;
-    }
-
-    // Secondary ability hotkey (E) - Druid only
-    if input.keys_pressed.remove(&KeyCode::KeyE) {
-        result.secondary_ability_pressed = true;
-    }
-
-    // Ranger ability hotkeys (1-4)
-    if input.keys_pressed.remove(&KeyCode::Digit1) {
-        result.ranger_ability = Some(0);
-    } else if input.keys_pressed.remove(&KeyCode::Digit2) {
-        result.ranger_ability = Some(1);
-    } else if input.keys_pressed.remove(&KeyCode::Digit3) {
-        result.ranger_ability = Some(2);
-    } else if input.keys_pressed.remove(&KeyCode::Digit4) {
-        result.ranger_ability = Some(3);
+    for command in take_commands(input) {
+        match command {
+            Command::ToggleFullscreen => result.toggle_fullscreen = true,
+            Command::ToggleInventory => result.toggle_inventory = true,
+            Command::ToggleGridLines => result.toggle_grid_lines = true,
+            Command::ToggleMessageHistory => result.toggle_message_history = true,
+            Command::ToggleMap => result.toggle_map = true,
+            Command::Examine => result.toggle_examine = true,
+            Command::ToggleKeyBindings => result.toggle_key_bindings = true,
+            Command::ToggleOptions => result.toggle_options = true,
+            Command::ToggleBestiary => result.toggle_bestiary = true,
+            Command::ToggleSneak => result.toggle_sneak = true,
+            Command::TakeAll => result.enter_pressed = true,
+            Command::Wait => result.wait = true,
+            Command::Rest => result.rest = true,
+            Command::WaitPrompt => result.toggle_wait_prompt = true,
+            Command::ClassAbility => result.ability_pressed = true,
+            Command::SecondaryAbility => result.secondary_ability_pressed = true,
+            Command::CompanionMenu => result.toggle_command_menu = true,
+            Command::NextCompanion => result.next_companion = true,
+            Command::AutoExplore => result.toggle_auto_explore = true,
+            Command::TravelStairsDown => result.travel = result.travel.or(Some(TravelTarget::StairsDown)),
+            Command::TravelStairsUp => result.travel = result.travel.or(Some(TravelTarget::StairsUp)),
+            Command::TravelShop => result.travel = result.travel.or(Some(TravelTarget::Shop)),
+            Command::TravelItems => result.travel = result.travel.or(Some(TravelTarget::ItemPile)),
+            // Number slots pick a command while the menu is open, otherwise a Ranger ability (1-4)
+            Command::Slot(n) => {
+                if input.command_menu.is_some() {
+                    result.command_choice = result.command_choice.or(Some(n - 1));
+                } else if n <= 4 {
+                    result.ranger_ability = result.ranger_ability.or(Some(n - 1));
+                }
+            }
+            Command::MoveUp | Command::MoveDown | Command::MoveLeft | Command::MoveRight => {
+                result.movement = result.movement.or(command.direction());
+            }
+            Command::RunUp | Command::RunDown | Command::RunLeft | Command::RunRight => {
+                result.run = result.run.or(command.direction());
+            }
+            Command::AttackUp | Command::AttackDown | Command::AttackLeft | Command::AttackRight => {
+                result.attack_direction = result.attack_direction.or(command.direction());
+            }
+            Command::InteractUp | Command::InteractDown | Command::InteractLeft | Command::InteractRight => {
+                result.interact_direction = result.interact_direction.or(command.direction());
+            }
+        }
     }
 
-    // Check if shift is held (don't consume - it's a modifier)
-    let shift_held = input.keys_pressed.contains(&KeyCode::ShiftLeft)
-        || input.keys_pressed.contains(&KeyCode::ShiftRight);
-
-    // Check if ctrl is held (don't consume - it's a modifier)
-    let ctrl_held = input.keys_pressed.contains(&KeyCode::ControlLeft)
-        || input.keys_pressed.contains(&KeyCode::ControlRight);
-
-    // Movement or attack direction (only process once per key press)
-    // Shift+direction = attack direction, Ctrl+direction = interact direction, plain direction = movement
-    let direction = if input.keys_pressed.remove(&KeyCode::KeyW)
-        || input.keys_pressed.remove(&KeyCode::ArrowUp)
-    {
-        Some((0, 1))
-    } else if input.keys_pressed.remove(&KeyCode::KeyS)
-        || input.keys_pressed.remove(&KeyCode::ArrowDown)
-    {
-        Some((0, -1))
-    } else if input.keys_pressed.remove(&KeyCode::KeyA)
-        || input.keys_pressed.remove(&KeyCode::ArrowLeft)
-    {
-        Some((-1, 0))
-    } else if input.keys_pressed.remove(&KeyCode::KeyD)
-        || input.keys_pressed.remove(&KeyCode::ArrowRight)
-    {
-        Some((1, 0))
-    } else {
-        None
-    };
+    result
+}
 
-    if let Some(dir) = direction {
-        if shift_held {
-            result.attack_direction = Some(dir);
-        } else if ctrl_held {
-            result.interact_direction = Some(dir);
-        } else {
-            result.movement = Some(dir);
+/// Turn newly pressed keys into commands through the key bindings.
+/// Bound keys are consumed (one command per press); modifiers stay held.
+fn take_commands(input: &mut InputState) -> Vec<Command> {
+    let keys: Vec<KeyCode> = input
+        .keys_pressed
+        .iter()
+        .copied()
+        .filter(|&key| !keybindings::is_modifier(key))
+        .collect();
+
+    let mut commands = Vec::new();
+    for key in keys {
+        let combo = KeyCombo::with_held(key, &input.keys_pressed);
+        if let Some(command) = input.bindings.command_for(combo) {
+            input.keys_pressed.remove(&key);
+            commands.push(command);
         }
     }
-
-    result
+    commands
 }
 
 /// Identify what the player clicked on at a given tile position.
@@ -377,6 +492,11 @@ pub fn handle_click_to_move(
         return;
     }
 
+    // A click takes over from auto-explore, running and resting
+    input.auto_explore = None;
+    input.run = None;
+    input.rest = None;
+
     // Identify what was clicked
     let target = identify_click_target(world, grid, tile_x, tile_y);
 
@@ -425,7 +545,7 @@ pub fn handle_click_to_move(
 
             let blocked = queries::get_blocking_positions(world, Some(player_entity));
 
-            if let Some(path) = pathfinding::find_path(grid, player_pos, (x, y), &blocked) {
+            if let Some(path) = hazards::find_path_for(world, grid, player_entity, player_pos, (x, y), &blocked) {
                 input.player_path = VecDeque::from(path);
                 input.player_path_destination = Some((x, y));
             }
@@ -469,8 +589,9 @@ fn path_to_adjacent(
             continue;
         }
 
-        if let Some(path) = pathfinding::find_path(grid, player_pos, adj, &blocked) {
-            if best_path.as_ref().map(|p| path.len() < p.len()).unwrap_or(true) {
+        if let Some(path) = hazards::find_path_for(world, grid, player_entity, player_pos, adj, &blocked) {
+            let cost = pathfinding::path_cost(player_pos, &path);
+            if best_path.as_ref().map(|p| cost < pathfinding::path_cost(player_pos, p)).unwrap_or(true) {
                 best_path = Some(path);
             }
         }
@@ -620,6 +741,40 @@ pub fn get_shoot_target(
     (tile_x, tile_y)
 }
 
+/// The tile under the mouse cursor
+pub fn mouse_tile(input: &InputState, camera: &Camera) -> (i32, i32) {
+    let world_pos = camera.screen_to_world(input.mouse_pos.0, input.mouse_pos.1);
+    (world_pos.x.floor() as i32, world_pos.y.floor() as i32)
+}
+
+/// Open the command menu at the mouse cursor, noting the tile and any enemy under it.
+/// A companion under the cursor is picked to receive the order.
+fn open_command_menu(input: &InputState, world: &World, camera: &Camera, player_entity: Entity) -> CommandMenu {
+    let world_pos = camera.screen_to_world(input.mouse_pos.0, input.mouse_pos.1);
+    let tile = (world_pos.x.floor() as i32, world_pos.y.floor() as i32);
+    let target = world
+        .query::<(&Position, &Attackable)>()
+        .without::<&Player>()
+        .without::<&TamedBy>()
+        .iter()
+        .find(|(_, (pos, _))| (pos.x, pos.y) == tile)
+        .map(|(entity, _)| entity);
+
+    let mut companions = Vec::new();
+    let mut selected = None;
+    for (entity, (ai, pos, name)) in world.query::<(&CompanionAI, &Position, Option<&Name>)>().iter() {
+        if ai.owner != player_entity {
+            continue;
+        }
+        if (pos.x, pos.y) == tile {
+            selected = Some(companions.len());
+        }
+        companions.push((entity, name.map_or("Companion", |name| name.0)));
+    }
+
+    CommandMenu { screen_pos: input.mouse_pos, tile, target, companions, selected }
+}
+
 /// Result of processing all input for a frame.
 ///
 /// Combines keyboard, mouse, and path-following into a unified result.
@@ -631,6 +786,18 @@ pub struct FrameInput {
     pub toggle_inventory: bool,
     /// Player wants to toggle grid lines
     pub toggle_grid_lines: bool,
+    /// Player wants to toggle the message history window
+    pub toggle_message_history: bool,
+    /// Player wants to open or close the full-screen map
+    pub toggle_map: bool,
+    /// Player wants to open or close the key bindings screen
+    pub toggle_key_bindings: bool,
+    /// Player wants to open or close the options screen
+    pub toggle_options: bool,
+    /// Player wants to open or close the bestiary
+    pub toggle_bestiary: bool,
+    /// Player wants to start or stop sneaking
+    pub toggle_sneak: bool,
     /// Player pressed Enter (take all / loot)
     pub enter_pressed: bool,
     /// Player is dead - no actions allowed
@@ -647,6 +814,16 @@ pub struct FrameInput {
     pub secondary_ability_pressed: bool,
     /// Ranger ability key pressed (0-3 for keys 1-4)
     pub ranger_ability: Option<usize>,
+    /// Auto-explore is standing on a pile with things worth picking up
+    pub explore_loot: Option<Entity>,
+    /// Auto-explore stopped by itself (nothing left, enemy or discovery)
+    pub explore_stopped: Option<exploration::ExploreStop>,
+    /// A travel command couldn't find its target or a way there
+    pub travel_failed: Option<(TravelTarget, TravelFailure)>,
+    /// Player wants to open or close the wait prompt
+    pub toggle_wait_prompt: bool,
+    /// Resting or waiting stopped by itself, or couldn't start
+    pub rest_stopped: Option<(RestKind, RestStop)>,
 }
 
 impl Default for FrameInput {
@@ -655,6 +832,12 @@ impl Default for FrameInput {
             toggle_fullscreen: false,
             toggle_inventory: false,
             toggle_grid_lines: false,
+            toggle_message_history: false,
+            toggle_map: false,
+            toggle_key_bindings: false,
+            toggle_options: false,
+            toggle_bestiary: false,
+            toggle_sneak: false,
             enter_pressed: false,
             player_dead: false,
             player_intent: None,
@@ -663,6 +846,11 @@ impl Default for FrameInput {
             ability_pressed: false,
             secondary_ability_pressed: false,
             ranger_ability: None,
+            explore_loot: None,
+            explore_stopped: None,
+            travel_failed: None,
+            toggle_wait_prompt: false,
+            rest_stopped: None,
         }
     }
 }
@@ -675,20 +863,33 @@ pub fn process_frame(
     input: &mut InputState,
     world: &World,
     grid: &Grid,
+    spatial_cache: &SpatialCache,
     camera: &Camera,
     player_entity: Entity,
+    game_time: f32,
 ) -> FrameInput {
     let mut result = FrameInput::default();
 
     // Process keyboard first to get raw input state
-    let kb = process_keyboard(input);
+    let mut kb = process_keyboard(input);
+    // A tile clicked on the map travels like a travel key
+    if let Some(target) = input.pending_travel.take() {
+        kb.travel = Some(target);
+    }
     result.toggle_fullscreen = kb.toggle_fullscreen;
     result.toggle_inventory = kb.toggle_inventory;
     result.toggle_grid_lines = kb.toggle_grid_lines;
+    result.toggle_message_history = kb.toggle_message_history;
+    result.toggle_map = kb.toggle_map;
+    result.toggle_key_bindings = kb.toggle_key_bindings;
+    result.toggle_options = kb.toggle_options;
+    result.toggle_bestiary = kb.toggle_bestiary;
+    result.toggle_sneak = kb.toggle_sneak;
     result.enter_pressed = kb.enter_pressed;
     result.ability_pressed = kb.ability_pressed;
     result.secondary_ability_pressed = kb.secondary_ability_pressed;
     result.ranger_ability = kb.ranger_ability;
+    result.toggle_wait_prompt = kb.toggle_wait_prompt;
 
     // Check if player is dead
     let is_dead = world
@@ -699,11 +900,86 @@ pub fn process_frame(
     if is_dead {
         result.player_dead = true;
         input.clear_path();
+        input.rest = None;
+        input.examine = None;
         input.pending_left_click = false;
         input.pending_right_click = false;
         return result;
     }
 
+    // Look mode: movement keys and the mouse move the cursor, and the player does nothing
+    if kb.toggle_examine {
+        if input.examine.is_some() {
+            input.examine = None;
+        } else if let Ok(pos) = world.get::<&Position>(player_entity) {
+            input.clear_path();
+            input.rest = None;
+            input.examine = Some(Examine::new((pos.x, pos.y), mouse_tile(input, camera)));
+        }
+    }
+    let mouse = mouse_tile(input, camera);
+    if let Some(examine) = input.examine.as_mut() {
+        examine.follow_mouse(grid, mouse);
+        if let Some(step) = kb.movement {
+            examine.step(grid, step);
+        }
+        if let Some((dx, dy)) = kb.run {
+            examine.step(grid, (dx * EXAMINE_CURSOR_JUMP, dy * EXAMINE_CURSOR_JUMP));
+        }
+        input.pending_left_click = false;
+        input.pending_right_click = false;
+        input.pending_travel = None;
+        return result;
+    }
+
+    // Any command of the player's own ends a rest; pressing R again just stops it
+    let commanded = kb.movement.is_some()
+        || kb.run.is_some()
+        || kb.attack_direction.is_some()
+        || kb.interact_direction.is_some()
+        || kb.wait
+        || kb.travel.is_some()
+        || kb.toggle_auto_explore
+        || kb.ability_pressed
+        || kb.secondary_ability_pressed
+        || kb.ranger_ability.is_some()
+        || input.pending_right_click
+        || input.pending_command.is_some();
+    let was_resting = input.rest.is_some();
+    if commanded || kb.rest {
+        input.rest = None;
+    }
+    if kb.rest && !was_resting {
+        input.clear_path();
+        match Rest::start(world, grid, player_entity, RestKind::UntilRested, game_time) {
+            Ok(rest) => input.rest = Some(rest),
+            Err(reason) => result.rest_stopped = Some((RestKind::UntilRested, reason)),
+        }
+    }
+
+    // Companion command menu
+    if kb.toggle_command_menu {
+        if input.command_menu.is_some() {
+            input.command_menu = None;
+        } else if queries::has_companions(world, player_entity) {
+            input.command_menu = Some(open_command_menu(input, world, camera, player_entity));
+        }
+    }
+    if let (Some(menu), true) = (&mut input.command_menu, kb.next_companion) {
+        menu.next_companion();
+    }
+    if let (Some(menu), Some(choice)) = (&input.command_menu, kb.command_choice) {
+        if let Some(order) = menu.options(grid)[choice].1 {
+            input.pending_command = Some(order);
+        }
+    }
+    if let Some(order) = input.pending_command.take() {
+        let companion = input.command_menu.take().and_then(|menu| menu.companion());
+        result.player_intent = Some(PlayerIntent::CommandCompanions { order, companion });
+        result.from_keyboard = true;
+        return result;
+    }
+
     // Handle pending right-click (ranged shooting)
     if input.pending_right_click {
         input.pending_right_click = false;
@@ -899,7 +1175,7 @@ pub fn process_frame(
         input.pending_left_click = false;
     }
 
-    // Attack direction (Shift+movement) takes priority
+    // Attack direction (Alt+movement) takes priority
     if let Some((dx, dy)) = kb.attack_direction {
         input.clear_path();
         result.player_intent = Some(PlayerIntent::AttackDirection { dx, dy });
@@ -931,6 +1207,91 @@ pub fn process_frame(
         return result;
     }
 
+    // Running: the first step is taken right away, the rest as the player gets to act
+    if let Some((dx, dy)) = kb.run {
+        input.clear_path();
+        input.run = Run::start(world, grid, spatial_cache, player_entity, (dx, dy));
+        result.player_intent = Some(PlayerIntent::Move { dx, dy });
+        result.from_keyboard = true;
+        return result;
+    }
+
+    // Travel commands replace whatever path we were on
+    if let Some(target) = kb.travel {
+        input.clear_path();
+        match travel::plan(world, grid, player_entity, target) {
+            Ok(trip) => {
+                input.player_path = VecDeque::from(trip.path);
+                input.player_path_destination = Some(trip.destination);
+            }
+            Err(failure) => {
+                result.travel_failed = Some((target, failure));
+                return result;
+            }
+        }
+    }
+
+    // Auto-explore: pressing the key again stops it
+    if kb.toggle_auto_explore {
+        let was_exploring = input.auto_explore.is_some();
+        input.clear_path();
+        if !was_exploring {
+            input.auto_explore = Some(AutoExplore::start(world, grid));
+        }
+    }
+
+    // Re-plan the exploration path whenever the player is ready for the next step
+    if input.auto_explore.is_some() && queries::can_entity_act(world, player_entity) {
+        if let Some(pile) = exploration::pile_to_loot(world, player_entity) {
+            result.explore_loot = Some(pile);
+            return result;
+        }
+        let planned = input
+            .auto_explore
+            .as_mut()
+            .map(|explore| explore.next_path(world, grid, player_entity));
+        match planned {
+            Some(Ok(path)) => input.player_path = VecDeque::from(path),
+            Some(Err(reason)) => {
+                input.clear_path();
+                result.explore_stopped = Some(reason);
+                return result;
+            }
+            None => {}
+        }
+    }
+
+    // Keep running until something interesting turns up
+    if let Some(run) = input.run.as_mut() {
+        if queries::can_entity_act(world, player_entity) {
+            match run.next_step(world, grid, spatial_cache, player_entity) {
+                Some((dx, dy)) => {
+                    result.player_intent = Some(PlayerIntent::Move { dx, dy });
+                    result.from_keyboard = true;
+                }
+                None => input.run = None,
+            }
+        }
+        return result;
+    }
+
+    // Resting: wait again whenever the player is ready, until something interrupts
+    if let Some(rest) = input.rest.as_mut() {
+        if queries::can_entity_act(world, player_entity) {
+            match rest.check(world, grid, player_entity, game_time) {
+                Some(reason) => {
+                    result.rest_stopped = Some((rest.kind, reason));
+                    input.rest = None;
+                }
+                None => {
+                    result.player_intent = Some(PlayerIntent::Wait);
+                    result.from_keyboard = true;
+                }
+            }
+        }
+        return result;
+    }
+
     // Click-to-move path following
     update_pursuit(input, world, grid, player_entity);
     if let Some((dx, dy)) = get_path_movement(input, world, player_entity) {
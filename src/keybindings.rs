@@ -0,0 +1,622 @@
+//! Key bindings: which keys (with modifiers) trigger which command.
+//!
+//! The input layer turns key presses into `Command`s through `KeyBindings`
+//! and never matches on `KeyCode`s itself. Bindings start from the defaults
+//! below and are overridden per command from `keybindings.json` in the
+//! config directory, which maps command names to lists of keys such as
+//! `"run_up": ["Shift+W", "Shift+Up"]`. The rebinding screen saves back to
+//! the same file.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use winit::keyboard::KeyCode;
+
+use crate::config;
+
+const BINDINGS_FILE: &str = "keybindings.json";
+
+/// Something the player can do with a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Command {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    RunUp,
+    RunDown,
+    RunLeft,
+    RunRight,
+    AttackUp,
+    AttackDown,
+    AttackLeft,
+    AttackRight,
+    InteractUp,
+    InteractDown,
+    InteractLeft,
+    InteractRight,
+    Wait,
+    Rest,
+    WaitPrompt,
+    ClassAbility,
+    SecondaryAbility,
+    /// Ranger ability or command menu choice 1-6
+    Slot(usize),
+    ToggleSneak,
+    TakeAll,
+    CompanionMenu,
+    /// Pick which companion the command menu orders
+    NextCompanion,
+    AutoExplore,
+    TravelStairsDown,
+    TravelStairsUp,
+    TravelShop,
+    TravelItems,
+    ToggleInventory,
+    ToggleMessageHistory,
+    ToggleMap,
+    Examine,
+    ToggleBestiary,
+    ToggleGridLines,
+    ToggleFullscreen,
+    ToggleKeyBindings,
+    ToggleOptions,
+}
+
+/// Headings for the rebinding screen, with the commands under each
+pub const COMMAND_GROUPS: [(&str, &[Command]); 4] = [
+    (
+        "Movement",
+        &[
+            Command::MoveUp,
+            Command::MoveDown,
+            Command::MoveLeft,
+            Command::MoveRight,
+            Command::RunUp,
+            Command::RunDown,
+            Command::RunLeft,
+            Command::RunRight,
+            Command::AttackUp,
+            Command::AttackDown,
+            Command::AttackLeft,
+            Command::AttackRight,
+            Command::InteractUp,
+            Command::InteractDown,
+            Command::InteractLeft,
+            Command::InteractRight,
+        ],
+    ),
+    (
+        "Actions",
+        &[
+            Command::Wait,
+            Command::Rest,
+            Command::WaitPrompt,
+            Command::ClassAbility,
+            Command::SecondaryAbility,
+            Command::Slot(1),
+            Command::Slot(2),
+            Command::Slot(3),
+            Command::Slot(4),
+            Command::Slot(5),
+            Command::Slot(6),
+            Command::ToggleSneak,
+            Command::TakeAll,
+            Command::CompanionMenu,
+            Command::NextCompanion,
+        ],
+    ),
+    (
+        "Travel",
+        &[
+            Command::AutoExplore,
+            Command::TravelStairsDown,
+            Command::TravelStairsUp,
+            Command::TravelShop,
+            Command::TravelItems,
+        ],
+    ),
+    (
+        "Interface",
+        &[
+            Command::ToggleInventory,
+            Command::ToggleMessageHistory,
+            Command::ToggleMap,
+            Command::Examine,
+            Command::ToggleBestiary,
+            Command::ToggleGridLines,
+            Command::ToggleFullscreen,
+            Command::ToggleKeyBindings,
+            Command::ToggleOptions,
+        ],
+    ),
+];
+
+impl Command {
+    /// Every command, in the order they're looked up and listed
+    pub fn all() -> impl Iterator<Item = Command> {
+        COMMAND_GROUPS.into_iter().flat_map(|(_, commands)| commands.iter().copied())
+    }
+
+    /// Name used in the bindings file
+    pub fn id(self) -> String {
+        let id = match self {
+            Command::MoveUp => "move_up",
+            Command::MoveDown => "move_down",
+            Command::MoveLeft => "move_left",
+            Command::MoveRight => "move_right",
+            Command::RunUp => "run_up",
+            Command::RunDown => "run_down",
+            Command::RunLeft => "run_left",
+            Command::RunRight => "run_right",
+            Command::AttackUp => "attack_up",
+            Command::AttackDown => "attack_down",
+            Command::AttackLeft => "attack_left",
+            Command::AttackRight => "attack_right",
+            Command::InteractUp => "interact_up",
+            Command::InteractDown => "interact_down",
+            Command::InteractLeft => "interact_left",
+            Command::InteractRight => "interact_right",
+            Command::Wait => "wait",
+            Command::Rest => "rest",
+            Command::WaitPrompt => "wait_prompt",
+            Command::ClassAbility => "class_ability",
+            Command::SecondaryAbility => "secondary_ability",
+            Command::Slot(n) => return format!("slot_{}", n),
+            Command::ToggleSneak => "toggle_sneak",
+            Command::TakeAll => "take_all",
+            Command::CompanionMenu => "companion_menu",
+            Command::NextCompanion => "next_companion",
+            Command::AutoExplore => "auto_explore",
+            Command::TravelStairsDown => "travel_stairs_down",
+            Command::TravelStairsUp => "travel_stairs_up",
+            Command::TravelShop => "travel_shop",
+            Command::TravelItems => "travel_items",
+            Command::ToggleInventory => "toggle_inventory",
+            Command::ToggleMessageHistory => "toggle_message_history",
+            Command::ToggleMap => "toggle_map",
+            Command::Examine => "examine",
+            Command::ToggleBestiary => "toggle_bestiary",
+            Command::ToggleGridLines => "toggle_grid_lines",
+            Command::ToggleFullscreen => "toggle_fullscreen",
+            Command::ToggleKeyBindings => "toggle_key_bindings",
+            Command::ToggleOptions => "toggle_options",
+        };
+        id.to_string()
+    }
+
+    /// Description for the rebinding screen
+    pub fn label(self) -> String {
+        let label = match self {
+            Command::MoveUp => "Move up",
+            Command::MoveDown => "Move down",
+            Command::MoveLeft => "Move left",
+            Command::MoveRight => "Move right",
+            Command::RunUp => "Run up",
+            Command::RunDown => "Run down",
+            Command::RunLeft => "Run left",
+            Command::RunRight => "Run right",
+            Command::AttackUp => "Attack up",
+            Command::AttackDown => "Attack down",
+            Command::AttackLeft => "Attack left",
+            Command::AttackRight => "Attack right",
+            Command::InteractUp => "Interact up",
+            Command::InteractDown => "Interact down",
+            Command::InteractLeft => "Interact left",
+            Command::InteractRight => "Interact right",
+            Command::Wait => "Wait a turn",
+            Command::Rest => "Rest until healed",
+            Command::WaitPrompt => "Wait for a while",
+            Command::ClassAbility => "Class ability",
+            Command::SecondaryAbility => "Secondary ability",
+            Command::Slot(n) => return format!("Ability / menu choice {}", n),
+            Command::ToggleSneak => "Sneak",
+            Command::TakeAll => "Take all",
+            Command::CompanionMenu => "Companion commands",
+            Command::NextCompanion => "Pick companion to command",
+            Command::AutoExplore => "Auto-explore",
+            Command::TravelStairsDown => "Travel to stairs down",
+            Command::TravelStairsUp => "Travel to stairs up",
+            Command::TravelShop => "Travel to the shop",
+            Command::TravelItems => "Travel to items",
+            Command::ToggleInventory => "Inventory",
+            Command::ToggleMessageHistory => "Message history",
+            Command::ToggleMap => "Map",
+            Command::Examine => "Look around",
+            Command::ToggleBestiary => "Bestiary",
+            Command::ToggleGridLines => "Grid lines",
+            Command::ToggleFullscreen => "Fullscreen",
+            Command::ToggleKeyBindings => "Key bindings",
+            Command::ToggleOptions => "Options",
+        };
+        label.to_string()
+    }
+
+    /// The direction of a move, run, attack or interact command
+    pub fn direction(self) -> Option<(i32, i32)> {
+        match self {
+            Command::MoveUp | Command::RunUp | Command::AttackUp | Command::InteractUp => Some((0, 1)),
+            Command::MoveDown | Command::RunDown | Command::AttackDown | Command::InteractDown => Some((0, -1)),
+            Command::MoveLeft | Command::RunLeft | Command::AttackLeft | Command::InteractLeft => Some((-1, 0)),
+            Command::MoveRight | Command::RunRight | Command::AttackRight | Command::InteractRight => Some((1, 0)),
+            _ => None,
+        }
+    }
+
+    fn default_keys(self) -> Vec<KeyCombo> {
+        let plain = KeyCombo::plain;
+        let shift = |key| KeyCombo { shift: true, ..KeyCombo::plain(key) };
+        let ctrl = |key| KeyCombo { ctrl: true, ..KeyCombo::plain(key) };
+        let alt = |key| KeyCombo { alt: true, ..KeyCombo::plain(key) };
+        let directional = |with: &dyn Fn(KeyCode) -> KeyCombo| {
+            let (letter, arrow) = match self.direction() {
+                Some((0, 1)) => (KeyCode::KeyW, KeyCode::ArrowUp),
+                Some((0, -1)) => (KeyCode::KeyS, KeyCode::ArrowDown),
+                Some((-1, 0)) => (KeyCode::KeyA, KeyCode::ArrowLeft),
+                _ => (KeyCode::KeyD, KeyCode::ArrowRight),
+            };
+            vec![with(letter), with(arrow)]
+        };
+
+        match self {
+            Command::MoveUp | Command::MoveDown | Command::MoveLeft | Command::MoveRight => directional(&plain),
+            Command::RunUp | Command::RunDown | Command::RunLeft | Command::RunRight => directional(&shift),
+            Command::AttackUp | Command::AttackDown | Command::AttackLeft | Command::AttackRight => directional(&alt),
+            Command::InteractUp | Command::InteractDown | Command::InteractLeft | Command::InteractRight => {
+                directional(&ctrl)
+            }
+            Command::Wait => vec![plain(KeyCode::Period)],
+            Command::Rest => vec![plain(KeyCode::KeyR)],
+            Command::WaitPrompt => vec![plain(KeyCode::KeyZ)],
+            Command::ClassAbility => vec![plain(KeyCode::KeyQ)],
+            Command::SecondaryAbility => vec![plain(KeyCode::KeyE)],
+            Command::Slot(n) => {
+                const DIGITS: [KeyCode; 6] = [
+                    KeyCode::Digit1,
+                    KeyCode::Digit2,
+                    KeyCode::Digit3,
+                    KeyCode::Digit4,
+                    KeyCode::Digit5,
+                    KeyCode::Digit6,
+                ];
+                DIGITS.get(n.wrapping_sub(1)).map(|&key| plain(key)).into_iter().collect()
+            }
+            Command::ToggleSneak => vec![plain(KeyCode::KeyC)],
+            Command::TakeAll => vec![plain(KeyCode::Enter)],
+            Command::CompanionMenu => vec![plain(KeyCode::KeyV)],
+            Command::NextCompanion => vec![plain(KeyCode::Tab)],
+            Command::AutoExplore => vec![plain(KeyCode::KeyX)],
+            Command::TravelStairsDown => vec![shift(KeyCode::Period)],
+            Command::TravelStairsUp => vec![shift(KeyCode::Comma)],
+            Command::TravelShop => vec![plain(KeyCode::KeyG)],
+            Command::TravelItems => vec![plain(KeyCode::KeyP)],
+            Command::ToggleInventory => vec![plain(KeyCode::KeyI)],
+            Command::ToggleMessageHistory => vec![plain(KeyCode::KeyL)],
+            Command::ToggleMap => vec![plain(KeyCode::KeyM)],
+            Command::Examine => vec![plain(KeyCode::Semicolon)],
+            Command::ToggleBestiary => vec![plain(KeyCode::KeyB)],
+            Command::ToggleGridLines => vec![plain(KeyCode::BracketRight)],
+            Command::ToggleFullscreen => vec![plain(KeyCode::F11)],
+            Command::ToggleKeyBindings => vec![plain(KeyCode::F1)],
+            Command::ToggleOptions => vec![plain(KeyCode::F10)],
+        }
+    }
+}
+
+/// A key plus the modifiers that must be held with it (exactly)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyCombo {
+    pub key: KeyCode,
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl KeyCombo {
+    pub fn plain(key: KeyCode) -> Self {
+        Self {
+            key,
+            shift: false,
+            ctrl: false,
+            alt: false,
+        }
+    }
+
+    /// `key` with whichever modifiers are down in `held`
+    pub fn with_held(key: KeyCode, held: &HashSet<KeyCode>) -> Self {
+        let down = |left, right| held.contains(&left) || held.contains(&right);
+        Self {
+            key,
+            shift: down(KeyCode::ShiftLeft, KeyCode::ShiftRight),
+            ctrl: down(KeyCode::ControlLeft, KeyCode::ControlRight),
+            alt: down(KeyCode::AltLeft, KeyCode::AltRight),
+        }
+    }
+
+    /// Parse `"Ctrl+Shift+W"` style names (case doesn't matter)
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let key_name = parts.pop()?;
+        let key = KEY_NAMES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(key_name))
+            .map(|&(key, _)| key)?;
+
+        let mut combo = Self::plain(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "shift" => combo.shift = true,
+                "ctrl" | "control" => combo.ctrl = true,
+                "alt" => combo.alt = true,
+                _ => return None,
+            }
+        }
+        Some(combo)
+    }
+
+    /// Display name, e.g. `"Ctrl+Shift+W"`
+    pub fn name(&self) -> String {
+        let key = KEY_NAMES
+            .iter()
+            .find(|&&(key, _)| key == self.key)
+            .map(|&(_, name)| name.to_string())
+            .unwrap_or_else(|| format!("{:?}", self.key));
+        let mut name = String::new();
+        for (held, modifier) in [(self.ctrl, "Ctrl+"), (self.alt, "Alt+"), (self.shift, "Shift+")] {
+            if held {
+                name.push_str(modifier);
+            }
+        }
+        name + &key
+    }
+}
+
+/// Whether `key` can be bound (it has a name in the bindings file)
+pub fn is_bindable(key: KeyCode) -> bool {
+    KEY_NAMES.iter().any(|&(named, _)| named == key)
+}
+
+/// Whether `key` is a modifier (never bound on its own)
+pub fn is_modifier(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::ShiftLeft
+            | KeyCode::ShiftRight
+            | KeyCode::ControlLeft
+            | KeyCode::ControlRight
+            | KeyCode::AltLeft
+            | KeyCode::AltRight
+            | KeyCode::SuperLeft
+            | KeyCode::SuperRight
+    )
+}
+
+/// Command → keys mapping
+#[derive(Debug, Clone)]
+pub struct KeyBindings {
+    keys: HashMap<Command, Vec<KeyCombo>>,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            keys: Command::all().map(|command| (command, command.default_keys())).collect(),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Defaults overridden by the bindings file, if there is one.
+    /// Problems with the file are reported and the defaults used instead.
+    pub fn load() -> Self {
+        let mut bindings = Self::default();
+        match config::read(BINDINGS_FILE) {
+            Ok(Some(json)) => {
+                for problem in bindings.apply_overrides(&json) {
+                    eprintln!("Warning: {}: {}", BINDINGS_FILE, problem);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: {}", e),
+        }
+        bindings
+    }
+
+    /// Save every command's keys to the bindings file
+    pub fn save(&self) -> Result<(), String> {
+        let map: BTreeMap<String, Vec<String>> = Command::all()
+            .map(|command| (command.id(), self.keys(command).iter().map(KeyCombo::name).collect()))
+            .collect();
+        let json = serde_json::to_string_pretty(&map).map_err(|e| e.to_string())?;
+        config::write(BINDINGS_FILE, &json)
+    }
+
+    /// Replace the keys of each command listed in `json`; returns what couldn't be understood
+    pub fn apply_overrides(&mut self, json: &str) -> Vec<String> {
+        let map: BTreeMap<String, Vec<String>> = match serde_json::from_str(json) {
+            Ok(map) => map,
+            Err(e) => return vec![e.to_string()],
+        };
+
+        let mut problems = Vec::new();
+        for (id, names) in map {
+            let Some(command) = Command::all().find(|command| command.id() == id) else {
+                problems.push(format!("unknown command \"{}\"", id));
+                continue;
+            };
+            let mut combos = Vec::new();
+            for name in names {
+                match KeyCombo::parse(&name) {
+                    Some(combo) => combos.push(combo),
+                    None => problems.push(format!("unknown key \"{}\" for {}", name, id)),
+                }
+            }
+            self.keys.insert(command, combos);
+        }
+        problems
+    }
+
+    /// Keys bound to `command`
+    pub fn keys(&self, command: Command) -> &[KeyCombo] {
+        self.keys.get(&command).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// The command a key combination triggers, if any
+    pub fn command_for(&self, combo: KeyCombo) -> Option<Command> {
+        Command::all().find(|&command| self.keys(command).contains(&combo))
+    }
+
+    /// Add a key to `command`, taking it away from any other command
+    pub fn bind(&mut self, command: Command, combo: KeyCombo) {
+        for combos in self.keys.values_mut() {
+            combos.retain(|&bound| bound != combo);
+        }
+        self.keys.entry(command).or_default().push(combo);
+    }
+
+    /// Remove a key from `command`
+    pub fn unbind(&mut self, command: Command, combo: KeyCombo) {
+        if let Some(combos) = self.keys.get_mut(&command) {
+            combos.retain(|&bound| bound != combo);
+        }
+    }
+}
+
+/// Keys that can be bound, with their names in the bindings file.
+/// Escape and the backquote (dev menu) are reserved.
+const KEY_NAMES: &[(KeyCode, &str)] = &[
+    (KeyCode::KeyA, "A"),
+    (KeyCode::KeyB, "B"),
+    (KeyCode::KeyC, "C"),
+    (KeyCode::KeyD, "D"),
+    (KeyCode::KeyE, "E"),
+    (KeyCode::KeyF, "F"),
+    (KeyCode::KeyG, "G"),
+    (KeyCode::KeyH, "H"),
+    (KeyCode::KeyI, "I"),
+    (KeyCode::KeyJ, "J"),
+    (KeyCode::KeyK, "K"),
+    (KeyCode::KeyL, "L"),
+    (KeyCode::KeyM, "M"),
+    (KeyCode::KeyN, "N"),
+    (KeyCode::KeyO, "O"),
+    (KeyCode::KeyP, "P"),
+    (KeyCode::KeyQ, "Q"),
+    (KeyCode::KeyR, "R"),
+    (KeyCode::KeyS, "S"),
+    (KeyCode::KeyT, "T"),
+    (KeyCode::KeyU, "U"),
+    (KeyCode::KeyV, "V"),
+    (KeyCode::KeyW, "W"),
+    (KeyCode::KeyX, "X"),
+    (KeyCode::KeyY, "Y"),
+    (KeyCode::KeyZ, "Z"),
+    (KeyCode::Digit0, "0"),
+    (KeyCode::Digit1, "1"),
+    (KeyCode::Digit2, "2"),
+    (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"),
+    (KeyCode::Digit5, "5"),
+    (KeyCode::Digit6, "6"),
+    (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"),
+    (KeyCode::Digit9, "9"),
+    (KeyCode::Numpad0, "Num0"),
+    (KeyCode::Numpad1, "Num1"),
+    (KeyCode::Numpad2, "Num2"),
+    (KeyCode::Numpad3, "Num3"),
+    (KeyCode::Numpad4, "Num4"),
+    (KeyCode::Numpad5, "Num5"),
+    (KeyCode::Numpad6, "Num6"),
+    (KeyCode::Numpad7, "Num7"),
+    (KeyCode::Numpad8, "Num8"),
+    (KeyCode::Numpad9, "Num9"),
+    (KeyCode::F1, "F1"),
+    (KeyCode::F2, "F2"),
+    (KeyCode::F3, "F3"),
+    (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"),
+    (KeyCode::F6, "F6"),
+    (KeyCode::F7, "F7"),
+    (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"),
+    (KeyCode::F10, "F10"),
+    (KeyCode::F11, "F11"),
+    (KeyCode::F12, "F12"),
+    (KeyCode::ArrowUp, "Up"),
+    (KeyCode::ArrowDown, "Down"),
+    (KeyCode::ArrowLeft, "Left"),
+    (KeyCode::ArrowRight, "Right"),
+    (KeyCode::Enter, "Enter"),
+    (KeyCode::Space, "Space"),
+    (KeyCode::Tab, "Tab"),
+    (KeyCode::Backspace, "Backspace"),
+    (KeyCode::Insert, "Insert"),
+    (KeyCode::Delete, "Delete"),
+    (KeyCode::Home, "Home"),
+    (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"),
+    (KeyCode::PageDown, "PageDown"),
+    (KeyCode::Period, "."),
+    (KeyCode::Comma, ","),
+    (KeyCode::Slash, "/"),
+    (KeyCode::Semicolon, ";"),
+    (KeyCode::Quote, "'"),
+    (KeyCode::BracketLeft, "["),
+    (KeyCode::BracketRight, "]"),
+    (KeyCode::Backslash, "\\"),
+    (KeyCode::Minus, "-"),
+    (KeyCode::Equal, "="),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_names_round_trip() {
+        for name in ["W", "Shift+.", "Ctrl+Alt+Up", "F11", "]"] {
+            assert_eq!(KeyCombo::parse(name).unwrap().name(), name);
+        }
+        assert_eq!(KeyCombo::parse("shift+w"), KeyCombo::parse("Shift+W"));
+        assert_eq!(KeyCombo::parse("Hyper+W"), None);
+        assert_eq!(KeyCombo::parse("Escape"), None);
+    }
+
+    #[test]
+    fn test_defaults_have_no_clashes() {
+        let bindings = KeyBindings::default();
+        let mut seen = HashSet::new();
+        for command in Command::all() {
+            assert!(!bindings.keys(command).is_empty(), "{:?} has no default key", command);
+            for &combo in bindings.keys(command) {
+                assert!(seen.insert(combo), "{} is bound twice", combo.name());
+            }
+        }
+        let shift_w = KeyCombo::parse("Shift+W").unwrap();
+        assert_eq!(bindings.command_for(shift_w), Some(Command::RunUp));
+        assert_eq!(bindings.command_for(KeyCombo::parse("W").unwrap()), Some(Command::MoveUp));
+    }
+
+    #[test]
+    fn test_overrides_replace_only_listed_commands() {
+        let mut bindings = KeyBindings::default();
+        let problems = bindings.apply_overrides(r#"{"wait": ["Space", "Num5"], "jump": ["J"], "rest": ["Bogus"]}"#);
+
+        assert_eq!(problems.len(), 2);
+        assert_eq!(bindings.command_for(KeyCombo::plain(KeyCode::Space)), Some(Command::Wait));
+        assert_eq!(bindings.command_for(KeyCombo::plain(KeyCode::Period)), None);
+        assert!(bindings.keys(Command::Rest).is_empty());
+        assert_eq!(bindings.keys(Command::MoveUp), Command::MoveUp.default_keys().as_slice());
+    }
+
+    #[test]
+    fn test_binding_a_key_takes_it_from_other_commands() {
+        let mut bindings = KeyBindings::default();
+        let q = KeyCombo::plain(KeyCode::KeyQ);
+        bindings.bind(Command::Wait, q);
+
+        assert_eq!(bindings.command_for(q), Some(Command::Wait));
+        assert!(bindings.keys(Command::ClassAbility).is_empty());
+        assert_eq!(bindings.keys(Command::Wait).len(), 2);
+    }
+}
@@ -1,9 +1,12 @@
 mod active_ai_tracker;
 mod app;
 mod audio;
+mod bestiary;
 mod camera;
 mod components;
+mod config;
 mod constants;
+mod dijkstra_map;
 mod dungeon_gen;
 mod engine;
 mod events;
@@ -11,11 +14,14 @@ mod fov;
 mod game;
 mod grid;
 mod input;
+mod keybindings;
+mod message_log;
 mod multi_tileset;
 mod pathfinding;
 mod queries;
 mod render;
 mod renderer;
+mod settings;
 mod spatial_cache;
 mod spawning;
 mod systems;
@@ -112,7 +118,7 @@ impl ApplicationHandler for App {
         // Create game engine (starts in StartScreen mode)
         let engine = GameEngine::new();
 
-        self.state = Some(AppState {
+        let mut state = AppState {
             window,
             gl_surface,
             gl_context,
@@ -121,7 +127,9 @@ impl ApplicationHandler for App {
             render_ctx,
             engine,
             last_frame_time: Instant::now(),
-        });
+        };
+        state.apply_settings();
+        self.state = Some(state);
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
@@ -143,7 +151,10 @@ impl ApplicationHandler for App {
                 state.render_ctx.camera.viewport_height = size.height as f32;
             }
             WindowEvent::RedrawRequested => {
-                state.update_and_render();
+                if state.update_and_render() == Some(WindowAction::Exit) {
+                    event_loop.exit();
+                    return;
+                }
                 state.window.request_redraw();
             }
             _ => {
@@ -155,7 +166,7 @@ impl ApplicationHandler for App {
                 ) {
                     match action {
                         WindowAction::Exit => event_loop.exit(),
-                        WindowAction::ToggleFullscreen => state.toggle_fullscreen(),
+                        WindowAction::ApplySettings => state.apply_settings(),
                     }
                 }
             }
@@ -170,7 +181,8 @@ impl ApplicationHandler for App {
 }
 
 impl AppState {
-    fn update_and_render(&mut self) {
+    /// Returns `WindowAction::Exit` when the player quit from the UI
+    fn update_and_render(&mut self) -> Option<WindowAction> {
         puffin::GlobalProfiler::lock().new_frame();
         puffin::profile_function!();
 
@@ -189,7 +201,7 @@ impl AppState {
         // Handle window actions from tick
         if let Some(action) = tick_result.window_action {
             match action {
-                WindowAction::ToggleFullscreen => self.toggle_fullscreen(),
+                WindowAction::ApplySettings => self.apply_settings(),
                 WindowAction::Exit => {
                     // Can't exit from here, but this shouldn't happen from tick
                 }
@@ -209,7 +221,10 @@ impl AppState {
         };
 
         // Process UI actions
-        self.engine.process_ui_actions(&ui_actions);
+        let ui_window_action = self.engine.process_ui_actions(&ui_actions);
+        if ui_actions.settings.is_some() {
+            self.apply_settings();
+        }
 
         // Handle start game action (from class selection screen)
         if let Some(class) = ui_actions.start_game {
@@ -241,14 +256,16 @@ impl AppState {
 
         // Swap buffers
         self.gl_surface.swap_buffers(&self.gl_context).unwrap();
+        ui_window_action
     }
 
-    fn toggle_fullscreen(&mut self) {
-        let fullscreen = if self.window.fullscreen().is_some() {
-            None
-        } else {
-            Some(Fullscreen::Borderless(None))
-        };
-        self.window.set_fullscreen(fullscreen);
+    /// Apply the engine's settings to the window, UI and camera
+    fn apply_settings(&mut self) {
+        let settings = &self.engine.settings;
+        self.render_ctx.camera.set_zoom_limits(settings.min_zoom, settings.max_zoom);
+        self.egui_glow.egui_ctx.set_zoom_factor(settings.ui_scale);
+        if settings.fullscreen != self.window.fullscreen().is_some() {
+            self.window.set_fullscreen(settings.fullscreen.then_some(Fullscreen::Borderless(None)));
+        }
     }
 }
@@ -0,0 +1,602 @@
+//! Message log - turns the game event stream into readable sentences.
+//!
+//! Like the VFX manager, the log only reacts to events and never changes game
+//! state. Names are looked up while the event is processed, so corpses and
+//! spent arrows can still be described.
+
+use std::collections::VecDeque;
+
+use hecs::{Entity, World};
+
+use crate::components::{AIState, ChaseAI, CompanionOrder, DamageType, Dialogue, EffectType, Name, Position, Projectile, TamedBy};
+use crate::constants::MESSAGE_LOG_CAPACITY;
+use crate::events::{GameEvent, StairDirection};
+use crate::grid::Grid;
+use crate::systems::exploration::{ExploreStop, Sight};
+use crate::systems::items::item_name;
+use crate::systems::rest::{RestKind, RestStop};
+use crate::systems::travel::{TravelFailure, TravelTarget};
+
+/// What a message is about - decides its colour in the log
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageCategory {
+    /// The player's side dealt damage or killed something
+    Attack,
+    /// A critical hit
+    Critical,
+    /// The player's side took damage or died
+    Hurt,
+    /// An attack that failed its hit roll
+    Miss,
+    /// Status effects being gained or wearing off
+    Status,
+    /// Traps, ambushes, enemies noticing the player
+    Danger,
+    /// Pickups, trades and equipment changes
+    Item,
+    /// Level-ups and tamed companions
+    Progress,
+    /// Everything else (stairs, fights the player isn't part of)
+    General,
+}
+
+/// A single line in the log
+#[derive(Debug, Clone)]
+pub struct LogMessage {
+    pub text: String,
+    pub category: MessageCategory,
+    /// How many times this message arrived back-to-back
+    pub count: u32,
+}
+
+impl LogMessage {
+    /// Message text with the repeat counter appended, e.g. "The Rat misses you. (x3)"
+    pub fn display_text(&self) -> String {
+        if self.count > 1 {
+            format!("{} (x{})", self.text, self.count)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// Bounded history of log messages, oldest first
+pub struct MessageLog {
+    messages: VecDeque<LogMessage>,
+    capacity: usize,
+}
+
+impl Default for MessageLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MessageLog {
+    pub fn new() -> Self {
+        Self::with_capacity(MESSAGE_LOG_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Add a message, merging it into the previous line if it is identical
+    pub fn push(&mut self, text: impl Into<String>, category: MessageCategory) {
+        let text = text.into();
+        if let Some(last) = self.messages.back_mut() {
+            if last.text == text && last.category == category {
+                last.count += 1;
+                return;
+            }
+        }
+        if self.messages.len() >= self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(LogMessage { text, category, count: 1 });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// All stored messages, oldest first
+    pub fn messages(&self) -> impl DoubleEndedIterator<Item = &LogMessage> + ExactSizeIterator {
+        self.messages.iter()
+    }
+
+    /// The newest `count` messages, oldest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &LogMessage> {
+        self.messages.iter().skip(self.messages.len().saturating_sub(count))
+    }
+
+    /// Describe a game event, if it is worth telling the player about
+    pub fn handle_event(&mut self, event: &GameEvent, world: &World, grid: &Grid, player: Entity) {
+        let names = Names { world, grid, player };
+
+        match event {
+            GameEvent::AttackHit { attacker, target, damage, damage_type, crit, .. } => {
+                if !names.noticed(*attacker) && !names.noticed(*target) {
+                    return;
+                }
+                let (you_verb, verb) = damage_verb(*damage_type);
+                let attacker_name = names.subject(*attacker);
+                let verb = if *attacker == player { you_verb } else { verb };
+                let target_name = names.object(*target);
+                let amount = damage_amount(*damage);
+                if *crit {
+                    self.push(format!("{} critically {} {} {}!", attacker_name, verb, target_name, amount), MessageCategory::Critical);
+                } else {
+                    self.push(format!("{} {} {} {}.", attacker_name, verb, target_name, amount), names.damage_category(*target));
+                }
+            }
+            GameEvent::SneakAttack { attacker, target } => {
+                if !names.noticed(*attacker) && !names.noticed(*target) {
+                    return;
+                }
+                let verb = if *attacker == player { "catch" } else { "catches" };
+                self.push(
+                    format!("Sneak attack! {} {} {} off guard.", names.subject(*attacker), verb, names.object(*target)),
+                    MessageCategory::Critical,
+                );
+            }
+            GameEvent::AttackMissed { attacker, target, ranged, .. } => {
+                if !names.noticed(*attacker) && !names.noticed(*target) {
+                    return;
+                }
+                let text = if *ranged {
+                    format!("{} arrow misses {}.", names.possessive(*attacker), names.object(*target))
+                } else {
+                    let verb = if *attacker == player { "miss" } else { "misses" };
+                    format!("{} {} {}.", names.subject(*attacker), verb, names.object(*target))
+                };
+                self.push(text, MessageCategory::Miss);
+            }
+            GameEvent::ProjectileHit { projectile, target: Some(target), damage, .. } => {
+                let Ok(projectile) = world.get::<&Projectile>(*projectile) else { return };
+                // Thrown potions are described by their splash, not as a hit
+                if projectile.potion_type.is_some() {
+                    return;
+                }
+                if !names.noticed(projectile.source) && !names.noticed(*target) {
+                    return;
+                }
+                self.push(
+                    format!("{} arrow hits {} {}.", names.possessive(projectile.source), names.object(*target), damage_amount(*damage)),
+                    names.damage_category(*target),
+                );
+            }
+            GameEvent::LifeDrainTick { caster, target, damage, .. } => {
+                if !names.noticed(*caster) && !names.noticed(*target) {
+                    return;
+                }
+                let verb = if *caster == player { "drain" } else { "drains" };
+                self.push(
+                    format!("{} {} {} {}.", names.subject(*caster), verb, names.object(*target), damage_amount(*damage)),
+                    names.damage_category(*target),
+                );
+            }
+            GameEvent::BurnDamage { entity, damage, .. } if *entity == player => {
+                self.push(format!("You burn {}.", damage_amount(*damage)), MessageCategory::Hurt);
+            }
+            GameEvent::EntityDied { entity, .. } => {
+                if *entity == player {
+                    self.push("You die...", MessageCategory::Hurt);
+                } else if names.noticed(*entity) {
+                    self.push(format!("{} dies.", names.subject(*entity)), names.damage_category(*entity));
+                }
+            }
+            GameEvent::StatusEffectExpired { entity, effect_type } => {
+                if !names.noticed(*entity) {
+                    return;
+                }
+                let verb = if *entity == player { "are" } else { "is" };
+                self.push(
+                    format!("{} {} no longer {}.", names.subject(*entity), verb, effect_adjective(*effect_type)),
+                    MessageCategory::Status,
+                );
+            }
+            GameEvent::CaughtFire { entity, .. } => {
+                if !names.noticed(*entity) {
+                    return;
+                }
+                let verb = if *entity == player { "catch" } else { "catches" };
+                self.push(format!("{} {} fire!", names.subject(*entity), verb), names.danger_category(*entity));
+            }
+            GameEvent::FireTrapTriggered { victim, .. } => {
+                if !names.noticed(*victim) {
+                    return;
+                }
+                let verb = if *victim == player { "trigger" } else { "triggers" };
+                self.push(format!("{} {} a fire trap!", names.subject(*victim), verb), names.danger_category(*victim));
+            }
+            GameEvent::SnareTrapTriggered { victim, .. } => {
+                if !names.noticed(*victim) {
+                    return;
+                }
+                let verb = if *victim == player { "are" } else { "is" };
+                self.push(format!("{} {} caught in a snare!", names.subject(*victim), verb), names.danger_category(*victim));
+            }
+            GameEvent::CoffinSkeletonSpawn { .. } => {
+                self.push("A skeleton rises from the coffin!", MessageCategory::Danger);
+            }
+            GameEvent::AIStateChanged { entity, new_state: AIState::Chasing } if names.is_visible(*entity) => {
+                // Monsters can be going for each other rather than for the player's side
+                let target = world.get::<&ChaseAI>(*entity).ok().and_then(|ai| ai.current_target);
+                match target {
+                    Some(target) if target != player && !names.is_companion(target) => {
+                        self.push(
+                            format!("{} turns on {}.", names.subject(*entity), names.object(target)),
+                            MessageCategory::General,
+                        );
+                    }
+                    _ => self.push(format!("{} notices you!", names.subject(*entity)), MessageCategory::Danger),
+                }
+            }
+            GameEvent::LifeDrainInterrupted { caster, .. } if *caster == player => {
+                self.push("Your life drain is interrupted!", MessageCategory::Danger);
+            }
+            GameEvent::HealCast { caster, target, amount, .. } => {
+                if *amount <= 0 || (!names.noticed(*caster) && !names.noticed(*target)) {
+                    return;
+                }
+                let target_name = if caster == target { "itself".to_string() } else { names.object(*target) };
+                self.push(format!("{} heals {}.", names.subject(*caster), target_name), MessageCategory::Danger);
+            }
+            GameEvent::BuffCast { caster, target, effect_type } => {
+                if !names.noticed(*caster) && !names.noticed(*target) {
+                    return;
+                }
+                let target_name = if caster == target { "itself".to_string() } else { names.object(*target) };
+                self.push(
+                    format!("{} makes {} {}.", names.subject(*caster), target_name, effect_adjective(*effect_type)),
+                    MessageCategory::Danger,
+                );
+            }
+            GameEvent::SneakToggled { entity, sneaking } if *entity == player => {
+                let text = if *sneaking { "You start sneaking." } else { "You stop sneaking." };
+                self.push(text, MessageCategory::General);
+            }
+            GameEvent::ExploreStopped { reason } => {
+                let (text, category) = match reason {
+                    ExploreStop::Done => ("Nothing left to explore that you can reach.".to_string(), MessageCategory::General),
+                    ExploreStop::EnemyInView(enemy) => (format!("You spot {}.", names.object(*enemy)), MessageCategory::Danger),
+                    ExploreStop::Spotted(sight) => {
+                        let what = match sight {
+                            Sight::StairsDown => "stairs leading down",
+                            Sight::StairsUp => "stairs leading up",
+                            Sight::Chest => "a chest",
+                            Sight::Shop => "a shop",
+                        };
+                        (format!("You spot {}.", what), MessageCategory::General)
+                    }
+                };
+                self.push(text, category);
+            }
+            GameEvent::TravelFailed { target, failure } => {
+                let text = match (failure, target) {
+                    (TravelFailure::Unexplored, TravelTarget::ItemPile) => "You don't know of any items lying around.".to_string(),
+                    (TravelFailure::Unreachable, TravelTarget::ItemPile) => "You don't know a way to any items.".to_string(),
+                    (TravelFailure::Unexplored, TravelTarget::Tile(..)) => "You haven't explored there yet.".to_string(),
+                    (TravelFailure::Unreachable, TravelTarget::Tile(..)) => "You don't know a way there.".to_string(),
+                    (TravelFailure::Unexplored, _) => format!("You haven't found {} yet.", travel_target_name(*target)),
+                    (TravelFailure::Unreachable, _) => format!("You don't know a way to {}.", travel_target_name(*target)),
+                };
+                self.push(text, MessageCategory::General);
+            }
+            GameEvent::RestStopped { kind, reason } => {
+                let doing = match kind {
+                    RestKind::UntilRested => "resting",
+                    RestKind::For(_) => "waiting",
+                };
+                let (text, category) = match (reason, kind) {
+                    (RestStop::Rested, _) => ("You feel fully rested.".to_string(), MessageCategory::General),
+                    (RestStop::TimeUp, RestKind::UntilRested) => ("You can't rest any longer.".to_string(), MessageCategory::General),
+                    (RestStop::TimeUp, RestKind::For(_)) => ("You finish waiting.".to_string(), MessageCategory::General),
+                    (RestStop::AlreadyRested, _) => ("You don't need to rest.".to_string(), MessageCategory::General),
+                    (RestStop::EnemyInView(enemy), _) => (format!("You spot {}.", names.object(*enemy)), MessageCategory::Danger),
+                    (RestStop::TookDamage, _) => (format!("You are hurt and stop {}.", doing), MessageCategory::Danger),
+                    (RestStop::EffectEnded(effect_type), _) => (
+                        format!("You stop {} now that you are no longer {}.", doing, effect_adjective(*effect_type)),
+                        MessageCategory::Status,
+                    ),
+                };
+                self.push(text, category);
+            }
+            GameEvent::CompanionsOrdered { owner, companion, order } if *owner == player => {
+                let command = match order {
+                    CompanionOrder::Follow => "follow you".to_string(),
+                    CompanionOrder::Stay => "stay put".to_string(),
+                    CompanionOrder::Attack { target } => format!("attack {}", names.object(*target)),
+                    CompanionOrder::Guard { .. } => "guard the spot".to_string(),
+                    CompanionOrder::Passive => "hold back".to_string(),
+                    CompanionOrder::Return => "come back".to_string(),
+                };
+                let who = companion.map_or("your companions".to_string(), |companion| names.object(companion));
+                self.push(format!("You order {} to {}.", who, command), MessageCategory::General);
+            }
+            GameEvent::LevelUp { new_level } => {
+                self.push(format!("You reach level {}!", new_level), MessageCategory::Progress);
+            }
+            GameEvent::TamingCompleted { tamer, target } if *tamer == player => {
+                // The target already carries TamedBy, so it reads as "your Rat"
+                self.push(format!("{} is now your companion!", names.subject(*target)), MessageCategory::Progress);
+            }
+            GameEvent::TamingFailed { tamer, target } if *tamer == player => {
+                self.push(format!("{} slips away before it is tamed.", names.subject(*target)), MessageCategory::General);
+            }
+            GameEvent::BarkskinActivated { entity } if *entity == player => {
+                self.push("Your skin hardens into bark.", MessageCategory::Status);
+            }
+            GameEvent::FearActivated { entity, .. } if *entity == player => {
+                self.push("A wave of dread pours out from you.", MessageCategory::Status);
+            }
+            GameEvent::ItemPickedUp { entity, item } if *entity == player => {
+                self.push(format!("You pick up the {}.", item_name(*item)), MessageCategory::Item);
+            }
+            GameEvent::GoldPickedUp { entity, amount } if *entity == player => {
+                self.push(format!("You pick up {} gold.", amount), MessageCategory::Item);
+            }
+            GameEvent::ItemDropped { entity, item, .. } if *entity == player => {
+                self.push(format!("You drop the {}.", item_name(*item)), MessageCategory::Item);
+            }
+            GameEvent::ItemPurchased { item, price, .. } => {
+                self.push(format!("You buy the {} for {} gold.", item_name(*item), price), MessageCategory::Item);
+            }
+            GameEvent::ItemSold { item, value, .. } => {
+                self.push(format!("You sell the {} for {} gold.", item_name(*item), value), MessageCategory::Item);
+            }
+            GameEvent::PotionDrunk { entity, potion_type } if *entity == player => {
+                self.push(format!("You drink the {}.", item_name(*potion_type)), MessageCategory::Item);
+            }
+            GameEvent::WeaponEquipped { entity, weapon_type } if *entity == player => {
+                self.push(format!("You wield the {}.", item_name(*weapon_type)), MessageCategory::Item);
+            }
+            GameEvent::ArmorEquipped { entity, armor_type } if *entity == player => {
+                self.push(format!("You put on the {}.", item_name(*armor_type)), MessageCategory::Item);
+            }
+            GameEvent::FloorTransition { direction, .. } => {
+                let text = match direction {
+                    StairDirection::Down => "You descend deeper into the dungeon.",
+                    StairDirection::Up => "You climb back up the stairs.",
+                };
+                self.push(text, MessageCategory::General);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Looks up how entities are referred to in sentences
+struct Names<'a> {
+    world: &'a World,
+    grid: &'a Grid,
+    player: Entity,
+}
+
+impl Names<'_> {
+    fn name(&self, entity: Entity) -> String {
+        if let Ok(name) = self.world.get::<&Name>(entity) {
+            return name.0.to_string();
+        }
+        if let Ok(dialogue) = self.world.get::<&Dialogue>(entity) {
+            return dialogue.name.clone();
+        }
+        "something".to_string()
+    }
+
+    /// True for the player's tamed companions
+    fn is_companion(&self, entity: Entity) -> bool {
+        self.world
+            .get::<&TamedBy>(entity)
+            .map(|tamed| tamed.owner == self.player)
+            .unwrap_or(false)
+    }
+
+    fn is_visible(&self, entity: Entity) -> bool {
+        self.world
+            .get::<&Position>(entity)
+            .ok()
+            .and_then(|pos| self.grid.get(pos.x, pos.y).map(|tile| tile.visible))
+            .unwrap_or(false)
+    }
+
+    /// Whether the player would know this happened (their side, or in view)
+    fn noticed(&self, entity: Entity) -> bool {
+        entity == self.player || self.is_companion(entity) || self.is_visible(entity)
+    }
+
+    /// Mid-sentence reference: "you", "your Rat", "the Skeleton"
+    fn object(&self, entity: Entity) -> String {
+        if entity == self.player {
+            "you".to_string()
+        } else if self.is_companion(entity) {
+            format!("your {}", self.name(entity))
+        } else {
+            format!("the {}", self.name(entity))
+        }
+    }
+
+    /// Start-of-sentence reference: "You", "Your Rat", "The Skeleton"
+    fn subject(&self, entity: Entity) -> String {
+        capitalize(&self.object(entity))
+    }
+
+    /// Start-of-sentence possessive: "Your", "Your Rat's", "The Skeleton Archer's"
+    fn possessive(&self, entity: Entity) -> String {
+        if entity == self.player {
+            "Your".to_string()
+        } else {
+            format!("{}'s", self.subject(entity))
+        }
+    }
+
+    /// Damage to the player's side is bad news, anything else is progress
+    fn damage_category(&self, target: Entity) -> MessageCategory {
+        if target == self.player || self.is_companion(target) {
+            MessageCategory::Hurt
+        } else {
+            MessageCategory::Attack
+        }
+    }
+
+    /// Hazards only count as danger when they hit the player's side
+    fn danger_category(&self, victim: Entity) -> MessageCategory {
+        if victim == self.player || self.is_companion(victim) {
+            MessageCategory::Danger
+        } else {
+            MessageCategory::Status
+        }
+    }
+}
+
+/// Verb for a hit of this damage type, as (second person, third person)
+fn damage_verb(damage_type: DamageType) -> (&'static str, &'static str) {
+    match damage_type {
+        DamageType::Physical => ("hit", "hits"),
+        DamageType::Fire => ("burn", "burns"),
+        DamageType::Necrotic => ("drain", "drains"),
+        DamageType::Poison => ("poison", "poisons"),
+        DamageType::Cold => ("chill", "chills"),
+    }
+}
+
+fn damage_amount(damage: i32) -> String {
+    if damage > 0 {
+        format!("for {}", damage)
+    } else {
+        "for no damage".to_string()
+    }
+}
+
+/// Completes "The Rat is no longer ..."
+fn effect_adjective(effect_type: EffectType) -> &'static str {
+    match effect_type {
+        EffectType::Invisible => "invisible",
+        EffectType::SpeedBoost => "hasted",
+        EffectType::Regenerating => "regenerating",
+        EffectType::Strengthened => "strengthened",
+        EffectType::Protected => "protected",
+        EffectType::Barkskin => "covered in bark",
+        EffectType::Confused => "confused",
+        EffectType::Feared => "afraid",
+        EffectType::Slowed => "slowed",
+        EffectType::Burning => "burning",
+        EffectType::Rooted => "rooted",
+        EffectType::Invulnerable => "invulnerable",
+    }
+}
+
+/// Completes "You haven't found ..."
+fn travel_target_name(target: TravelTarget) -> &'static str {
+    match target {
+        TravelTarget::StairsDown => "the stairs leading down",
+        TravelTarget::StairsUp => "the stairs leading up",
+        TravelTarget::Shop => "the shop",
+        TravelTarget::ItemPile => "any items",
+        TravelTarget::Tile(..) => "that spot",
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Player;
+    use crate::spawning::enemies;
+
+    fn setup() -> (World, Grid, Entity) {
+        let mut world = World::new();
+        let player = world.spawn((Player, Position::new(1, 1)));
+        (world, Grid::new(40, 30), player)
+    }
+
+    #[test]
+    fn test_repeated_messages_merge() {
+        let mut log = MessageLog::new();
+        log.push("The Rat misses you.", MessageCategory::Miss);
+        log.push("The Rat misses you.", MessageCategory::Miss);
+        log.push("You hit the Rat for 3.", MessageCategory::Attack);
+        log.push("The Rat misses you.", MessageCategory::Miss);
+
+        let lines: Vec<String> = log.recent(10).map(LogMessage::display_text).collect();
+        assert_eq!(lines, vec!["The Rat misses you. (x2)", "You hit the Rat for 3.", "The Rat misses you."]);
+    }
+
+    #[test]
+    fn test_history_is_bounded() {
+        let mut log = MessageLog::with_capacity(3);
+        for i in 0..5 {
+            log.push(format!("Message {}", i), MessageCategory::General);
+        }
+        assert_eq!(log.messages().len(), 3);
+        let lines: Vec<String> = log.recent(2).map(LogMessage::display_text).collect();
+        assert_eq!(lines, vec!["Message 3", "Message 4"]);
+    }
+
+    #[test]
+    fn test_events_use_entity_names() {
+        let (mut world, grid, player) = setup();
+        let rat = enemies::RAT.spawn(&mut world, 2, 1, &mut rand::thread_rng());
+        let mut log = MessageLog::new();
+
+        log.handle_event(
+            &GameEvent::AttackHit {
+                attacker: player,
+                target: rat,
+                target_pos: (2.5, 1.5),
+                damage: 4,
+                damage_type: DamageType::Physical,
+                crit: true,
+            },
+            &world,
+            &grid,
+            player,
+        );
+        log.handle_event(
+            &GameEvent::AttackMissed { attacker: rat, target: player, target_pos: (1.5, 1.5), ranged: false },
+            &world,
+            &grid,
+            player,
+        );
+        log.handle_event(
+            &GameEvent::StatusEffectExpired { entity: player, effect_type: EffectType::Invisible },
+            &world,
+            &grid,
+            player,
+        );
+
+        let messages: Vec<_> = log.messages().collect();
+        assert_eq!(messages[0].text, "You critically hit the Rat for 4!");
+        assert_eq!(messages[0].category, MessageCategory::Critical);
+        assert_eq!(messages[1].text, "The Rat misses you.");
+        assert_eq!(messages[2].text, "You are no longer invisible.");
+    }
+
+    #[test]
+    fn test_unseen_fights_are_not_logged() {
+        let (mut world, grid, player) = setup();
+        let mut rng = rand::thread_rng();
+        let rat = enemies::RAT.spawn(&mut world, 5, 5, &mut rng);
+        let skeleton = enemies::SKELETON.spawn(&mut world, 6, 5, &mut rng);
+        let mut log = MessageLog::new();
+
+        log.handle_event(
+            &GameEvent::AttackMissed { attacker: rat, target: skeleton, target_pos: (6.5, 5.5), ranged: false },
+            &world,
+            &grid,
+            player,
+        );
+        assert_eq!(log.messages().len(), 0);
+    }
+}
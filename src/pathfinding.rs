@@ -1,3 +1,7 @@
+use crate::constants::{
+    DIAGONAL_MOVEMENT_MULTIPLIER, PATH_ARCHER_RANGE_PENALTY, PATH_FIRE_PENALTY, PATH_TRAP_PENALTY,
+    PATH_WATER_PENALTY,
+};
 use crate::grid::Grid;
 use crate::tile::TileType;
 use std::collections::{BinaryHeap, HashMap, HashSet};
@@ -96,6 +100,97 @@ pub fn step_distance(from: (i32, i32), to: (i32, i32)) -> f32 {
     }
 }
 
+/// Cost of an orthogonal step (fixed point: one walk = 100)
+pub const STEP_COST: i32 = 100;
+/// Cost of a diagonal step, matching the longer diagonal move duration
+pub const DIAGONAL_STEP_COST: i32 = (STEP_COST as f32 * DIAGONAL_MOVEMENT_MULTIPLIER) as i32;
+
+/// The eight directions a path can step in, orthogonal first
+pub const NEIGHBORS: [(i32, i32); 8] = [
+    (0, 1),
+    (0, -1),
+    (1, 0),
+    (-1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Cost of a single step between adjacent tiles
+pub fn step_cost(from: (i32, i32), to: (i32, i32)) -> i32 {
+    if from.0 != to.0 && from.1 != to.1 {
+        DIAGONAL_STEP_COST
+    } else {
+        STEP_COST
+    }
+}
+
+/// Total cost of walking a path (as returned by `find_path`) from `start`
+pub fn path_cost(start: (i32, i32), path: &[(i32, i32)]) -> i32 {
+    let mut prev = start;
+    path.iter()
+        .map(|&pos| {
+            let cost = step_cost(prev, pos);
+            prev = pos;
+            cost
+        })
+        .sum()
+}
+
+/// Whether a path may step from `from` to the adjacent tile `to`, ignoring entities.
+/// Diagonal steps can't cut the corner of a wall and can't enter, leave or
+/// squeeze past a doorway.
+pub fn can_step(grid: &Grid, from: (i32, i32), to: (i32, i32)) -> bool {
+    if !grid.is_walkable(to.0, to.1) {
+        return false;
+    }
+    if from.0 == to.0 || from.1 == to.1 {
+        return true;
+    }
+
+    let corner_open = |x: i32, y: i32| grid.is_walkable(x, y) && !grid.is_doorway(x, y);
+    !grid.is_doorway(from.0, from.1)
+        && !grid.is_doorway(to.0, to.1)
+        && corner_open(to.0, from.1)
+        && corner_open(from.0, to.1)
+}
+
+/// How much a walker dislikes each kind of hazard, as extra cost (in straight
+/// steps) for entering an affected tile. Zero means the walker doesn't care.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CostProfile {
+    /// Burning tiles (braziers, campfires)
+    pub fire: i32,
+    /// Traps the walker knows about and would set off
+    pub traps: i32,
+    /// Deep water
+    pub water: i32,
+    /// Tiles within range of a hostile archer
+    pub archer_range: i32,
+}
+
+impl CostProfile {
+    /// Walks straight through anything
+    pub const NONE: Self = Self { fire: 0, traps: 0, water: 0, archer_range: 0 };
+
+    /// Steers around every hazard it knows about (the player)
+    pub const CAUTIOUS: Self = Self {
+        fire: PATH_FIRE_PENALTY,
+        traps: PATH_TRAP_PENALTY,
+        water: PATH_WATER_PENALTY,
+        archer_range: PATH_ARCHER_RANGE_PENALTY,
+    };
+
+    /// Keeps out of fire but otherwise takes the short way (most creatures)
+    pub const CREATURE: Self = Self { fire: PATH_FIRE_PENALTY, ..Self::NONE };
+
+    /// Whether this profile adds nothing to the plain step cost
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 struct Node {
     x: i32,
@@ -122,6 +217,7 @@ impl PartialOrd for ScoredNode {
 }
 
 /// Find a path from start to goal using A* algorithm.
+/// Paths step in all eight directions, each weighted by how long the move takes.
 /// Returns the path as a vector of (x, y) positions, excluding the start position.
 /// Returns None if no path exists.
 pub fn find_path(
@@ -129,6 +225,43 @@ pub fn find_path(
     start: (i32, i32),
     goal: (i32, i32),
     blocked: &HashSet<(i32, i32)>,
+) -> Option<Vec<(i32, i32)>> {
+    search(grid, start, goal, &|tile| blocked.contains(&tile), usize::MAX, &|_| 0)
+}
+
+/// Like `find_path`, but gives up after expanding `max_nodes` tiles, and asks
+/// `is_blocked` about each tile it reaches instead of taking a prebuilt set.
+/// Used where a search must stay cheap no matter how the floor is laid out.
+pub fn find_path_limited(
+    grid: &Grid,
+    start: (i32, i32),
+    goal: (i32, i32),
+    is_blocked: &dyn Fn((i32, i32)) -> bool,
+    max_nodes: usize,
+) -> Option<Vec<(i32, i32)>> {
+    search(grid, start, goal, is_blocked, max_nodes, &|_| 0)
+}
+
+/// Like `find_path`, but entering a tile also costs `tile_cost(tile)` on top of
+/// the step itself, so the path detours around expensive tiles when it's worth it.
+pub fn find_path_weighted(
+    grid: &Grid,
+    start: (i32, i32),
+    goal: (i32, i32),
+    blocked: &HashSet<(i32, i32)>,
+    tile_cost: &dyn Fn((i32, i32)) -> i32,
+) -> Option<Vec<(i32, i32)>> {
+    search(grid, start, goal, &|tile| blocked.contains(&tile), usize::MAX, tile_cost)
+}
+
+/// A* search shared by the `find_path` variants
+fn search(
+    grid: &Grid,
+    start: (i32, i32),
+    goal: (i32, i32),
+    is_blocked: &dyn Fn((i32, i32)) -> bool,
+    max_nodes: usize,
+    tile_cost: &dyn Fn((i32, i32)) -> i32,
 ) -> Option<Vec<(i32, i32)>> {
     let start_node = Node { x: start.0, y: start.1 };
     let goal_node = Node { x: goal.0, y: goal.1 };
@@ -152,31 +285,33 @@ pub fn find_path(
         f_score: heuristic(start, goal),
     });
 
+    let mut expanded = 0;
     while let Some(current) = open_set.pop() {
         if current.node == goal_node {
             // Reconstruct path
             return Some(reconstruct_path(&came_from, current.node));
         }
 
+        expanded += 1;
+        if expanded > max_nodes {
+            return None;
+        }
+
         let current_g = *g_score.get(&current.node).unwrap_or(&i32::MAX);
 
-        // Check all 4 neighbors
-        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+        // Check all 8 neighbors
+        let from = (current.node.x, current.node.y);
+        for (dx, dy) in NEIGHBORS {
             let nx = current.node.x + dx;
             let ny = current.node.y + dy;
             let neighbor = Node { x: nx, y: ny };
 
-            // Check if walkable
-            let (walkable, is_stairs) = if let Some(tile) = grid.get(nx, ny) {
-                let is_stairs = matches!(tile.tile_type, TileType::StairsUp | TileType::StairsDown);
-                (tile.tile_type.is_walkable(), is_stairs)
-            } else {
-                (false, false)
-            };
-
-            if !walkable {
+            if !can_step(grid, from, (nx, ny)) {
                 continue;
             }
+            let is_stairs = grid
+                .get(nx, ny)
+                .is_some_and(|tile| matches!(tile.tile_type, TileType::StairsUp | TileType::StairsDown));
 
             // Avoid stairs unless they're the goal (prevents accidental floor transitions)
             if is_stairs && (nx, ny) != goal {
@@ -184,11 +319,11 @@ pub fn find_path(
             }
 
             // Check if blocked by entity (but allow the goal position)
-            if blocked.contains(&(nx, ny)) && (nx, ny) != goal {
+            if (nx, ny) != goal && is_blocked((nx, ny)) {
                 continue;
             }
 
-            let tentative_g = current_g + 1;
+            let tentative_g = current_g + step_cost(from, (nx, ny)) + tile_cost((nx, ny));
             let neighbor_g = *g_score.get(&neighbor).unwrap_or(&i32::MAX);
 
             if tentative_g < neighbor_g {
@@ -220,9 +355,11 @@ pub fn next_step_toward(
     find_path(grid, start, goal, blocked).and_then(|path| path.first().copied())
 }
 
-/// Manhattan distance heuristic
+/// Octile distance heuristic: the cost of the cheapest path on open floor
 fn heuristic(from: (i32, i32), to: (i32, i32)) -> i32 {
-    (from.0 - to.0).abs() + (from.1 - to.1).abs()
+    let dx = (from.0 - to.0).abs();
+    let dy = (from.1 - to.1).abs();
+    STEP_COST * dx.max(dy) + (DIAGONAL_STEP_COST - STEP_COST) * dx.min(dy)
 }
 
 /// Reconstruct the path from came_from map
@@ -249,63 +386,28 @@ mod tests {
 
     /// Create a simple test grid with all floor tiles
     fn make_floor_grid(width: usize, height: usize) -> Grid {
-        Grid {
-            width,
-            height,
-            tiles: vec![Tile::new(TileType::Floor); width * height],
-            chest_positions: vec![],
-            door_positions: vec![],
-            brazier_positions: vec![],
-            decals: vec![],
-            stairs_up_pos: None,
-            stairs_down_pos: None,
-            starting_room: None,
-            illumination: vec![0.0; width * height],
-            themed_rooms: vec![],
-            water_positions: vec![],
-            coffin_positions: vec![],
-            barrel_positions: vec![],
-            shop_position: None,
-            shop_decor_positions: vec![],
-        }
+        Grid::filled(width, height, TileType::Floor)
     }
 
     /// Create a grid with a wall in the middle
     fn make_grid_with_wall() -> Grid {
         // 5x5 grid with a vertical wall at x=2 (except y=0 which is open)
-        let mut tiles = vec![Tile::new(TileType::Floor); 25];
+        let mut grid = Grid::filled(5, 5, TileType::Floor);
         // Wall at (2,1), (2,2), (2,3), (2,4)
         for y in 1..5 {
-            tiles[y * 5 + 2] = Tile::new(TileType::Wall);
-        }
-        Grid {
-            width: 5,
-            height: 5,
-            tiles,
-            chest_positions: vec![],
-            door_positions: vec![],
-            brazier_positions: vec![],
-            decals: vec![],
-            stairs_up_pos: None,
-            stairs_down_pos: None,
-            starting_room: None,
-            illumination: vec![0.0; 25],
-            themed_rooms: vec![],
-            water_positions: vec![],
-            coffin_positions: vec![],
-            barrel_positions: vec![],
-            shop_position: None,
-            shop_decor_positions: vec![],
+            grid.tiles[y * 5 + 2] = Tile::new(TileType::Wall);
         }
+        grid
     }
 
     #[test]
     fn test_heuristic() {
         assert_eq!(heuristic((0, 0), (0, 0)), 0);
-        assert_eq!(heuristic((0, 0), (1, 0)), 1);
-        assert_eq!(heuristic((0, 0), (0, 1)), 1);
-        assert_eq!(heuristic((0, 0), (3, 4)), 7);
-        assert_eq!(heuristic((5, 5), (2, 1)), 7);
+        assert_eq!(heuristic((0, 0), (1, 0)), STEP_COST);
+        assert_eq!(heuristic((0, 0), (0, 1)), STEP_COST);
+        assert_eq!(heuristic((0, 0), (1, 1)), DIAGONAL_STEP_COST);
+        assert_eq!(heuristic((0, 0), (3, 4)), 3 * DIAGONAL_STEP_COST + STEP_COST);
+        assert_eq!(heuristic((5, 5), (2, 1)), 3 * DIAGONAL_STEP_COST + STEP_COST);
     }
 
     #[test]
@@ -328,9 +430,14 @@ mod tests {
         let path = find_path(&grid, (0, 0), (3, 3), &blocked);
         assert!(path.is_some());
         let path = path.unwrap();
-        // Manhattan distance is 6, so path should be 6 steps
-        assert_eq!(path.len(), 6);
-        assert_eq!(path.last(), Some(&(3, 3)));
+        // Straight along the diagonal
+        assert_eq!(path, vec![(1, 1), (2, 2), (3, 3)]);
+        assert_eq!(path_cost((0, 0), &path), 3 * DIAGONAL_STEP_COST);
+
+        // Mixed: diagonals first or last, never a zig-zag that costs more
+        let path = find_path(&grid, (0, 0), (5, 2), &blocked).unwrap();
+        assert_eq!(path.len(), 5);
+        assert_eq!(path_cost((0, 0), &path), 2 * DIAGONAL_STEP_COST + 3 * STEP_COST);
     }
 
     #[test]
@@ -368,6 +475,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_no_corner_cutting_past_walls() {
+        // Two walls touching at a corner leave only a diagonal gap:
+        //   . . . .
+        //   . # . .
+        //   . . # .
+        //   . . . .
+        let mut grid = make_floor_grid(4, 4);
+        grid.tiles[4 + 1] = Tile::new(TileType::Wall);
+        grid.tiles[2 * 4 + 2] = Tile::new(TileType::Wall);
+        let blocked = HashSet::new();
+
+        assert!(!can_step(&grid, (1, 2), (2, 1)));
+        assert!(can_step(&grid, (0, 0), (1, 0)));
+
+        // Squeezing through the gap would take one step; going around takes three
+        let path = find_path(&grid, (1, 2), (2, 1), &blocked).unwrap();
+        assert!(path.len() > 1);
+        let mut prev = (1, 2);
+        for &step in &path {
+            assert!(can_step(&grid, prev, step), "cut a corner from {:?} to {:?}", prev, step);
+            prev = step;
+        }
+    }
+
+    #[test]
+    fn test_doorways_are_entered_straight() {
+        // Vertical wall at x=2 with a door at (2, 2)
+        let mut grid = make_floor_grid(5, 5);
+        for y in 0..5 {
+            grid.tiles[y * 5 + 2] = Tile::new(TileType::Wall);
+        }
+        grid.tiles[2 * 5 + 2] = Tile::new(TileType::Floor);
+        grid.tiles[2 * 5 + 2].doorway = true;
+        let blocked = HashSet::new();
+
+        let path = find_path(&grid, (0, 0), (4, 4), &blocked).unwrap();
+        let door_idx = path.iter().position(|&p| p == (2, 2)).unwrap();
+        assert_eq!(path[door_idx - 1], (1, 2));
+        assert_eq!(path[door_idx + 1], (3, 2));
+
+        // A closed door sitting in a gap between open tiles can't be cut past either
+        let mut grid = make_floor_grid(3, 3);
+        grid.tiles[3 + 1].doorway = true;
+        assert!(!can_step(&grid, (0, 1), (1, 0)));
+        assert!(!can_step(&grid, (0, 0), (1, 1)));
+        assert!(can_step(&grid, (0, 0), (1, 0)));
+    }
+
+    #[test]
+    fn test_find_path_limited_gives_up() {
+        let grid = make_floor_grid(30, 30);
+
+        assert!(find_path_limited(&grid, (0, 0), (29, 29), &|_| false, 10).is_none());
+        let path = find_path_limited(&grid, (0, 0), (29, 29), &|_| false, 10_000).unwrap();
+        assert_eq!(path.len(), 29);
+
+        // Blocked tiles come from the callback
+        let detour = find_path_limited(&grid, (0, 0), (2, 0), &|tile| tile == (1, 0), 10_000).unwrap();
+        assert!(!detour.contains(&(1, 0)));
+    }
+
     #[test]
     fn test_find_path_no_path() {
         // Create a grid where the goal is completely surrounded by walls
@@ -381,25 +550,7 @@ mod tests {
         tiles[3 * 5 + 1] = Tile::new(TileType::Wall);
         tiles[3 * 5 + 2] = Tile::new(TileType::Wall);
         tiles[3 * 5 + 3] = Tile::new(TileType::Wall);
-        let grid = Grid {
-            width: 5,
-            height: 5,
-            tiles,
-            chest_positions: vec![],
-            door_positions: vec![],
-            brazier_positions: vec![],
-            decals: vec![],
-            stairs_up_pos: None,
-            stairs_down_pos: None,
-            starting_room: None,
-            illumination: vec![0.0; 25],
-            themed_rooms: vec![],
-            water_positions: vec![],
-            coffin_positions: vec![],
-            barrel_positions: vec![],
-            shop_position: None,
-            shop_decor_positions: vec![],
-        };
+        let grid = Grid { tiles, ..Grid::filled(5, 5, TileType::Floor) };
 
         let path = find_path(&grid, (0, 0), (2, 2), &HashSet::new());
         assert!(path.is_none());
@@ -409,25 +560,7 @@ mod tests {
     fn test_find_path_to_unwalkable_tile() {
         let mut tiles = vec![Tile::new(TileType::Floor); 25];
         tiles[2 * 5 + 2] = Tile::new(TileType::Wall); // Goal is a wall
-        let grid = Grid {
-            width: 5,
-            height: 5,
-            tiles,
-            chest_positions: vec![],
-            door_positions: vec![],
-            brazier_positions: vec![],
-            decals: vec![],
-            stairs_up_pos: None,
-            stairs_down_pos: None,
-            starting_room: None,
-            illumination: vec![0.0; 25],
-            themed_rooms: vec![],
-            water_positions: vec![],
-            coffin_positions: vec![],
-            barrel_positions: vec![],
-            shop_position: None,
-            shop_decor_positions: vec![],
-        };
+        let grid = Grid { tiles, ..Grid::filled(5, 5, TileType::Floor) };
 
         let path = find_path(&grid, (0, 0), (2, 2), &HashSet::new());
         assert!(path.is_none());
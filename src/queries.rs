@@ -8,7 +8,7 @@ use std::collections::HashSet;
 use hecs::{Entity, World};
 
 use crate::components::{
-    Actor, Attackable, BlocksMovement, BlocksVision, EffectType, Position,
+    Actor, Attackable, BlocksMovement, BlocksVision, CompanionAI, EffectType, Position,
 };
 use crate::systems::effects;
 
@@ -68,6 +68,14 @@ pub fn get_entity_position(world: &World, entity: Entity) -> Option<(i32, i32)>
     world.get::<&Position>(entity).ok().map(|p| (p.x, p.y))
 }
 
+/// Check if an entity has any companions following it.
+pub fn has_companions(world: &World, owner: Entity) -> bool {
+    world
+        .query::<&CompanionAI>()
+        .iter()
+        .any(|(_, ai)| ai.owner == owner)
+}
+
 /// Check if a position is blocked by any entity (excluding a specific one).
 pub fn is_position_blocked(world: &World, x: i32, y: i32, exclude: Option<Entity>) -> bool {
     world
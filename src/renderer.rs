@@ -1099,6 +1099,7 @@ impl Renderer {
                     crate::vfx::VfxType::Slash { angle } => *angle,
                     crate::vfx::VfxType::DamageNumber { .. } => continue, // Rendered via egui
                     crate::vfx::VfxType::HealNumber { .. } => continue, // Rendered via egui
+                    crate::vfx::VfxType::Miss => continue, // Rendered via egui
                     crate::vfx::VfxType::Fire { .. } => continue, // Rendered separately
                     crate::vfx::VfxType::Alert => continue, // Rendered via egui
                     crate::vfx::VfxType::Explosion { .. } => continue, // Rendered via egui
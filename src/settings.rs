@@ -0,0 +1,123 @@
+//! Player settings that persist between runs.
+//!
+//! Stored as `settings.json` in the config directory. Missing fields fall
+//! back to their defaults and out-of-range values are clamped, so an old or
+//! hand-edited file never stops the game from starting.
+
+use serde::{Deserialize, Serialize};
+
+use crate::config;
+use crate::constants::{
+    CAMERA_MAX_ZOOM, CAMERA_MIN_ZOOM, CAMERA_ZOOM_LIMIT_CEILING, CAMERA_ZOOM_LIMIT_FLOOR, DEFAULT_VOLUME,
+    UI_SCALE_MAX, UI_SCALE_MIN,
+};
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Everything on the options screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Master volume (0.0 - 1.0)
+    pub volume: f32,
+    pub fullscreen: bool,
+    pub show_grid_lines: bool,
+    /// Closest the camera zooms out (pixels per grid cell)
+    pub min_zoom: f32,
+    /// Furthest the camera zooms in (pixels per grid cell)
+    pub max_zoom: f32,
+    /// Size of the interface relative to normal
+    pub ui_scale: f32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            volume: DEFAULT_VOLUME,
+            fullscreen: false,
+            show_grid_lines: false,
+            min_zoom: CAMERA_MIN_ZOOM,
+            max_zoom: CAMERA_MAX_ZOOM,
+            ui_scale: 1.0,
+        }
+    }
+}
+
+impl Settings {
+    /// The settings file, validated, or the defaults if there isn't one.
+    /// A broken file is reported and replaced by the defaults.
+    pub fn load() -> Self {
+        match config::read(SETTINGS_FILE) {
+            Ok(Some(json)) => match serde_json::from_str::<Settings>(&json) {
+                Ok(settings) => settings.validated(),
+                Err(e) => {
+                    eprintln!("Warning: {}: {}", SETTINGS_FILE, e);
+                    Self::default()
+                }
+            },
+            Ok(None) => Self::default(),
+            Err(e) => {
+                eprintln!("Warning: {}", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Write the settings file
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        config::write(SETTINGS_FILE, &json)
+    }
+
+    /// Clamp every value into its allowed range
+    pub fn validated(self) -> Self {
+        let defaults = Self::default();
+        let clamp = |value: f32, min: f32, max: f32, default: f32| {
+            if value.is_finite() {
+                value.clamp(min, max)
+            } else {
+                default
+            }
+        };
+
+        let min_zoom = clamp(self.min_zoom, CAMERA_ZOOM_LIMIT_FLOOR, CAMERA_ZOOM_LIMIT_CEILING, defaults.min_zoom);
+        let max_zoom = clamp(self.max_zoom, CAMERA_ZOOM_LIMIT_FLOOR, CAMERA_ZOOM_LIMIT_CEILING, defaults.max_zoom);
+        Self {
+            volume: clamp(self.volume, 0.0, 1.0, defaults.volume),
+            min_zoom: min_zoom.min(max_zoom),
+            max_zoom: max_zoom.max(min_zoom),
+            ui_scale: clamp(self.ui_scale, UI_SCALE_MIN, UI_SCALE_MAX, defaults.ui_scale),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: Settings = serde_json::from_str(r#"{"volume": 0.2, "fullscreen": true}"#).unwrap();
+        assert_eq!(settings.volume, 0.2);
+        assert!(settings.fullscreen);
+        assert_eq!(settings.max_zoom, Settings::default().max_zoom);
+    }
+
+    #[test]
+    fn test_validation_clamps_and_orders_values() {
+        let settings = Settings {
+            volume: 3.0,
+            min_zoom: 200.0,
+            max_zoom: 100.0,
+            ui_scale: f32::NAN,
+            ..Settings::default()
+        }
+        .validated();
+
+        assert_eq!(settings.volume, 1.0);
+        assert_eq!((settings.min_zoom, settings.max_zoom), (100.0, 200.0));
+        assert_eq!(settings.ui_scale, 1.0);
+        assert_eq!(Settings::default().validated(), Settings::default());
+    }
+}
@@ -1,13 +1,19 @@
 //! Spatial cache for efficient blocking position lookups.
 //!
 //! Maintains persistent HashSets of blocking positions that are updated
-//! incrementally rather than rebuilt on every query.
+//! incrementally rather than rebuilt on every query, a coarse index of where
+//! tracked entities stand (for "who is near here" lookups), plus the shared
+//! navigation maps AI reads its movement from.
 
 use std::collections::{HashMap, HashSet};
 
 use hecs::{Entity, World};
 
-use crate::components::{BlocksMovement, BlocksVision, Position};
+use crate::components::{BlocksMovement, BlocksVision, Container, Health, Position};
+use crate::dijkstra_map::NavigationMaps;
+
+/// Side length of the chunks the entity index buckets positions into
+const INDEX_CHUNK_SIZE: i32 = 8;
 
 /// Cached spatial data for blocking position lookups.
 ///
@@ -26,6 +32,15 @@ pub struct SpatialCache {
 
     /// Entity -> blocking flags for knowing what to update
     entity_flags: HashMap<Entity, (bool, bool)>, // (blocks_movement, blocks_vision)
+
+    /// Chunk -> tracked entities standing in it, for radius lookups
+    chunks: HashMap<(i32, i32), Vec<Entity>>,
+
+    /// Position -> tracked entities standing on it
+    occupants: HashMap<(i32, i32), Vec<Entity>>,
+
+    /// Dijkstra maps toward/away from the player, rebuilt once per player move
+    nav_maps: NavigationMaps,
 }
 
 impl SpatialCache {
@@ -36,6 +51,9 @@ impl SpatialCache {
             vision_blocking: HashSet::new(),
             entity_positions: HashMap::new(),
             entity_flags: HashMap::new(),
+            chunks: HashMap::new(),
+            occupants: HashMap::new(),
+            nav_maps: NavigationMaps::new(),
         }
     }
 
@@ -49,20 +67,25 @@ impl SpatialCache {
 
     /// Rebuild cache in place from current world state.
     /// Called after floor transitions and other world-altering operations.
+    /// Cost is proportional to the number of blocking entities, not the map size.
     pub fn rebuild_in_place(&mut self, world: &World) {
         self.blocking_positions.clear();
         self.vision_blocking.clear();
         self.entity_positions.clear();
         self.entity_flags.clear();
-
-        // Register all entities with BlocksMovement
-        for (entity, (pos, _)) in world.query::<(&Position, &BlocksMovement)>().iter() {
+        self.chunks.clear();
+        self.occupants.clear();
+        self.nav_maps.clear();
+
+        // Register all entities with BlocksMovement (optionally also blocking vision)
+        for (entity, (pos, _, blocks_vision)) in world
+            .query::<(&Position, &BlocksMovement, Option<&BlocksVision>)>()
+            .iter()
+        {
             let position = (pos.x, pos.y);
+            let blocks_vision = blocks_vision.is_some();
             self.blocking_positions.insert(position);
-            self.entity_positions.insert(entity, position);
-
-            // Check if also blocks vision
-            let blocks_vision = world.get::<&BlocksVision>(entity).is_ok();
+            self.track(entity, position);
             self.entity_flags.insert(entity, (true, blocks_vision));
 
             if blocks_vision {
@@ -71,18 +94,25 @@ impl SpatialCache {
         }
 
         // Register entities that only block vision (no BlocksMovement)
-        for (entity, (pos, _)) in world.query::<(&Position, &BlocksVision)>().iter() {
+        for (entity, (pos, _)) in world
+            .query::<hecs::Without<(&Position, &BlocksVision), &BlocksMovement>>()
+            .iter()
+        {
             let position = (pos.x, pos.y);
-
-            // Skip if already registered (has BlocksMovement)
-            if self.entity_flags.contains_key(&entity) {
-                continue;
-            }
-
             self.vision_blocking.insert(position);
-            self.entity_positions.insert(entity, position);
+            self.track(entity, position);
             self.entity_flags.insert(entity, (false, true));
         }
+
+        // Creatures and containers that block nothing (tamed companions, item piles, corpses)
+        // are still indexed for radius lookups
+        for (entity, (pos, _)) in world
+            .query::<hecs::Without<(&Position, hecs::Or<&Health, &Container>), hecs::Or<&BlocksMovement, &BlocksVision>>>()
+            .iter()
+        {
+            self.track(entity, (pos.x, pos.y));
+            self.entity_flags.insert(entity, (false, false));
+        }
     }
 
     /// Register a new entity with blocking properties.
@@ -98,7 +128,7 @@ impl SpatialCache {
             return; // Nothing to track
         }
 
-        self.entity_positions.insert(entity, position);
+        self.track(entity, position);
         self.entity_flags.insert(entity, (blocks_movement, blocks_vision));
 
         if blocks_movement {
@@ -109,6 +139,16 @@ impl SpatialCache {
         }
     }
 
+    /// Index something that blocks nothing (an item pile, a corpse) for radius lookups.
+    /// Does nothing if the entity is already tracked.
+    pub fn track_item(&mut self, entity: Entity, position: (i32, i32)) {
+        if self.entity_positions.contains_key(&entity) {
+            return;
+        }
+        self.track(entity, position);
+        self.entity_flags.insert(entity, (false, false));
+    }
+
     /// Update an entity's position in the cache.
     /// Called when entities move (apply_move, apply_blink).
     pub fn update_position(&mut self, entity: Entity, old_pos: (i32, i32), new_pos: (i32, i32)) {
@@ -117,7 +157,10 @@ impl SpatialCache {
         };
 
         // Update position mapping
-        self.entity_positions.insert(entity, new_pos);
+        if let Some(indexed) = self.entity_positions.get(&entity).copied() {
+            self.untrack(entity, indexed);
+        }
+        self.track(entity, new_pos);
 
         // Update blocking sets
         if blocks_movement {
@@ -133,9 +176,10 @@ impl SpatialCache {
     /// Remove an entity from the cache.
     /// Called when entities die or are despawned.
     pub fn remove_entity(&mut self, entity: Entity) {
-        let Some(position) = self.entity_positions.remove(&entity) else {
+        let Some(position) = self.entity_positions.get(&entity).copied() else {
             return; // Entity not tracked
         };
+        self.untrack(entity, position);
 
         let Some((blocks_movement, blocks_vision)) = self.entity_flags.remove(&entity) else {
             return;
@@ -201,6 +245,48 @@ impl SpatialCache {
         &self.vision_blocking
     }
 
+    /// Shared navigation maps (see `dijkstra_map`).
+    pub fn nav_maps(&self) -> &NavigationMaps {
+        &self.nav_maps
+    }
+
+    /// Mutable access for rebuilding the navigation maps.
+    pub fn nav_maps_mut(&mut self) -> &mut NavigationMaps {
+        &mut self.nav_maps
+    }
+
+    /// Tracked entities within `radius` tiles (Chebyshev distance) of `center`, with their positions.
+    /// Only looks at the chunks the radius overlaps, so the cost does not grow with the floor.
+    pub fn entities_within(&self, center: (i32, i32), radius: i32) -> impl Iterator<Item = (Entity, (i32, i32))> + '_ {
+        let (min_x, min_y) = chunk_of((center.0 - radius, center.1 - radius));
+        let (max_x, max_y) = chunk_of((center.0 + radius, center.1 + radius));
+        (min_y..=max_y)
+            .flat_map(move |cy| (min_x..=max_x).map(move |cx| (cx, cy)))
+            .filter_map(|chunk| self.chunks.get(&chunk))
+            .flatten()
+            .filter_map(|entity| self.entity_positions.get(entity).map(|&pos| (*entity, pos)))
+            .filter(move |&(_, pos)| (pos.0 - center.0).abs().max((pos.1 - center.1).abs()) <= radius)
+    }
+
+    /// Tracked entities standing on `pos`
+    pub fn entities_at(&self, pos: (i32, i32)) -> impl Iterator<Item = Entity> + '_ {
+        self.occupants.get(&pos).into_iter().flatten().copied()
+    }
+
+    /// Record an entity's position in the position map, the chunk index and the occupant map
+    fn track(&mut self, entity: Entity, position: (i32, i32)) {
+        self.entity_positions.insert(entity, position);
+        self.chunks.entry(chunk_of(position)).or_default().push(entity);
+        self.occupants.entry(position).or_default().push(entity);
+    }
+
+    /// Drop an entity from the occupant map, the chunk index and the position map
+    fn untrack(&mut self, entity: Entity, position: (i32, i32)) {
+        self.entity_positions.remove(&entity);
+        remove_from(&mut self.chunks, chunk_of(position), entity);
+        remove_from(&mut self.occupants, position, entity);
+    }
+
     /// Check if a position is blocked for movement.
     #[inline]
     pub fn is_blocked(&self, pos: (i32, i32)) -> bool {
@@ -214,6 +300,20 @@ impl SpatialCache {
     }
 }
 
+fn chunk_of(pos: (i32, i32)) -> (i32, i32) {
+    (pos.0.div_euclid(INDEX_CHUNK_SIZE), pos.1.div_euclid(INDEX_CHUNK_SIZE))
+}
+
+/// Remove an entity from one bucket of an index, dropping the bucket once empty
+fn remove_from(index: &mut HashMap<(i32, i32), Vec<Entity>>, key: (i32, i32), entity: Entity) {
+    if let Some(entities) = index.get_mut(&key) {
+        entities.retain(|&e| e != entity);
+        if entities.is_empty() {
+            index.remove(&key);
+        }
+    }
+}
+
 impl Default for SpatialCache {
     fn default() -> Self {
         Self::new()
@@ -242,25 +342,7 @@ mod tests {
                 }
             }
         }
-        crate::grid::Grid {
-            width,
-            height,
-            tiles,
-            chest_positions: vec![],
-            door_positions: vec![],
-            brazier_positions: vec![],
-            decals: vec![],
-            stairs_up_pos: None,
-            stairs_down_pos: None,
-            starting_room: None,
-            illumination: vec![0.0; width * height],
-            themed_rooms: vec![],
-            water_positions: vec![],
-            coffin_positions: vec![],
-            barrel_positions: vec![],
-            shop_position: None,
-            shop_decor_positions: vec![],
-        }
+        crate::grid::Grid { tiles, ..crate::grid::Grid::filled(width, height, TileType::Floor) }
     }
 
     #[test]
@@ -380,4 +462,29 @@ mod tests {
             "enemy should pathfind through corridor without ghost blockers"
         );
     }
+
+    #[test]
+    fn test_entities_within_follows_moves_and_removals() {
+        let mut world = World::new();
+        let near = world.spawn((Position { x: 3, y: 3 }, BlocksMovement));
+        let far = world.spawn((Position { x: 40, y: 3 }, BlocksMovement));
+        // Creatures that block nothing are indexed too
+        let companion = world.spawn((Position { x: 5, y: 4 }, crate::components::Health::new(10)));
+
+        let mut cache = SpatialCache::rebuild_from_world(&world);
+        let mut found: Vec<Entity> = cache.entities_within((4, 4), 2).map(|(e, _)| e).collect();
+        found.sort();
+        let mut expected = vec![near, companion];
+        expected.sort();
+        assert_eq!(found, expected);
+
+        // Crossing a chunk boundary moves it in the index
+        cache.update_position(far, (40, 3), (6, 3));
+        assert!(cache.entities_within((4, 4), 2).any(|(e, pos)| e == far && pos == (6, 3)));
+        assert_eq!(cache.entities_at((6, 3)).collect::<Vec<_>>(), vec![far]);
+
+        cache.remove_entity(near);
+        assert!(cache.entities_within((4, 4), 2).all(|(e, _)| e != near));
+        assert!(!cache.is_blocked((3, 3)));
+    }
 }
@@ -5,11 +5,14 @@
 
 use crate::components::{
     Actor, Attackable, BlocksMovement, ChaseAI, Dialogue, DialogueAction, DialogueNode, DialogueOption,
-    Equipment, FriendlyNPC, Health, LightSource, OverlaySprite, Position, RangedWeapon, Sprite, Stats,
-    StatusEffects, Tameable, VisualPosition, Vendor, Weapon,
+    Equipment, Faction, FriendlyNPC, Health, ItemType, LightSource, Name, OverlaySprite, Position, RangedWeapon, Resistances, Sprite, Stats,
+    StatusEffects, Tameable, VisualPosition, Vendor, Weapon, Behavior, Tactic,
 };
-use crate::tile::{tile_ids, SpriteSheet};
+use crate::grid::Grid;
+use crate::systems::item_defs::get_armor_def;
+use crate::tile::{tile_ids, SpriteSheet, TileType};
 use hecs::World;
+use rand::Rng;
 
 /// Ranged attack configuration for enemies
 #[derive(Clone, Copy)]
@@ -25,8 +28,7 @@ pub struct RangedConfig {
 /// Definition of an enemy type - all the data needed to spawn one
 #[derive(Clone)]
 pub struct EnemyDef {
-    /// Display name (for future UI/logs)
-    #[allow(dead_code)] // Reserved for combat log/bestiary
+    /// Display name (message log, bestiary)
     pub name: &'static str,
     /// Sprite sheet and tile ID
     pub sprite: (SpriteSheet, u32),
@@ -50,11 +52,21 @@ pub struct EnemyDef {
     pub ranged: Option<RangedConfig>,
     /// Whether this enemy can be tamed (for Druid ability)
     pub tameable: bool,
+    /// Armor pieces this enemy may spawn wearing, with the chance for each
+    pub armor: &'static [(ItemType, f32)],
+    /// Damage type resistances and vulnerabilities
+    pub resistances: Resistances,
+    /// Behavior archetype (see `archetypes`); empty for plain chase AI
+    pub behavior: &'static [Tactic],
+    /// Tile type this enemy prefers to spawn on, if any
+    pub habitat: Option<TileType>,
+    /// Which side it is on (who it fights, who it helps)
+    pub faction: Faction,
 }
 
 impl EnemyDef {
-    /// Spawn this enemy type at the given position
-    pub fn spawn(&self, world: &mut World, x: i32, y: i32) -> hecs::Entity {
+    /// Spawn this enemy type at the given position, rolling its armor from `rng`
+    pub fn spawn(&self, world: &mut World, x: i32, y: i32, rng: &mut impl Rng) -> hecs::Entity {
         let pos = Position::new(x, y);
 
         // Build base components
@@ -80,6 +92,16 @@ impl EnemyDef {
             )
         };
 
+        // Roll for armor (dropped on death like any other loot)
+        let mut equipment = equipment;
+        for &(item_type, chance) in self.armor {
+            if rng.gen::<f32>() < chance {
+                if let Some(def) = get_armor_def(item_type) {
+                    equipment.equip_armor(def.slot, item_type);
+                }
+            }
+        }
+
         // Spawn with or without overlay sprite
         let entity = if let Some(overlay_ref) = self.overlay_sprite {
             world.spawn((
@@ -112,20 +134,74 @@ impl EnemyDef {
             ))
         };
 
+        let _ = world.insert(entity, (Name(self.name), self.faction));
+
+        if self.resistances != Resistances::NONE {
+            let _ = world.insert_one(entity, self.resistances);
+        }
+
         // Add Tameable component for animals that can be tamed
         if self.tameable {
             let _ = world.insert_one(entity, Tameable);
         }
 
+        if !self.behavior.is_empty() {
+            let _ = world.insert_one(entity, Behavior::new(self.behavior, (x, y)));
+        }
+
         entity
     }
 }
 
+/// Behavior archetypes: tactic lists that enemy definitions pick from
+pub mod archetypes {
+    use crate::components::{EffectType, Tactic};
+    use crate::constants::*;
+
+    /// Keeps out of melee reach so it can keep shooting
+    pub const KITER: &[Tactic] = &[Tactic::KeepDistance { min_distance: KITER_MIN_DISTANCE }];
+
+    /// Lies still in tall grass until prey walks right up to it
+    pub const AMBUSHER: &[Tactic] = &[Tactic::Ambush { reveal_range: AMBUSH_REVEAL_RANGE }];
+
+    /// Walks a beat around its post
+    pub const GUARD: &[Tactic] = &[Tactic::Patrol { radius: GUARD_PATROL_RADIUS }];
+
+    /// Only closes in once the rest of the pack is on the hunt
+    pub const SWARMER: &[Tactic] = &[Tactic::WaitForAllies {
+        min_allies: SWARM_MIN_ALLIES,
+        radius: SWARM_ALLY_RADIUS,
+    }];
+
+    /// Heals and wards allies from behind the front line
+    pub const SUPPORT_CASTER: &[Tactic] = &[
+        Tactic::HealAllies {
+            range: SUPPORT_RANGE,
+            amount: SUPPORT_HEAL_AMOUNT,
+            cooldown: SUPPORT_HEAL_COOLDOWN,
+        },
+        Tactic::BuffAllies {
+            range: SUPPORT_RANGE,
+            effect: EffectType::Protected,
+            duration: SUPPORT_WARD_DURATION,
+            cooldown: SUPPORT_WARD_COOLDOWN,
+        },
+        Tactic::KeepDistance { min_distance: KITER_MIN_DISTANCE },
+    ];
+}
+
 /// Predefined enemy types
 pub mod enemies {
     use super::*;
     use crate::constants::*;
 
+    /// Skeletons shrug off arrows but burn easily
+    const SKELETON_RESISTANCES: Resistances = Resistances {
+        fire: SKELETON_FIRE_MULT,
+        projectile: SKELETON_PROJECTILE_MULT,
+        ..Resistances::NONE
+    };
+
     pub const SKELETON: EnemyDef = EnemyDef {
         name: "Skeleton",
         sprite: tile_ids::SKELETON,
@@ -140,6 +216,15 @@ pub mod enemies {
         agility: SKELETON_AGILITY,
         ranged: None,
         tameable: false,
+        armor: &[
+            (ItemType::Helmet, SKELETON_HELMET_CHANCE),
+            (ItemType::Shield, SKELETON_SHIELD_CHANCE),
+            (ItemType::ChainMail, SKELETON_CHAIN_MAIL_CHANCE),
+        ],
+        resistances: SKELETON_RESISTANCES,
+        behavior: archetypes::GUARD,
+        habitat: None,
+        faction: Faction::Undead,
     };
 
     pub const RAT: EnemyDef = EnemyDef {
@@ -156,6 +241,14 @@ pub mod enemies {
         agility: RAT_AGILITY,
         ranged: None,
         tameable: true, // Rats are animals and can be tamed by Druids
+        armor: &[],
+        resistances: Resistances {
+            fire: RAT_FIRE_MULT,
+            ..Resistances::NONE
+        },
+        behavior: archetypes::SWARMER,
+        habitat: None,
+        faction: Faction::Vermin,
     };
 
     pub const SKELETON_ARCHER: EnemyDef = EnemyDef {
@@ -176,7 +269,86 @@ pub mod enemies {
             damage: SKELETON_ARCHER_BOW_DAMAGE,
         }),
         tameable: false,
+        armor: &[(ItemType::LeatherArmor, SKELETON_ARCHER_LEATHER_ARMOR_CHANCE)],
+        resistances: SKELETON_RESISTANCES,
+        behavior: archetypes::KITER,
+        habitat: None,
+        faction: Faction::Undead,
+    };
+
+    pub const GIANT_SPIDER: EnemyDef = EnemyDef {
+        name: "Giant Spider",
+        sprite: tile_ids::SPIDER,
+        overlay_sprite: None,
+        health: GIANT_SPIDER_HEALTH,
+        max_energy: GIANT_SPIDER_MAX_ENERGY,
+        speed: GIANT_SPIDER_SPEED,
+        sight_radius: GIANT_SPIDER_SIGHT_RADIUS,
+        damage: GIANT_SPIDER_DAMAGE,
+        strength: GIANT_SPIDER_STRENGTH,
+        intelligence: GIANT_SPIDER_INTELLIGENCE,
+        agility: GIANT_SPIDER_AGILITY,
+        ranged: None,
+        tameable: true,
+        armor: &[],
+        resistances: Resistances {
+            fire: GIANT_SPIDER_FIRE_MULT,
+            ..Resistances::NONE
+        },
+        behavior: archetypes::AMBUSHER,
+        habitat: Some(TileType::TallGrass),
+        faction: Faction::Vermin,
     };
+
+    pub const GOBLIN_SHAMAN: EnemyDef = EnemyDef {
+        name: "Goblin Shaman",
+        sprite: tile_ids::GOBLIN,
+        overlay_sprite: None,
+        health: GOBLIN_SHAMAN_HEALTH,
+        max_energy: GOBLIN_SHAMAN_MAX_ENERGY,
+        speed: GOBLIN_SHAMAN_SPEED,
+        sight_radius: GOBLIN_SHAMAN_SIGHT_RADIUS,
+        damage: GOBLIN_SHAMAN_DAMAGE,
+        strength: GOBLIN_SHAMAN_STRENGTH,
+        intelligence: GOBLIN_SHAMAN_INTELLIGENCE,
+        agility: GOBLIN_SHAMAN_AGILITY,
+        ranged: None,
+        tameable: false,
+        armor: &[],
+        resistances: Resistances::NONE,
+        behavior: archetypes::SUPPORT_CASTER,
+        habitat: None,
+        faction: Faction::Goblins,
+    };
+
+    pub const GIANT_BAT: EnemyDef = EnemyDef {
+        name: "Giant Bat",
+        sprite: tile_ids::BAT,
+        overlay_sprite: None,
+        health: GIANT_BAT_HEALTH,
+        max_energy: GIANT_BAT_MAX_ENERGY,
+        speed: GIANT_BAT_SPEED,
+        sight_radius: GIANT_BAT_SIGHT_RADIUS,
+        damage: GIANT_BAT_DAMAGE,
+        strength: GIANT_BAT_STRENGTH,
+        intelligence: GIANT_BAT_INTELLIGENCE,
+        agility: GIANT_BAT_AGILITY,
+        ranged: None,
+        tameable: true,
+        armor: &[],
+        resistances: Resistances::NONE,
+        behavior: &[],
+        habitat: None,
+        faction: Faction::Wildlife,
+    };
+
+    /// Every enemy type
+    pub const ALL: [&EnemyDef; 6] = [&RAT, &GIANT_BAT, &GIANT_SPIDER, &SKELETON, &SKELETON_ARCHER, &GOBLIN_SHAMAN];
+
+    /// The enemy type with this display name (enemies carry it as their `Name`)
+    pub fn by_name(name: &str) -> Option<&'static EnemyDef> {
+        ALL.into_iter().find(|def| def.name == name)
+    }
 }
 
 /// Spawn configuration for a dungeon level
@@ -193,7 +365,10 @@ pub struct SpawnEntry {
 impl SpawnConfig {
     /// Create a default spawn config for the first dungeon level
     pub fn level_1() -> Self {
-        use crate::constants::{RAT_SPAWN_COUNT, SKELETON_ARCHER_SPAWN_COUNT, SKELETON_SPAWN_COUNT};
+        use crate::constants::{
+            GIANT_BAT_SPAWN_COUNT, GIANT_SPIDER_SPAWN_COUNT, GOBLIN_SHAMAN_SPAWN_COUNT, RAT_SPAWN_COUNT, SKELETON_ARCHER_SPAWN_COUNT,
+            SKELETON_SPAWN_COUNT,
+        };
         Self {
             entries: vec![
                 SpawnEntry {
@@ -208,47 +383,103 @@ impl SpawnConfig {
                     enemy: enemies::SKELETON_ARCHER.clone(),
                     count: SKELETON_ARCHER_SPAWN_COUNT,
                 },
+                SpawnEntry {
+                    enemy: enemies::GIANT_SPIDER.clone(),
+                    count: GIANT_SPIDER_SPAWN_COUNT,
+                },
+                SpawnEntry {
+                    enemy: enemies::GOBLIN_SHAMAN.clone(),
+                    count: GOBLIN_SHAMAN_SPAWN_COUNT,
+                },
+                SpawnEntry {
+                    enemy: enemies::GIANT_BAT.clone(),
+                    count: GIANT_BAT_SPAWN_COUNT,
+                },
             ],
         }
     }
 
+    /// Scale enemy counts by floor area relative to the default floor size,
+    /// so larger floors keep roughly the same enemy density.
+    pub fn scaled_to_floor(mut self, width: usize, height: usize) -> Self {
+        use crate::constants::{DUNGEON_DEFAULT_HEIGHT, DUNGEON_DEFAULT_WIDTH};
+        let scale = (width * height) as f32 / (DUNGEON_DEFAULT_WIDTH * DUNGEON_DEFAULT_HEIGHT) as f32;
+        for entry in &mut self.entries {
+            entry.count = (entry.count as f32 * scale).round() as usize;
+        }
+        self
+    }
+
     /// Spawn all enemies according to this config
     /// Returns the number of enemies spawned
     ///
     /// - `excluded_positions`: Individual tiles to exclude (e.g., player spawn)
     /// - `excluded_room`: Optional room rectangle to exclude entirely (e.g., starting room)
+    ///
+    /// Enemies with a habitat are placed on matching tiles while any are left.
+    /// Some enemies start out asleep or unaware.
     pub fn spawn_all(
         &self,
         world: &mut World,
-        walkable_tiles: &[(i32, i32)],
+        grid: &Grid,
         excluded_positions: &[(i32, i32)],
         excluded_room: Option<&crate::dungeon_gen::Rect>,
         rng: &mut impl rand::Rng,
     ) -> usize {
         let mut spawned = 0;
-        let mut used_positions: Vec<(i32, i32)> = excluded_positions.to_vec();
 
         // Helper to check if a position is in the excluded room
         let is_in_excluded_room = |x: i32, y: i32| -> bool {
             excluded_room.map(|r| r.contains(x, y)).unwrap_or(false)
         };
 
+        // Bucket the candidate tiles by tile type once; each spawn removes its tile in O(1)
+        let mut buckets: Vec<(TileType, Vec<(i32, i32)>)> = Vec::new();
+        for &(x, y) in &grid.walkable_positions {
+            if excluded_positions.contains(&(x, y)) || is_in_excluded_room(x, y) {
+                continue;
+            }
+            let Some(tile_type) = grid.get(x, y).map(|t| t.tile_type) else { continue };
+            match buckets.iter_mut().find(|(t, _)| *t == tile_type) {
+                Some((_, positions)) => positions.push((x, y)),
+                None => buckets.push((tile_type, vec![(x, y)])),
+            }
+        }
+        let mut remaining: usize = buckets.iter().map(|(_, positions)| positions.len()).sum();
+
         // Spawn all enemies using the unified template system
         for entry in &self.entries {
             for _ in 0..entry.count {
-                // Find a valid spawn position (not in used positions and not in excluded room)
-                let available: Vec<_> = walkable_tiles
-                    .iter()
-                    .filter(|&&(x, y)| !used_positions.contains(&(x, y)) && !is_in_excluded_room(x, y))
-                    .collect();
-
-                if available.is_empty() {
+                if remaining == 0 {
                     break;
                 }
 
-                let &(x, y) = available[rng.gen_range(0..available.len())];
-                entry.enemy.spawn(world, x, y);
-                used_positions.push((x, y));
+                let habitat = entry
+                    .enemy
+                    .habitat
+                    .and_then(|tile_type| buckets.iter().position(|(t, p)| *t == tile_type && !p.is_empty()));
+                let bucket = habitat.unwrap_or_else(|| {
+                    // Uniform over every remaining tile, whatever its type
+                    let mut n = rng.gen_range(0..remaining);
+                    buckets
+                        .iter()
+                        .position(|(_, positions)| {
+                            if n < positions.len() {
+                                return true;
+                            }
+                            n -= positions.len();
+                            false
+                        })
+                        .expect("remaining counts every bucketed tile")
+                });
+
+                let positions = &mut buckets[bucket].1;
+                let (x, y) = positions.swap_remove(rng.gen_range(0..positions.len()));
+                remaining -= 1;
+                let enemy = entry.enemy.spawn(world, x, y, rng);
+                if let Ok(mut ai) = world.get::<&mut ChaseAI>(enemy) {
+                    ai.awareness = crate::systems::perception::roll_spawn_awareness(rng);
+                }
                 spawned += 1;
             }
         }
@@ -422,6 +653,7 @@ pub mod vendors {
                 (ItemType::Bread, 2),
                 (ItemType::ScrollOfSpeed, 1),
                 (ItemType::ScrollOfProtection, 1),
+                (ItemType::LeatherArmor, 1),
                 (ItemType::Arrow, 10),
             ],
             2..=3 => vec![
@@ -430,6 +662,9 @@ pub mod vendors {
                 (ItemType::ScrollOfInvisibility, 1),
                 (ItemType::ScrollOfBlink, 1),
                 (ItemType::Dagger, 1),
+                (ItemType::Helmet, 1),
+                (ItemType::Shield, 1),
+                (ItemType::RingOfAgility, 1),
                 (ItemType::Arrow, 15),
             ],
             _ => vec![
@@ -438,6 +673,9 @@ pub mod vendors {
                 (ItemType::ScrollOfFireball, 1),
                 (ItemType::ScrollOfFear, 1),
                 (ItemType::Sword, 1),
+                (ItemType::ChainMail, 1),
+                (ItemType::RingOfStrength, 1),
+                (ItemType::AmuletOfWisdom, 1),
                 (ItemType::Arrow, 20),
             ],
         }
@@ -479,3 +717,38 @@ pub fn spawn_brazier(world: &mut World, x: i32, y: i32) -> hecs::Entity {
         CausesBurning,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_all_puts_habitat_enemies_on_their_tiles() {
+        let mut grid = Grid::filled(10, 10, TileType::Floor);
+        let grass = [(1, 1), (5, 5), (8, 2)];
+        for &(x, y) in &grass {
+            grid.get_mut(x, y).unwrap().tile_type = TileType::TallGrass;
+        }
+        grid.walkable_positions = (0..100).map(|i| (i % 10, i / 10)).collect();
+        let config = SpawnConfig {
+            entries: vec![
+                SpawnEntry { enemy: enemies::GIANT_SPIDER.clone(), count: 3 },
+                SpawnEntry { enemy: enemies::RAT.clone(), count: 20 },
+            ],
+        };
+
+        let mut world = World::new();
+        let spawned = config.spawn_all(&mut world, &grid, &[(0, 0)], None, &mut rand::thread_rng());
+
+        assert_eq!(spawned, 23);
+        let mut on_grass: Vec<(i32, i32)> = world
+            .query::<(&Position, &Name)>()
+            .iter()
+            .filter(|(_, (_, name))| name.0 == "Giant Spider")
+            .map(|(_, (pos, _))| (pos.x, pos.y))
+            .collect();
+        on_grass.sort();
+        assert_eq!(on_grass, vec![(1, 1), (5, 5), (8, 2)]);
+        assert!(world.query::<&Position>().iter().all(|(_, pos)| (pos.x, pos.y) != (0, 0)));
+    }
+}
@@ -53,6 +53,9 @@ pub fn calculate_action_duration(action_type: &ActionType, speed: f32) -> f32 {
         ActionType::PlaceSnareTrap { .. } => SNARE_TRAP_DURATION, // Ranger trap
         ActionType::ShootCripplingShot { .. } => ACTION_SHOOT_DURATION, // Same as bow shot
         ActionType::Recover => ACTION_RECOVER_DURATION, // Post-shot recovery
+        ActionType::HealAlly { .. } => ACTION_SHOOT_DURATION, // Spellcasting takes aim like a shot
+        ActionType::BuffAlly { .. } => ACTION_SHOOT_DURATION,
+        ActionType::CommandCompanions { .. } => COMPANION_COMMAND_DURATION, // A quick shout or gesture
     };
 
     // Speed modifies duration: higher speed = shorter duration
@@ -6,9 +6,9 @@
 use hecs::{Entity, World};
 
 use crate::components::{
-    Attackable, BlocksMovement, ChaseAI, ClassAbility, CompanionAI, Container, ContainerType, Door, EffectType, Equipment,
+    ArmorSlot, Attackable, BlocksMovement, ChaseAI, ClassAbility, CompanionAI, CompanionOrder, Container, ContainerType, DamageType, Door, EffectType, Equipment,
     EquippedWeapon, Health, Inventory, ItemType, LifeDrainInProgress, LungeAnimation, PlacedTrap, Player, Position, Projectile,
-    ProjectileMarker, RangedCooldown, SecondaryAbility, Sprite, Stats, StatusEffects, TamedBy, TamingInProgress, TrapType, VisualPosition, Weapon, RangedWeapon,
+    ProjectileMarker, RangedCooldown, SecondaryAbility, Sprite, StatusEffects, TamedBy, TamingInProgress, TrapType, VisualPosition, Weapon, RangedWeapon,
 };
 use crate::constants::*;
 use crate::events::{EventQueue, GameEvent, StairDirection};
@@ -17,6 +17,7 @@ use crate::pathfinding::{BresenhamLineIter, step_distance};
 use crate::queries;
 use crate::tile::tile_ids;
 
+use super::combat::{agility_of, effective_stats, final_damage, melee_hit_chance};
 use super::effects;
 
 /// Result of applying an action's effects
@@ -188,6 +189,7 @@ fn check_fire_trap_trigger(
     }
 
     // Trap triggered! Apply burst damage and burning effect
+    let burst_damage = final_damage(world, victim, burst_damage, DamageType::Fire, false);
     if let Ok(mut health) = world.get::<&mut Health>(victim) {
         health.current -= burst_damage;
     }
@@ -205,6 +207,7 @@ fn check_fire_trap_trigger(
     // Emit events
     events.push(GameEvent::FireTrapTriggered {
         trap: trap_entity,
+        owner: trap_owner,
         victim,
         position: (target_x, target_y),
     });
@@ -287,16 +290,34 @@ pub fn apply_attack(
         None => return ActionResult::Invalid,
     };
 
+    let mut rng = rand::thread_rng();
+
+    // An enemy that hasn't noticed anyone can't dodge
+    let sneak_attack = crate::systems::perception::is_unaware(world, target);
+
+    // Roll to hit: attacker accuracy vs target evasion
+    let hit_chance = melee_hit_chance(agility_of(world, attacker), agility_of(world, target));
+    if !sneak_attack && rng.gen::<f32>() >= hit_chance {
+        // A miss still draws the target's attention
+        crate::systems::ai::generate_threat(world, target, attacker, THREAT_PER_DAMAGE);
+        crate::systems::ai::generate_companion_threat(world, target, attacker, THREAT_PER_DAMAGE);
+
+        let _ = world.insert_one(attacker, LungeAnimation::new(target_pos.0 + 0.5, target_pos.1 + 0.5));
+        events.push(GameEvent::AttackMissed {
+            attacker,
+            target,
+            target_pos: (target_pos.0 + 0.5, target_pos.1 + 0.5),
+            ranged: false,
+        });
+        return ActionResult::Completed;
+    }
+
     // Check for status effects
     let has_strength_boost = queries::has_status_effect(world, attacker, EffectType::Strengthened);
-    let has_protection = queries::has_status_effect(world, target, EffectType::Protected)
-        || queries::has_status_effect(world, target, EffectType::Barkskin);
-    let is_invulnerable = queries::has_status_effect(world, target, EffectType::Invulnerable);
 
     // Calculate damage
     let base_damage = {
-        let strength = world
-            .get::<&Stats>(attacker)
+        let strength = effective_stats(world, attacker)
             .map(|s| s.strength)
             .unwrap_or(10);
         let weapon_damage = world
@@ -309,7 +330,6 @@ pub fn apply_attack(
     };
 
     // Apply damage variance and crit
-    let mut rng = rand::thread_rng();
     let damage_mult = rng.gen_range(COMBAT_DAMAGE_MIN_MULT..=COMBAT_DAMAGE_MAX_MULT);
     let is_crit = rng.gen::<f32>() < COMBAT_CRIT_CHANCE;
     let mut damage = (base_damage as f32 * damage_mult) as i32;
@@ -322,17 +342,13 @@ pub fn apply_attack(
         damage = (damage as f32 * STRENGTH_DAMAGE_MULTIPLIER) as i32;
     }
 
-    // Apply Protected reduction
-    if has_protection {
-        damage = (damage as f32 * PROTECTION_DAMAGE_REDUCTION) as i32;
+    if sneak_attack {
+        damage = (damage as f32 * SNEAK_ATTACK_MULTIPLIER) as i32;
+        events.push(GameEvent::SneakAttack { attacker, target });
     }
 
-    // Invulnerable negates all damage
-    if is_invulnerable {
-        damage = 0;
-    } else {
-        damage = damage.max(1);
-    }
+    // Protection, resistances and armor
+    let damage = final_damage(world, target, damage.max(1), DamageType::Physical, false);
 
     // Apply damage to target
     if let Ok(mut health) = world.get::<&mut Health>(target) {
@@ -371,6 +387,8 @@ pub fn apply_attack(
         target,
         target_pos: (target_pos.0 + 0.5, target_pos.1 + 0.5),
         damage,
+        damage_type: DamageType::Physical,
+        crit: is_crit,
     });
 
     ActionResult::Completed
@@ -675,7 +693,7 @@ pub fn apply_shoot_bow(
     };
 
     // Calculate damage with stats
-    let agility = world.get::<&Stats>(shooter).map(|s| s.agility).unwrap_or(10);
+    let agility = effective_stats(world, shooter).map(|s| s.agility).unwrap_or(10);
     let base_calc_damage = base_damage + (agility - 10) / 2;
 
     // Apply range band modifier (only for player)
@@ -727,6 +745,7 @@ pub fn apply_shoot_bow(
             potion_type: None,
             on_hit_effect: None,
             hit_enemy: false,
+            shooter_agility: Some(agility),
         },
         ProjectileMarker,
     ));
@@ -865,6 +884,7 @@ pub fn apply_throw_potion(
             potion_type: Some(potion_type),
             on_hit_effect: None,
             hit_enemy: false,
+            shooter_agility: None,
         },
         ProjectileMarker,
     ));
@@ -1022,19 +1042,22 @@ pub fn apply_fireball(
 
     // Apply damage to all
     for (entity, x, y) in damaged {
+        let damage = final_damage(world, entity, FIREBALL_DAMAGE, DamageType::Fire, false);
         if let Ok(mut health) = world.get::<&mut Health>(entity) {
-            health.current -= FIREBALL_DAMAGE;
+            health.current -= damage;
         }
         // Interrupt life drain if entity was channeling
         interrupt_life_drain_on_damage(world, entity, events);
         // Generate threat on fireball targets
-        crate::systems::ai::generate_threat(world, entity, caster, FIREBALL_DAMAGE as f32 * THREAT_PER_DAMAGE);
-        crate::systems::ai::generate_companion_threat(world, entity, caster, FIREBALL_DAMAGE as f32 * THREAT_PER_DAMAGE);
+        crate::systems::ai::generate_threat(world, entity, caster, damage as f32 * THREAT_PER_DAMAGE);
+        crate::systems::ai::generate_companion_threat(world, entity, caster, damage as f32 * THREAT_PER_DAMAGE);
         events.push(GameEvent::AttackHit {
             attacker: caster,
             target: entity,
             target_pos: (x as f32 + 0.5, y as f32 + 0.5),
-            damage: FIREBALL_DAMAGE,
+            damage,
+            damage_type: DamageType::Fire,
+            crit: false,
         });
     }
 
@@ -1134,6 +1157,67 @@ pub fn apply_unequip_weapon(
     ActionResult::Completed
 }
 
+/// Apply equip armor action - wears an armor piece or accessory from inventory,
+/// moving whatever was worn in that slot back to inventory
+pub fn apply_equip_armor(
+    world: &mut World,
+    entity: Entity,
+    item_index: usize,
+) -> ActionResult {
+    // Get the item type from inventory
+    let item_type = {
+        let Ok(inventory) = world.get::<&Inventory>(entity) else {
+            return ActionResult::Invalid;
+        };
+        if item_index >= inventory.items.len() {
+            return ActionResult::Invalid;
+        }
+        inventory.items[item_index]
+    };
+
+    let Some(armor) = crate::systems::item_defs::get_armor_def(item_type) else {
+        return ActionResult::Invalid; // Not wearable
+    };
+
+    if world.get::<&Equipment>(entity).is_err() {
+        return ActionResult::Invalid;
+    }
+
+    // Remove the item we're equipping from inventory
+    crate::systems::items::remove_item_from_inventory(world, entity, item_index);
+
+    // Wear it, returning the previous piece to inventory
+    let old_item = world
+        .get::<&mut Equipment>(entity)
+        .ok()
+        .and_then(|mut equipment| equipment.equip_armor(armor.slot, item_type));
+    if let Some(old_item) = old_item {
+        crate::systems::inventory::add_item_to_inventory(world, entity, old_item);
+    }
+
+    ActionResult::Completed
+}
+
+/// Apply unequip armor action - moves the piece worn in a slot to inventory
+pub fn apply_unequip_armor(
+    world: &mut World,
+    entity: Entity,
+    slot: ArmorSlot,
+) -> ActionResult {
+    let old_item = world
+        .get::<&mut Equipment>(entity)
+        .ok()
+        .and_then(|mut equipment| equipment.unequip_armor(slot));
+
+    match old_item {
+        Some(item) => {
+            crate::systems::inventory::add_item_to_inventory(world, entity, item);
+            ActionResult::Completed
+        }
+        None => ActionResult::Invalid, // Nothing to unequip
+    }
+}
+
 /// Apply drop item action - removes item from inventory and spawns on ground
 pub fn apply_drop_item(
     world: &mut World,
@@ -1222,6 +1306,40 @@ pub fn apply_drop_equipped_weapon(
     ActionResult::Completed
 }
 
+/// Apply drop equipped armor action - takes off the piece in a slot and drops it on the ground
+pub fn apply_drop_equipped_armor(
+    world: &mut World,
+    entity: Entity,
+    slot: ArmorSlot,
+    events: &mut EventQueue,
+) -> ActionResult {
+    // Get entity position
+    let (x, y) = match queries::get_entity_position(world, entity) {
+        Some(p) => p,
+        None => return ActionResult::Invalid,
+    };
+
+    let Some(item_type) = world
+        .get::<&mut Equipment>(entity)
+        .ok()
+        .and_then(|mut equipment| equipment.unequip_armor(slot))
+    else {
+        return ActionResult::Invalid; // Nothing to drop
+    };
+
+    // Spawn on ground
+    crate::systems::inventory::spawn_ground_item(world, x, y, item_type);
+
+    // Emit event
+    events.push(GameEvent::ItemDropped {
+        entity,
+        item: item_type,
+        position: (x, y),
+    });
+
+    ActionResult::Completed
+}
+
 // =============================================================================
 // CLASS ABILITY ACTIONS
 // =============================================================================
@@ -1246,8 +1364,7 @@ pub fn apply_cleave(
     });
 
     // Get attacker stats for damage calculation
-    let strength = world
-        .get::<&Stats>(attacker)
+    let strength = effective_stats(world, attacker)
         .map(|s| s.strength)
         .unwrap_or(10);
     let weapon_damage = world
@@ -1277,10 +1394,22 @@ pub fn apply_cleave(
 
     // Apply damage to each target
     let mut rng = rand::thread_rng();
+    let attacker_agility = agility_of(world, attacker);
     for (target, tx, ty) in &targets {
-        // Check for protection on target
-        let has_protection = queries::has_status_effect(world, *target, EffectType::Protected)
-            || queries::has_status_effect(world, *target, EffectType::Barkskin);
+        // Each target rolls to dodge, unless it hasn't noticed anyone
+        let hit_chance = melee_hit_chance(attacker_agility, agility_of(world, *target));
+        if !crate::systems::perception::is_unaware(world, *target) && rng.gen::<f32>() >= hit_chance {
+            // A miss still draws the target's attention
+            crate::systems::ai::generate_threat(world, *target, attacker, THREAT_PER_DAMAGE);
+            crate::systems::ai::generate_companion_threat(world, *target, attacker, THREAT_PER_DAMAGE);
+            events.push(GameEvent::AttackMissed {
+                attacker,
+                target: *target,
+                target_pos: (*tx as f32 + 0.5, *ty as f32 + 0.5),
+                ranged: false,
+            });
+            continue;
+        }
 
         // Apply damage variance and crit
         let damage_mult = rng.gen_range(COMBAT_DAMAGE_MIN_MULT..=COMBAT_DAMAGE_MAX_MULT);
@@ -1295,12 +1424,8 @@ pub fn apply_cleave(
             damage = (damage as f32 * STRENGTH_DAMAGE_MULTIPLIER) as i32;
         }
 
-        // Apply Protected reduction
-        if has_protection {
-            damage = (damage as f32 * PROTECTION_DAMAGE_REDUCTION) as i32;
-        }
-
-        damage = damage.max(1);
+        // Protection, resistances and armor
+        let damage = final_damage(world, *target, damage.max(1), DamageType::Physical, false);
 
         // Apply damage to target
         if let Ok(mut health) = world.get::<&mut Health>(*target) {
@@ -1321,6 +1446,8 @@ pub fn apply_cleave(
             target: *target,
             target_pos: (*tx as f32 + 0.5, *ty as f32 + 0.5),
             damage,
+            damage_type: DamageType::Physical,
+            crit: is_crit,
         });
     }
 
@@ -1458,10 +1585,92 @@ pub fn apply_activate_fear(
     ActionResult::Completed
 }
 
+/// Apply a support caster's heal to an ally
+pub fn apply_heal_ally(
+    world: &mut World,
+    caster: Entity,
+    target: Entity,
+    amount: i32,
+    events: &mut EventQueue,
+) -> ActionResult {
+    let Some((tx, ty)) = queries::get_entity_position(world, target) else {
+        return ActionResult::Invalid;
+    };
+    let healed = match world.get::<&mut Health>(target) {
+        Ok(mut health) if health.current > 0 => {
+            let before = health.current;
+            health.current = (health.current + amount).min(health.max);
+            health.current - before
+        }
+        // Target died or vanished while the spell was being cast
+        _ => return ActionResult::Invalid,
+    };
+
+    events.push(GameEvent::HealCast {
+        caster,
+        target,
+        target_pos: (tx as f32 + 0.5, ty as f32 + 0.5),
+        amount: healed,
+    });
+
+    ActionResult::Completed
+}
+
+/// Apply a support caster's buff to an ally
+pub fn apply_buff_ally(
+    world: &mut World,
+    caster: Entity,
+    target: Entity,
+    effect: EffectType,
+    duration: f32,
+    events: &mut EventQueue,
+) -> ActionResult {
+    let alive = world.get::<&Health>(target).map(|h| h.current > 0).unwrap_or(false);
+    if !alive {
+        return ActionResult::Invalid;
+    }
+
+    effects::add_effect_to_entity(world, target, effect, duration);
+    events.push(GameEvent::BuffCast { caster, target, effect_type: effect });
+
+    ActionResult::Completed
+}
+
+/// Give every companion of `owner` a new order.
+/// Stay holds each companion's own spot; Guard holds the chosen one.
+pub fn apply_command_companions(
+    world: &mut World,
+    owner: Entity,
+    companion: Option<Entity>,
+    order: CompanionOrder,
+    events: &mut EventQueue,
+) -> ActionResult {
+    let mut any = false;
+    for (id, (ai, pos)) in world.query_mut::<(&mut CompanionAI, &Position)>() {
+        if ai.owner != owner || companion.is_some_and(|companion| companion != id) {
+            continue;
+        }
+        ai.order = order;
+        ai.post = match order {
+            CompanionOrder::Stay => Some((pos.x, pos.y)),
+            CompanionOrder::Guard { x, y } => Some((x, y)),
+            _ => None,
+        };
+        any = true;
+    }
+    if !any {
+        return ActionResult::Invalid;
+    }
+
+    events.push(GameEvent::CompanionsOrdered { owner, companion, order });
+    ActionResult::Completed
+}
+
 /// Apply wait action - handles taming and life drain progress if applicable
 pub fn apply_wait(
     world: &mut World,
     entity: Entity,
+    spatial_cache: &mut crate::spatial_cache::SpatialCache,
     events: &mut EventQueue,
 ) -> ActionResult {
     // Check if entity is taming something
@@ -1483,7 +1692,7 @@ pub fn apply_wait(
 
                     if new_progress >= required {
                         // Taming complete!
-                        complete_taming(world, entity, target, events);
+                        complete_taming(world, entity, target, spatial_cache, events);
                     } else {
                         // Update progress
                         if let Ok(mut taming) = world.get::<&mut TamingInProgress>(entity) {
@@ -1561,13 +1770,18 @@ fn tick_life_drain(
     let new_timer = tick_timer + ACTION_WAIT_DURATION;
     if new_timer >= LIFE_DRAIN_TICK_INTERVAL {
         // Time to tick! Apply damage and healing
-        let intelligence = world
-            .get::<&Stats>(caster)
+        let intelligence = effective_stats(world, caster)
             .map(|s| s.intelligence)
             .unwrap_or(10);
 
         // Calculate damage: base + INT bonus
-        let damage = LIFE_DRAIN_DAMAGE_PER_TICK + (intelligence - 10) / 3;
+        let damage = final_damage(
+            world,
+            target,
+            LIFE_DRAIN_DAMAGE_PER_TICK + (intelligence - 10) / 3,
+            DamageType::Necrotic,
+            false,
+        );
 
         // Apply damage to target
         let target_died = if let Ok(mut health) = world.get::<&mut Health>(target) {
@@ -1657,6 +1871,7 @@ fn complete_taming(
     world: &mut World,
     tamer: Entity,
     target: Entity,
+    spatial_cache: &mut crate::spatial_cache::SpatialCache,
     events: &mut EventQueue,
 ) {
     use crate::components::{CompanionAI, TamedBy};
@@ -1666,14 +1881,11 @@ fn complete_taming(
 
     // Remove BlocksMovement so player can walk through their companion
     let _ = world.remove_one::<BlocksMovement>(target);
+    spatial_cache.clear_blocking_flags(target);
 
     // Add companion components
     let _ = world.insert_one(target, TamedBy { owner: tamer });
-    let _ = world.insert_one(target, CompanionAI {
-        owner: tamer,
-        follow_distance: 2,
-        threat_table: Vec::new(),
-    });
+    let _ = world.insert_one(target, CompanionAI::new(tamer, 2));
 
     // Remove taming state from player
     let _ = world.remove_one::<TamingInProgress>(tamer);
@@ -1955,7 +2167,7 @@ pub fn apply_shoot_crippling_shot(
     };
 
     // Calculate damage with stats (same as regular bow shot)
-    let agility = world.get::<&Stats>(shooter).map(|s| s.agility).unwrap_or(10);
+    let agility = effective_stats(world, shooter).map(|s| s.agility).unwrap_or(10);
     let damage = base_damage + (agility - 10) / 2;
 
     // Calculate arrow path using Bresenham
@@ -1986,6 +2198,7 @@ pub fn apply_shoot_crippling_shot(
             potion_type: None,
             on_hit_effect: Some((EffectType::Slowed, CRIPPLING_SHOT_SLOW_DURATION)),
             hit_enemy: false,
+            shooter_agility: Some(agility),
         },
         ProjectileMarker,
     ));
@@ -3,7 +3,9 @@
 //! This module handles AI state machines, perception, threat-based targeting,
 //! and action selection for non-player entities.
 //!
-//! Enemies use a WoW-style threat table to decide who to chase. Companions
+//! Enemies use a WoW-style threat table to decide who to chase. The faction
+//! relationship table (see `factions`) decides who goes in it: hostiles draw
+//! threat on sight, neutrals only once they've provoked the enemy. Companions
 //! operate in defensive mode — they only engage enemies that have attacked
 //! the player or that the player has attacked.
 
@@ -13,14 +15,19 @@ use hecs::{Entity, World};
 use rand::Rng;
 
 use crate::active_ai_tracker::ActiveAITracker;
-use crate::components::{ActionType, Actor, AIState, ChaseAI, CompanionAI, Door, EffectType, Equipment, Health, Position, RangedCooldown, TamedBy};
+use crate::components::{ActionType, Actor, AIState, Awareness, Behavior, ChaseAI, CompanionAI, CompanionOrder, Door, EffectType, Equipment, Health, Position, RangedCooldown, TamedBy};
 use crate::constants::*;
 use crate::events::{EventQueue, GameEvent};
 use crate::grid::Grid;
-use crate::pathfinding::{self, BresenhamLineIter};
+use crate::pathfinding::BresenhamLineIter;
 use crate::queries;
 use crate::spatial_cache::SpatialCache;
 use crate::systems::action_dispatch;
+use crate::systems::behaviors::{self, TacticContext};
+use crate::systems::factions;
+use crate::systems::hazards;
+use crate::systems::pack;
+use crate::systems::perception;
 use crate::time_system::{self, ActionScheduler, GameClock};
 
 // =============================================================================
@@ -32,6 +39,7 @@ use crate::time_system::{self, ActionScheduler, GameClock};
 pub fn generate_threat(world: &mut World, enemy: Entity, threat_source: Entity, amount: f32) {
     if let Ok(mut ai) = world.get::<&mut ChaseAI>(enemy) {
         ai.add_threat(threat_source, amount);
+        ai.awareness = Awareness::Alert;
     }
 }
 
@@ -209,8 +217,13 @@ fn determine_action(
     let is_feared = queries::has_status_effect(world, entity, EffectType::Feared);
     let is_rooted = queries::has_status_effect(world, entity, EffectType::Rooted);
 
-    // Confused: move randomly, ignore everything
+    // Confused: move randomly, lashing out at whoever is close - friend or foe
     if is_confused && !is_rooted {
+        if rng.gen::<f32>() < CONFUSED_LASH_OUT_CHANCE {
+            if let Some(target) = random_adjacent_creature(world, spatial_cache, entity, entity_pos, rng) {
+                return ActionType::Attack { target };
+            }
+        }
         let (dx, dy) = random_wander(grid, entity_pos, blocking_positions, rng);
         if dx == 0 && dy == 0 {
             return ActionType::Wait;
@@ -225,15 +238,29 @@ fn determine_action(
             Err(_) => return ActionType::Wait,
         };
 
+    // Tactics from the enemy's archetype (kiter, ambusher, guard, ...)
+    let mut behavior = world.get::<&Behavior>(entity).ok().map(|b| (*b).clone());
+    let sight_radius = behavior
+        .as_ref()
+        .map_or(sight_radius, |b| behaviors::sight_radius(world, entity, b, sight_radius));
+
     // Feared: flee from highest-threat source
     if is_feared && !is_rooted {
         let flee_from = world.get::<&ChaseAI>(entity).ok()
             .and_then(|ai| ai.highest_threat().map(|e| e.entity))
             .and_then(|e| queries::get_entity_position(world, e))
             .unwrap_or_else(|| queries::get_entity_position(world, player_entity).unwrap_or(entity_pos));
-        let (dx, dy) = flee_from_target(grid, entity_pos, flee_from, blocking_positions, rng);
+        // The shared flee map covers fleeing from the player; anyone else is fled the simple way
+        let profile = hazards::profile_for(world, entity);
+        let (dx, dy) = match spatial_cache.nav_maps().step_away(grid, entity_pos, flee_from, profile, blocking_positions) {
+            Some(step) => step.map(|(nx, ny)| (nx - entity_pos.0, ny - entity_pos.1)).unwrap_or((0, 0)),
+            None => flee_from_target(grid, entity_pos, flee_from, blocking_positions, rng),
+        };
         if dx == 0 && dy == 0 {
-            return ActionType::Wait;
+            // Cornered: panic and hit whatever is in the way
+            return random_adjacent_creature(world, spatial_cache, entity, entity_pos, rng)
+                .map(|target| ActionType::Attack { target })
+                .unwrap_or(ActionType::Wait);
         }
         return action_dispatch::determine_action_type(world, grid, entity, dx, dy);
     }
@@ -244,28 +271,49 @@ fn determine_action(
         .map(|e| e.has_bow())
         .unwrap_or(false);
 
-    // Build potential targets: player + all living companions
-    let mut potential_targets: Vec<(Entity, (i32, i32))> = Vec::new();
-    if let Some(ppos) = queries::get_entity_position(world, player_entity) {
-        potential_targets.push((player_entity, ppos));
-    }
-    for (comp_id, (comp_pos, _, health)) in world.query::<(&Position, &CompanionAI, &Health)>().iter() {
-        if health.current > 0 {
-            potential_targets.push((comp_id, (comp_pos.x, comp_pos.y)));
+    // Build potential targets: every living creature in range that it is hostile to,
+    // plus anyone who provoked it (neutral creatures only ever fight back)
+    let provoked: Vec<Entity> = world
+        .get::<&ChaseAI>(entity)
+        .map(|ai| ai.threat_table.iter().map(|e| e.entity).collect())
+        .unwrap_or_default();
+    let potential_targets: Vec<(Entity, (i32, i32))> = spatial_cache
+        .entities_within(entity_pos, sight_radius)
+        .filter(|&(id, _)| {
+            id != entity
+                && world.get::<&Health>(id).is_ok_and(|health| health.current > 0)
+                && (provoked.contains(&id) || factions::is_hostile(world, entity, id))
+        })
+        .collect();
+
+    let mut in_sight: Vec<(Entity, (i32, i32))> = potential_targets
+        .iter()
+        .copied()
+        .filter(|&(target_entity, target_pos)| {
+            can_see_target(world, grid, entity_pos, target_pos, sight_radius, Some(target_entity), spatial_cache)
+        })
+        .collect();
+
+    // Asleep or unaware: nobody counts as seen until they've been noticed
+    if !perception::check_awareness(world, grid, entity, entity_pos, sight_radius, &in_sight, rng) {
+        let asleep = world.get::<&ChaseAI>(entity).map(|ai| ai.awareness == Awareness::Asleep).unwrap_or(false);
+        if asleep {
+            return ActionType::Wait;
         }
+        in_sight.clear();
     }
 
-    // Visibility scan: check which targets we can see, add passive threat, update positions
+    // Visibility scan: add passive threat and update positions for targets we can see
     let mut visible_targets: HashSet<Entity> = HashSet::new();
-    for &(target_entity, target_pos) in &potential_targets {
-        let can_see = can_see_target(world, grid, entity_pos, target_pos, sight_radius, Some(target_entity), spatial_cache);
-        if can_see {
-            visible_targets.insert(target_entity);
-            // Passive visibility threat (enemies notice things walking around)
-            if let Ok(mut ai) = world.get::<&mut ChaseAI>(entity) {
+    for &(target_entity, target_pos) in &in_sight {
+        visible_targets.insert(target_entity);
+        // Passive visibility threat (enemies notice hostiles walking around)
+        let hostile = factions::is_hostile(world, entity, target_entity);
+        if let Ok(mut ai) = world.get::<&mut ChaseAI>(entity) {
+            if hostile {
                 ai.add_threat(target_entity, THREAT_PASSIVE_VISIBILITY);
-                ai.update_target_pos(target_entity, target_pos);
             }
+            ai.update_target_pos(target_entity, target_pos);
         }
     }
 
@@ -298,16 +346,20 @@ fn determine_action(
         })
     };
 
+    // Nobody to chase: go and check out the last noise heard, if any
+    let heard_noise = world.get::<&ChaseAI>(entity).ok().and_then(|ai| ai.heard_noise);
+
     // Update current_target and state machine
     let (chase_target_entity, chase_pos, target_visible) = match best_target {
         Some((e, pos, vis)) => (Some(e), Some(pos), vis),
-        None => (None, None, false),
+        None => (None, heard_noise, false),
     };
 
     // Get the last_known_pos for the current target (for state machine)
-    let last_known = chase_target_entity.and_then(|te| {
-        world.get::<&ChaseAI>(entity).ok().and_then(|ai| ai.last_known_pos_for(te))
-    });
+    let last_known = match chase_target_entity {
+        Some(te) => world.get::<&ChaseAI>(entity).ok().and_then(|ai| ai.last_known_pos_for(te)),
+        None => heard_noise,
+    };
 
     let (new_state, move_target, new_last_known) = update_state_machine(
         current_state,
@@ -323,12 +375,20 @@ fn determine_action(
             entity,
             new_state,
         });
+        // Just spotted someone: let the pack know
+        if let (AIState::Chasing, Some(target), Some(target_pos)) = (new_state, chase_target_entity, chase_pos) {
+            pack::alert_allies(world, spatial_cache, entity, entity_pos, target, target_pos, events);
+        }
     }
 
     // Update AI state
     if let Ok(mut ai) = world.get::<&mut ChaseAI>(entity) {
         ai.state = new_state;
         ai.current_target = chase_target_entity;
+        // The noise has been checked out (or a real target turned up)
+        if new_state != AIState::Investigating || chase_target_entity.is_some() {
+            ai.heard_noise = None;
+        }
         // Update last_known_pos for the current target
         if let Some(te) = chase_target_entity {
             if let Some(lk) = new_last_known {
@@ -350,6 +410,42 @@ fn determine_action(
         return ActionType::Wait;
     }
 
+    let pathfinding_blocked = ai_pathfinding_blocked(world, spatial_cache);
+    let ctx = TacticContext {
+        world,
+        grid,
+        entity,
+        pos: entity_pos,
+        blocked: blocking_positions,
+        path_blocked: &pathfinding_blocked,
+        spatial_cache,
+    };
+    let visible_target_pos = chase_pos.filter(|_| target_visible);
+
+    // Badly hurt: fall back to the pack
+    if let Some(action) = visible_target_pos.and_then(|target_pos| pack::retreat(&ctx, target_pos)) {
+        return action;
+    }
+
+    // Let tactics take over before the default chase/shoot/wander
+    if let Some(behavior) = behavior.as_mut() {
+        behaviors::note_state(behavior, new_state);
+        let mut action = behaviors::support_action(&ctx, behavior);
+        if action.is_none() {
+            action = visible_target_pos.and_then(|target_pos| behaviors::engaged_action(&ctx, behavior, target_pos));
+        }
+        if action.is_none() && move_target.is_none() {
+            action = behaviors::idle_action(&ctx, behavior, rng);
+        }
+
+        if let Ok(mut stored) = world.get::<&mut Behavior>(entity) {
+            *stored = behavior.clone();
+        }
+        if let Some(action) = action {
+            return action;
+        }
+    }
+
     // Check for ranged attack against best visible target in range
     if has_ranged_weapon && ranged_max > 0 {
         let ranged_ready = world
@@ -380,10 +476,24 @@ fn determine_action(
         }
     }
 
+    // Fight as a group: archers stay behind the melee line, melee spreads around the target
+    if let (Some(target), Some(target_pos)) = (chase_target_entity, visible_target_pos) {
+        let group_action = if has_ranged_weapon && ranged_max > 0 {
+            pack::hold_back(&ctx, target, target_pos, ranged_max)
+        } else {
+            pack::surround_step(&ctx, target, target_pos, sight_radius)
+        };
+        if let Some(action) = group_action {
+            return action;
+        }
+    }
+
     // Determine movement direction
     let (dx, dy) = if let Some(target_pos) = move_target {
-        let pathfinding_blocked = ai_pathfinding_blocked(world, spatial_cache);
-        pathfinding::next_step_toward(grid, entity_pos, target_pos, &pathfinding_blocked)
+        spatial_cache
+            .nav_maps()
+            .step_toward(grid, entity_pos, target_pos, hazards::profile_for(world, entity), &pathfinding_blocked)
+            .or_else(|| hazards::next_step_for(world, grid, entity, entity_pos, target_pos, &pathfinding_blocked))
             .map(|(nx, ny)| (nx - entity_pos.0, ny - entity_pos.1))
             .unwrap_or((0, 0))
     } else {
@@ -397,9 +507,9 @@ fn determine_action(
 
     let action = action_dispatch::determine_action_type(world, grid, entity, dx, dy);
 
-    // Don't attack fellow enemies - if we pathfound through one, just wait
+    // Don't attack bystanders - if we pathfound through one, just wait
     if let ActionType::Attack { target } = action {
-        if world.get::<&ChaseAI>(target).is_ok() {
+        if !provoked.contains(&target) && !factions::is_hostile(world, entity, target) {
             return ActionType::Wait;
         }
     }
@@ -407,13 +517,36 @@ fn determine_action(
     action
 }
 
+/// A random living creature next to `pos` - used when fear or confusion makes
+/// an enemy lash out without caring whose side the victim is on.
+fn random_adjacent_creature(
+    world: &World,
+    spatial_cache: &SpatialCache,
+    entity: Entity,
+    pos: (i32, i32),
+    rng: &mut impl Rng,
+) -> Option<Entity> {
+    let adjacent: Vec<Entity> = spatial_cache
+        .entities_within(pos, 1)
+        .filter(|&(id, other)| {
+            id != entity && other != pos && world.get::<&Health>(id).is_ok_and(|health| health.current > 0)
+        })
+        .map(|(id, _)| id)
+        .collect();
+    if adjacent.is_empty() {
+        return None;
+    }
+    Some(adjacent[rng.gen_range(0..adjacent.len())])
+}
+
 // =============================================================================
 // COMPANION AI (DEFENSIVE MODE)
 // =============================================================================
 
 /// Determine what action a companion (tamed animal) should take.
-/// Defensive mode: only engages enemies that have attacked the player, that the
-/// player has attacked, or that have attacked the companion directly.
+/// Defensive by default: only engages enemies that have attacked the player, that the
+/// player has attacked, or that have attacked the companion directly. The owner's
+/// current order narrows or overrides that (see `CompanionOrder`).
 fn determine_companion_action(
     world: &World,
     grid: &Grid,
@@ -437,10 +570,74 @@ fn determine_companion_action(
         Err(_) => return ActionType::Wait,
     };
 
+    let (mut order, post) = match world.get::<&CompanionAI>(entity) {
+        Ok(ai) => (ai.order, ai.post),
+        Err(_) => return ActionType::Wait,
+    };
     let blocking = ai_pathfinding_blocked(world, spatial_cache);
+    let dist_to_owner = behaviors::chebyshev(companion_pos, owner_pos);
+
+    // Orders that override defending
+    match order {
+        CompanionOrder::Attack { target } => {
+            let alive = world.get::<&Health>(target).map(|h| h.current > 0).unwrap_or(false);
+            if let (true, Some(target_pos)) = (alive, queries::get_entity_position(world, target)) {
+                set_companion_target(world, entity, Some(target));
+                return pursue_target(world, grid, entity, companion_pos, target, target_pos, &blocking);
+            }
+            // Target is gone - back to following
+            order = CompanionOrder::Follow;
+        }
+        CompanionOrder::Return if dist_to_owner > follow_distance => {
+            set_companion_target(world, entity, None);
+            return step_toward(world, grid, entity, companion_pos, owner_pos, &blocking);
+        }
+        CompanionOrder::Return => order = CompanionOrder::Follow,
+        _ => {}
+    }
+    if let Ok(mut ai) = world.get::<&mut CompanionAI>(entity) {
+        ai.order = order;
+    }
+
+    // Which enemies to take on: anywhere, only next to us, or only near the guarded spot
+    let engage_zone = match order {
+        CompanionOrder::Passive | CompanionOrder::Return => None,
+        CompanionOrder::Stay => Some((companion_pos, 1)),
+        CompanionOrder::Guard { x, y } => Some(((x, y), COMPANION_GUARD_RADIUS)),
+        CompanionOrder::Follow | CompanionOrder::Attack { .. } => Some((companion_pos, COMPANION_ENGAGE_RADIUS)),
+    };
+    let enemy = engage_zone
+        .and_then(|(center, radius)| companion_target(world, spatial_cache, entity, owner, companion_pos, center, radius));
+    set_companion_target(world, entity, enemy.map(|(e, _)| e));
+    if let Some((enemy, enemy_pos)) = enemy {
+        return pursue_target(world, grid, entity, companion_pos, enemy, enemy_pos, &blocking);
+    }
+
+    // Nothing to fight: hold the post, or keep up with the owner
+    match (order, post) {
+        (CompanionOrder::Stay | CompanionOrder::Guard { .. }, Some(post)) if post != companion_pos => {
+            step_toward(world, grid, entity, companion_pos, post, &blocking)
+        }
+        (CompanionOrder::Stay | CompanionOrder::Guard { .. }, _) => ActionType::Wait,
+        _ if dist_to_owner > follow_distance => step_toward(world, grid, entity, companion_pos, owner_pos, &blocking),
+        _ => ActionType::Wait,
+    }
+}
+
+/// Pick the enemy a companion should fight, if any lies within `radius` of `center`.
+/// Priority 1: highest threat in its own table (enemies that attacked it or its owner).
+/// Priority 2: the nearest enemy in combat with the owner.
+fn companion_target(
+    world: &World,
+    spatial_cache: &SpatialCache,
+    entity: Entity,
+    owner: Entity,
+    companion_pos: (i32, i32),
+    center: (i32, i32),
+    radius: i32,
+) -> Option<(Entity, (i32, i32))> {
+    let in_zone = |pos: (i32, i32)| behaviors::chebyshev(center, pos) <= radius;
 
-    // Priority 1: Fight highest-threat entry in our own threat table
-    // (enemies that attacked us or our owner)
     if let Ok(ai) = world.get::<&CompanionAI>(entity) {
         let mut entries: Vec<_> = ai.threat_table.iter().collect();
         entries.sort_by(|a, b| b.threat.partial_cmp(&a.threat).unwrap_or(std::cmp::Ordering::Equal));
@@ -463,47 +660,56 @@ fn determine_companion_action(
             }
 
             if let Some(target_pos) = queries::get_entity_position(world, entry.entity) {
-                return pursue_target(world, grid, entity, companion_pos, entry.entity, target_pos, &blocking);
+                if in_zone(target_pos) {
+                    return Some((entry.entity, target_pos));
+                }
             }
         }
     }
 
-    // Priority 2: Assist with enemies that have the player in their threat table
-    // (enemies currently in combat with the player)
     let mut best_enemy: Option<(Entity, i32, (i32, i32))> = None;
-    for (enemy_id, (enemy_pos, ai, health)) in world.query::<(&Position, &ChaseAI, &Health)>().iter() {
-        if health.current <= 0 {
+    for (enemy_id, enemy_pos) in spatial_cache.entities_within(center, radius) {
+        if !world.get::<&Health>(enemy_id).is_ok_and(|health| health.current > 0) {
             continue;
         }
         // Check if this enemy has threat on our owner
-        let has_owner_threat = ai.threat_table.iter().any(|e| e.entity == owner);
+        let has_owner_threat = world
+            .get::<&ChaseAI>(enemy_id)
+            .is_ok_and(|ai| ai.threat_table.iter().any(|e| e.entity == owner));
         if !has_owner_threat {
             continue;
         }
-        let dist = (enemy_pos.x - companion_pos.0).abs() + (enemy_pos.y - companion_pos.1).abs();
+        let dist = (enemy_pos.0 - companion_pos.0).abs() + (enemy_pos.1 - companion_pos.1).abs();
         if best_enemy.is_none() || dist < best_enemy.unwrap().1 {
-            best_enemy = Some((enemy_id, dist, (enemy_pos.x, enemy_pos.y)));
+            best_enemy = Some((enemy_id, dist, enemy_pos));
         }
     }
-    if let Some((enemy, _, enemy_pos)) = best_enemy {
-        return pursue_target(world, grid, entity, companion_pos, enemy, enemy_pos, &blocking);
-    }
+    best_enemy.map(|(enemy, _, pos)| (enemy, pos))
+}
 
-    // Priority 3: Follow owner if too far
-    let dist_to_owner = (companion_pos.0 - owner_pos.0)
-        .abs()
-        .max((companion_pos.1 - owner_pos.1).abs());
+/// Remember who a companion is fighting (shown in the party panel)
+fn set_companion_target(world: &World, entity: Entity, target: Option<Entity>) {
+    if let Ok(mut ai) = world.get::<&mut CompanionAI>(entity) {
+        ai.current_target = target;
+    }
+}
 
-    if dist_to_owner > follow_distance {
-        if let Some((nx, ny)) = pathfinding::next_step_toward(grid, companion_pos, owner_pos, &blocking) {
-            let dx = nx - companion_pos.0;
-            let dy = ny - companion_pos.1;
-            if dx != 0 || dy != 0 {
-                return action_dispatch::determine_action_type(world, grid, entity, dx, dy);
-            }
+/// Helper: take one step along a path toward a position.
+fn step_toward(
+    world: &World,
+    grid: &Grid,
+    entity: Entity,
+    entity_pos: (i32, i32),
+    goal: (i32, i32),
+    blocked: &HashSet<(i32, i32)>,
+) -> ActionType {
+    if let Some((nx, ny)) = hazards::next_step_for(world, grid, entity, entity_pos, goal, blocked) {
+        let dx = nx - entity_pos.0;
+        let dy = ny - entity_pos.1;
+        if dx != 0 || dy != 0 {
+            return action_dispatch::determine_action_type(world, grid, entity, dx, dy);
         }
     }
-
     ActionType::Wait
 }
 
@@ -526,7 +732,7 @@ fn pursue_target(
     }
 
     // Pathfind toward target
-    if let Some((nx, ny)) = pathfinding::next_step_toward(grid, entity_pos, target_pos, blocked) {
+    if let Some((nx, ny)) = hazards::next_step_for(world, grid, entity, entity_pos, target_pos, blocked) {
         let move_dx = nx - entity_pos.0;
         let move_dy = ny - entity_pos.1;
         if move_dx != 0 || move_dy != 0 {
@@ -542,7 +748,7 @@ fn pursue_target(
 // =============================================================================
 
 /// Check if there's a clear line of sight for a projectile (no blocking entities)
-fn has_clear_shot(from: (i32, i32), to: (i32, i32), blocking: &HashSet<(i32, i32)>) -> bool {
+pub fn has_clear_shot(from: (i32, i32), to: (i32, i32), blocking: &HashSet<(i32, i32)>) -> bool {
     for (x, y) in BresenhamLineIter::new(from.0, from.1, to.0, to.1) {
         if (x, y) == from || (x, y) == to {
             continue;
@@ -687,7 +893,7 @@ fn update_state_machine(
 // =============================================================================
 
 /// Build a blocked set for AI pathfinding that excludes traversable obstacles.
-fn ai_pathfinding_blocked(
+pub fn ai_pathfinding_blocked(
     world: &World,
     spatial_cache: &SpatialCache,
 ) -> HashSet<(i32, i32)> {
@@ -769,3 +975,141 @@ fn flee_from_target(
 
     random_wander(grid, pos, blocked, rng)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Attackable, BlocksMovement, Player};
+    use crate::spawning::enemies;
+    use crate::systems::actions;
+    use crate::tile::{Tile, TileType};
+
+    /// Owner at (2, 5), companion next to it, and an enemy fighting the owner further east
+    fn setup() -> (World, Grid, Entity, Entity, Entity) {
+        let grid = Grid::filled(16, 10, TileType::Floor);
+        let mut world = World::new();
+        let owner = world.spawn((Position::new(2, 5),));
+        let companion = world.spawn((Position::new(3, 5), Health::new(10), CompanionAI::new(owner, 2), TamedBy { owner }));
+        let mut enemy_ai = ChaseAI::new(8);
+        enemy_ai.add_threat(owner, 10.0);
+        let enemy = world.spawn((Position::new(9, 5), Health::new(10), enemy_ai));
+        (world, grid, owner, companion, enemy)
+    }
+
+    fn companion_action(world: &World, grid: &Grid, companion: Entity, owner: Entity) -> ActionType {
+        let cache = SpatialCache::rebuild_from_world(world);
+        determine_companion_action(world, grid, companion, owner, 2, &cache, &mut rand::thread_rng())
+    }
+
+    #[test]
+    fn test_companion_orders_limit_engagement() {
+        let (mut world, grid, owner, companion, enemy) = setup();
+        let mut events = EventQueue::new();
+
+        // Following: joins the owner's fight
+        assert!(matches!(companion_action(&world, &grid, companion, owner), ActionType::Move { dx: 1, dy: 0, .. }));
+        assert_eq!(world.get::<&CompanionAI>(companion).unwrap().current_target, Some(enemy));
+
+        // Staying: the enemy is too far away to bother with
+        actions::apply_command_companions(&mut world, owner, None, CompanionOrder::Stay, &mut events);
+        assert_eq!(world.get::<&CompanionAI>(companion).unwrap().post, Some((3, 5)));
+        assert!(matches!(companion_action(&world, &grid, companion, owner), ActionType::Wait));
+
+        // Passive: never starts a fight
+        actions::apply_command_companions(&mut world, owner, None, CompanionOrder::Passive, &mut events);
+        assert!(matches!(companion_action(&world, &grid, companion, owner), ActionType::Wait));
+        assert_eq!(world.get::<&CompanionAI>(companion).unwrap().current_target, None);
+
+        // Guarding a spot near the enemy: goes for it
+        actions::apply_command_companions(&mut world, owner, None, CompanionOrder::Guard { x: 8, y: 5 }, &mut events);
+        assert!(matches!(companion_action(&world, &grid, companion, owner), ActionType::Move { dx: 1, dy: 0, .. }));
+    }
+
+    #[test]
+    fn test_order_for_one_companion_leaves_the_others() {
+        let (mut world, _, owner, companion, _) = setup();
+        let other = world.spawn((Position::new(2, 6), Health::new(10), CompanionAI::new(owner, 2), TamedBy { owner }));
+        let mut events = EventQueue::new();
+
+        actions::apply_command_companions(&mut world, owner, Some(other), CompanionOrder::Stay, &mut events);
+        assert_eq!(world.get::<&CompanionAI>(other).unwrap().order, CompanionOrder::Stay);
+        assert_eq!(world.get::<&CompanionAI>(companion).unwrap().order, CompanionOrder::Follow);
+        assert!(matches!(
+            events.drain().next(),
+            Some(GameEvent::CompanionsOrdered { companion: Some(ordered), .. }) if ordered == other
+        ));
+    }
+
+    #[test]
+    fn test_attack_order_falls_back_to_follow_when_target_dies() {
+        let (mut world, grid, owner, companion, enemy) = setup();
+        let mut events = EventQueue::new();
+        // Far enough away from the owner that following means moving
+        world.get::<&mut Position>(companion).unwrap().x = 6;
+
+        actions::apply_command_companions(&mut world, owner, None, CompanionOrder::Attack { target: enemy }, &mut events);
+        assert!(matches!(companion_action(&world, &grid, companion, owner), ActionType::Move { dx: 1, dy: 0, .. }));
+
+        world.get::<&mut Health>(enemy).unwrap().current = 0;
+        assert!(matches!(companion_action(&world, &grid, companion, owner), ActionType::Move { dx: -1, dy: 0, .. }));
+        assert_eq!(world.get::<&CompanionAI>(companion).unwrap().order, CompanionOrder::Follow);
+    }
+
+    fn spawn_player(world: &mut World, x: i32, y: i32) -> Entity {
+        world.spawn((Player, Position::new(x, y), Health::new(50), Attackable, BlocksMovement))
+    }
+
+    fn enemy_action(world: &mut World, grid: &Grid, entity: Entity, player: Entity) -> ActionType {
+        let cache = SpatialCache::rebuild_from_world(world);
+        let mut events = EventQueue::new();
+        determine_action(world, grid, entity, player, &cache, &mut events, &mut rand::thread_rng())
+    }
+
+    #[test]
+    fn test_hostile_factions_fight_each_other() {
+        let grid = Grid::filled(20, 10, TileType::Floor);
+        let mut world = World::new();
+        // Player well out of the rat's sight
+        let player = spawn_player(&mut world, 18, 5);
+        let mut rng = rand::thread_rng();
+        let rat = enemies::RAT.spawn(&mut world, 5, 5, &mut rng);
+        let skeleton = enemies::SKELETON.spawn(&mut world, 6, 5, &mut rng);
+
+        assert!(matches!(enemy_action(&mut world, &grid, rat, player), ActionType::Attack { target } if target == skeleton));
+        assert_eq!(world.get::<&ChaseAI>(rat).unwrap().current_target, Some(skeleton));
+    }
+
+    #[test]
+    fn test_neutral_wildlife_ignores_player_until_provoked() {
+        let grid = Grid::filled(20, 10, TileType::Floor);
+        let mut world = World::new();
+        let player = spawn_player(&mut world, 4, 5);
+        let bat = enemies::GIANT_BAT.spawn(&mut world, 5, 5, &mut rand::thread_rng());
+
+        for _ in 0..10 {
+            let action = enemy_action(&mut world, &grid, bat, player);
+            assert!(!matches!(action, ActionType::Attack { .. }));
+            assert!(world.get::<&ChaseAI>(bat).unwrap().threat_table.is_empty());
+            // Wandering may have moved it in the world; put it back next to the player
+            *world.get::<&mut Position>(bat).unwrap() = Position::new(5, 5);
+        }
+
+        generate_threat(&mut world, bat, player, 5.0);
+        assert!(matches!(enemy_action(&mut world, &grid, bat, player), ActionType::Attack { target } if target == player));
+    }
+
+    #[test]
+    fn test_cornered_feared_enemy_lashes_out_at_ally() {
+        let mut grid = Grid::filled(4, 1, TileType::Floor);
+        grid.tiles[0] = Tile::new(TileType::Wall);
+        let mut world = World::new();
+        let player = spawn_player(&mut world, 3, 0);
+        let mut rng = rand::thread_rng();
+        let skeleton = enemies::SKELETON.spawn(&mut world, 1, 0, &mut rng);
+        let ally = enemies::SKELETON.spawn(&mut world, 2, 0, &mut rng);
+        crate::systems::effects::add_effect_to_entity(&mut world, skeleton, EffectType::Feared, 5.0);
+
+        // Boxed in between a wall and its friend: panics and swings at the friend
+        assert!(matches!(enemy_action(&mut world, &grid, skeleton, player), ActionType::Attack { target } if target == ally));
+    }
+}
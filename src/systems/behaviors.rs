@@ -0,0 +1,408 @@
+//! Reusable enemy behavior pieces.
+//!
+//! `ChaseAI` decides *who* an enemy is fighting; the tactics on its `Behavior`
+//! component decide *how*. Each tactic answers only the questions it cares
+//! about and passes on the rest:
+//! - perception: how far away targets are noticed (ambushers lurk)
+//! - support: heal or buff allies before doing anything else
+//! - engaged: how to fight a visible target (kite, wait for the pack)
+//! - idle: what to do with no target (patrol, hide)
+
+use std::collections::HashSet;
+
+use hecs::{Entity, World};
+use rand::Rng;
+
+use crate::components::{ActionType, AIState, Behavior, ChaseAI, Health, Tactic};
+use crate::constants::*;
+use crate::grid::Grid;
+use crate::queries;
+use crate::spatial_cache::SpatialCache;
+use crate::systems::action_dispatch;
+use crate::systems::factions;
+use crate::systems::hazards;
+use crate::tile::TileType;
+
+/// What a tactic can see when deciding
+pub struct TacticContext<'a> {
+    pub world: &'a World,
+    pub grid: &'a Grid,
+    pub entity: Entity,
+    pub pos: (i32, i32),
+    /// Tiles occupied by movement-blocking entities
+    pub blocked: &'a HashSet<(i32, i32)>,
+    /// Pathfinding obstacles (allies and closed doors excluded)
+    pub path_blocked: &'a HashSet<(i32, i32)>,
+    /// For finding who is nearby without scanning the whole world
+    pub spatial_cache: &'a SpatialCache,
+}
+
+// =============================================================================
+// HOOKS (called from ai::determine_action)
+// =============================================================================
+
+/// Sight radius after tactics: lurking ambushers only notice what walks right up to them
+pub fn sight_radius(world: &World, entity: Entity, behavior: &Behavior, base: i32) -> i32 {
+    let unhurt = world.get::<&Health>(entity).map(|h| h.current >= h.max).unwrap_or(true);
+    behavior.tactics.iter().fold(base, |radius, tactic| match tactic {
+        Tactic::Ambush { reveal_range } if !behavior.revealed && unhurt => radius.min(*reveal_range),
+        _ => radius,
+    })
+}
+
+/// Track state changes tactics care about
+pub fn note_state(behavior: &mut Behavior, state: AIState) {
+    match state {
+        AIState::Chasing => behavior.revealed = true,
+        AIState::Idle => behavior.revealed = false,
+        AIState::Investigating => {}
+    }
+}
+
+/// Heals and buffs, checked before anything else
+pub fn support_action(ctx: &TacticContext, behavior: &mut Behavior) -> Option<ActionType> {
+    if behavior.support_cooldown > 0.0 {
+        return None;
+    }
+    for tactic in behavior.tactics {
+        let action = match *tactic {
+            Tactic::HealAllies { range, amount, .. } => {
+                most_wounded_ally(ctx, range).map(|target| ActionType::HealAlly { target, amount })
+            }
+            Tactic::BuffAllies { range, effect, duration, .. } => {
+                unbuffed_ally(ctx, range, effect).map(|target| ActionType::BuffAlly { target, effect, duration })
+            }
+            _ => None,
+        };
+        if action.is_some() {
+            if let Tactic::HealAllies { cooldown, .. } | Tactic::BuffAllies { cooldown, .. } = *tactic {
+                behavior.support_cooldown = cooldown;
+            }
+            return action;
+        }
+    }
+    None
+}
+
+/// How to fight a target we can currently see
+pub fn engaged_action(ctx: &TacticContext, behavior: &Behavior, target_pos: (i32, i32)) -> Option<ActionType> {
+    behavior.tactics.iter().find_map(|tactic| match *tactic {
+        Tactic::KeepDistance { min_distance } => keep_distance(ctx, target_pos, min_distance),
+        Tactic::WaitForAllies { min_allies, radius } => wait_for_allies(ctx, target_pos, min_allies, radius),
+        _ => None,
+    })
+}
+
+/// What to do with no target (instead of wandering randomly)
+pub fn idle_action(ctx: &TacticContext, behavior: &mut Behavior, rng: &mut impl Rng) -> Option<ActionType> {
+    for tactic in behavior.tactics {
+        let action = match *tactic {
+            Tactic::Ambush { .. } => Some(hide(ctx)),
+            Tactic::Patrol { radius } => patrol(ctx, behavior, radius, rng),
+            _ => None,
+        };
+        if action.is_some() {
+            return action;
+        }
+    }
+    None
+}
+
+// =============================================================================
+// BEHAVIOR PIECES
+// =============================================================================
+
+/// Kiter: step back from a target that is too close. Cornered kiters fight.
+fn keep_distance(ctx: &TacticContext, target_pos: (i32, i32), min_distance: i32) -> Option<ActionType> {
+    if chebyshev(ctx.pos, target_pos) >= min_distance {
+        return None;
+    }
+    step_away(ctx, target_pos)
+}
+
+/// Swarmer: hold back until enough allies are chasing too, unless hurt or cornered
+fn wait_for_allies(ctx: &TacticContext, target_pos: (i32, i32), min_allies: usize, radius: i32) -> Option<ActionType> {
+    let distance = chebyshev(ctx.pos, target_pos);
+    let hurt = ctx.world.get::<&Health>(ctx.entity).map(|h| h.current < h.max).unwrap_or(false);
+    if hurt || distance <= 1 {
+        return None;
+    }
+
+    let allies = allies_within(ctx.world, ctx.spatial_cache, ctx.entity, ctx.pos, radius)
+        .filter(|&(id, _)| ctx.world.get::<&ChaseAI>(id).is_ok_and(|ai| ai.state == AIState::Chasing))
+        .count();
+    if allies >= min_allies {
+        return None;
+    }
+
+    // Keep just out of reach while the pack gathers
+    if distance < SWARM_HOLD_DISTANCE {
+        if let Some(action) = step_away(ctx, target_pos) {
+            return Some(action);
+        }
+    }
+    Some(ActionType::Wait)
+}
+
+/// Ambusher: lie still in tall grass, or creep to the nearest patch
+fn hide(ctx: &TacticContext) -> ActionType {
+    if is_cover(ctx.grid, ctx.pos) {
+        return ActionType::Wait;
+    }
+
+    let r = AMBUSH_HIDE_SEARCH_RADIUS;
+    let nearest_cover = (-r..=r)
+        .flat_map(|dy| (-r..=r).map(move |dx| (dx, dy)))
+        .map(|(dx, dy)| (ctx.pos.0 + dx, ctx.pos.1 + dy))
+        .filter(|&tile| is_cover(ctx.grid, tile) && !ctx.blocked.contains(&tile))
+        .min_by_key(|&tile| chebyshev(ctx.pos, tile));
+
+    nearest_cover
+        .and_then(|cover| hazards::next_step_for(ctx.world, ctx.grid, ctx.entity, ctx.pos, cover, ctx.path_blocked))
+        .map(|step| move_action(ctx, step))
+        .unwrap_or(ActionType::Wait)
+}
+
+/// Guard: walk a loop of waypoints around the spawn point
+fn patrol(ctx: &TacticContext, behavior: &mut Behavior, radius: i32, rng: &mut impl Rng) -> Option<ActionType> {
+    if behavior.patrol_route.is_empty() {
+        behavior.patrol_route = plan_patrol_route(ctx.grid, behavior.home, radius, rng);
+    }
+    if behavior.patrol_route.is_empty() {
+        return None;
+    }
+
+    let len = behavior.patrol_route.len();
+    if behavior.patrol_route[behavior.patrol_index % len] == ctx.pos {
+        behavior.patrol_index = (behavior.patrol_index + 1) % len;
+    }
+    let waypoint = behavior.patrol_route[behavior.patrol_index % len];
+
+    match hazards::next_step_for(ctx.world, ctx.grid, ctx.entity, ctx.pos, waypoint, ctx.path_blocked) {
+        Some(step) => Some(move_action(ctx, step)),
+        None => {
+            // Unreachable right now (door shut, someone in the way) - try the next one
+            behavior.patrol_index = (behavior.patrol_index + 1) % len;
+            Some(ActionType::Wait)
+        }
+    }
+}
+
+/// Support: the ally in range with the lowest health fraction, if badly hurt
+fn most_wounded_ally(ctx: &TacticContext, range: i32) -> Option<Entity> {
+    std::iter::once((ctx.entity, ctx.pos))
+        .chain(allies_within(ctx.world, ctx.spatial_cache, ctx.entity, ctx.pos, range))
+        .filter(|&(id, _)| ctx.world.get::<&ChaseAI>(id).is_ok())
+        .filter_map(|(id, _)| {
+            let health = ctx.world.get::<&Health>(id).ok()?;
+            let fraction = health.current as f32 / health.max as f32;
+            (fraction < SUPPORT_HEAL_THRESHOLD).then_some((id, fraction))
+        })
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(id, _)| id)
+}
+
+/// Support: the nearest ally in combat that lacks the effect
+fn unbuffed_ally(ctx: &TacticContext, range: i32, effect: crate::components::EffectType) -> Option<Entity> {
+    std::iter::once((ctx.entity, ctx.pos))
+        .chain(allies_within(ctx.world, ctx.spatial_cache, ctx.entity, ctx.pos, range))
+        .filter(|&(id, _)| {
+            ctx.world.get::<&ChaseAI>(id).is_ok_and(|ai| ai.state == AIState::Chasing)
+                && !queries::has_status_effect(ctx.world, id, effect)
+        })
+        .min_by_key(|&(_, pos)| chebyshev(ctx.pos, pos))
+        .map(|(id, _)| id)
+}
+
+// =============================================================================
+// HELPERS
+// =============================================================================
+
+pub fn chebyshev(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs().max((a.1 - b.1).abs())
+}
+
+/// Living allies of `entity` within `radius` tiles of `center`, looked up in the spatial cache
+pub fn allies_within<'a>(
+    world: &'a World,
+    spatial_cache: &'a SpatialCache,
+    entity: Entity,
+    center: (i32, i32),
+    radius: i32,
+) -> impl Iterator<Item = (Entity, (i32, i32))> + 'a {
+    spatial_cache.entities_within(center, radius).filter(move |&(id, _)| {
+        id != entity
+            && world.get::<&Health>(id).is_ok_and(|health| health.current > 0)
+            && factions::is_allied(world, entity, id)
+    })
+}
+
+fn is_cover(grid: &Grid, (x, y): (i32, i32)) -> bool {
+    grid.get(x, y).map(|t| t.tile_type == TileType::TallGrass).unwrap_or(false)
+}
+
+/// Turn a step into an action, refusing to attack allies that are in the way
+pub fn move_action(ctx: &TacticContext, step: (i32, i32)) -> ActionType {
+    let action = action_dispatch::determine_action_type(ctx.world, ctx.grid, ctx.entity, step.0 - ctx.pos.0, step.1 - ctx.pos.1);
+    match action {
+        ActionType::Attack { .. } => ActionType::Wait,
+        _ => action,
+    }
+}
+
+/// The free neighbouring tile that gains the most distance from `threat`
+pub fn step_away(ctx: &TacticContext, threat: (i32, i32)) -> Option<ActionType> {
+    let current = chebyshev(ctx.pos, threat);
+    let mut best: Option<((i32, i32), (i32, i32))> = None;
+    for dy in -1..=1 {
+        for dx in -1..=1 {
+            let tile = (ctx.pos.0 + dx, ctx.pos.1 + dy);
+            if (dx == 0 && dy == 0) || !ctx.grid.is_walkable(tile.0, tile.1) || ctx.blocked.contains(&tile) {
+                continue;
+            }
+            let score = (chebyshev(tile, threat), (tile.0 - threat.0).abs() + (tile.1 - threat.1).abs());
+            if score.0 > current && best.map(|(_, s)| score > s).unwrap_or(true) {
+                best = Some((tile, score));
+            }
+        }
+    }
+    best.map(|(tile, _)| move_action(ctx, tile))
+}
+
+/// Pick waypoints around `home` for a patrol loop, starting at home
+fn plan_patrol_route(grid: &Grid, home: (i32, i32), radius: i32, rng: &mut impl Rng) -> Vec<(i32, i32)> {
+    let mut route = vec![home];
+    for _ in 0..PATROL_WAYPOINT_ATTEMPTS {
+        if route.len() > PATROL_WAYPOINT_COUNT {
+            break;
+        }
+        let point = (
+            home.0 + rng.gen_range(-radius..=radius),
+            home.1 + rng.gen_range(-radius..=radius),
+        );
+        if grid.is_walkable(point.0, point.1) && !route.contains(&point) {
+            route.push(point);
+        }
+    }
+    route
+}
+
+/// Helpers shared by the tactic tests here and in `pack`
+#[cfg(test)]
+pub(crate) mod test_support {
+    use super::*;
+    use crate::components::{Faction, Position};
+
+    /// Context for `entity` where it stands, with the same tiles blocking moves and paths
+    pub fn ctx<'a>(
+        world: &'a World,
+        grid: &'a Grid,
+        entity: Entity,
+        blocked: &'a HashSet<(i32, i32)>,
+        spatial_cache: &'a SpatialCache,
+    ) -> TacticContext<'a> {
+        let pos = queries::get_entity_position(world, entity).unwrap();
+        TacticContext { world, grid, entity, pos, blocked, path_blocked: blocked, spatial_cache }
+    }
+
+    pub fn spawn_enemy(world: &mut World, x: i32, y: i32, state: AIState, faction: Faction) -> Entity {
+        let mut ai = ChaseAI::new(8);
+        ai.state = state;
+        world.spawn((Position::new(x, y), Health::new(20), ai, faction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::{ctx, spawn_enemy};
+    use super::*;
+    use crate::components::{EffectType, Faction};
+    use crate::tile::Tile;
+
+    #[test]
+    fn test_kiter_backs_away_from_close_target() {
+        let grid = Grid::filled(10, 10, TileType::Floor);
+        let mut world = World::new();
+        let archer = spawn_enemy(&mut world, 5, 5, AIState::Chasing, Faction::Goblins);
+        let blocked = HashSet::new();
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let behavior = Behavior::new(&[Tactic::KeepDistance { min_distance: 3 }], (5, 5));
+
+        let action = engaged_action(&ctx(&world, &grid, archer, &blocked, &cache), &behavior, (4, 5));
+        assert!(matches!(action, Some(ActionType::Move { dx: 1, .. })));
+
+        // Far enough away: leave it to the default chase/shoot logic
+        assert!(engaged_action(&ctx(&world, &grid, archer, &blocked, &cache), &behavior, (1, 5)).is_none());
+    }
+
+    #[test]
+    fn test_swarmer_waits_for_pack() {
+        let grid = Grid::filled(10, 10, TileType::Floor);
+        let mut world = World::new();
+        let rat = spawn_enemy(&mut world, 5, 5, AIState::Chasing, Faction::Goblins);
+        let blocked = HashSet::new();
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let behavior = Behavior::new(&[Tactic::WaitForAllies { min_allies: 1, radius: 4 }], (5, 5));
+
+        let alone = engaged_action(&ctx(&world, &grid, rat, &blocked, &cache), &behavior, (1, 5));
+        assert!(matches!(alone, Some(ActionType::Wait)));
+
+        spawn_enemy(&mut world, 6, 6, AIState::Chasing, Faction::Goblins);
+        let cache = SpatialCache::rebuild_from_world(&world);
+        assert!(engaged_action(&ctx(&world, &grid, rat, &blocked, &cache), &behavior, (1, 5)).is_none());
+    }
+
+    #[test]
+    fn test_ambusher_stays_in_grass_with_short_sight() {
+        let mut grid = Grid::filled(10, 10, TileType::Floor);
+        grid.tiles[5 * 10 + 5] = Tile::new(TileType::TallGrass);
+        let mut world = World::new();
+        let spider = spawn_enemy(&mut world, 5, 5, AIState::Idle, Faction::Goblins);
+        let blocked = HashSet::new();
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let mut behavior = Behavior::new(&[Tactic::Ambush { reveal_range: 2 }], (5, 5));
+
+        assert_eq!(sight_radius(&world, spider, &behavior, 8), 2);
+        let mut rng = rand::thread_rng();
+        let action = idle_action(&ctx(&world, &grid, spider, &blocked, &cache), &mut behavior, &mut rng);
+        assert!(matches!(action, Some(ActionType::Wait)));
+
+        note_state(&mut behavior, AIState::Chasing);
+        assert_eq!(sight_radius(&world, spider, &behavior, 8), 8);
+    }
+
+    #[test]
+    fn test_support_heals_most_wounded_then_cools_down() {
+        let grid = Grid::filled(10, 10, TileType::Floor);
+        let mut world = World::new();
+        let shaman = spawn_enemy(&mut world, 5, 5, AIState::Chasing, Faction::Goblins);
+        let scratched = spawn_enemy(&mut world, 6, 5, AIState::Chasing, Faction::Goblins);
+        let wounded = spawn_enemy(&mut world, 4, 5, AIState::Chasing, Faction::Goblins);
+        world.get::<&mut Health>(scratched).unwrap().current = 11;
+        world.get::<&mut Health>(wounded).unwrap().current = 3;
+        let blocked = HashSet::new();
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let mut behavior = Behavior::new(&[Tactic::HealAllies { range: 3, amount: 5, cooldown: 4.0 }], (5, 5));
+
+        let action = support_action(&ctx(&world, &grid, shaman, &blocked, &cache), &mut behavior);
+        assert!(matches!(action, Some(ActionType::HealAlly { target, amount: 5 }) if target == wounded));
+        assert!(support_action(&ctx(&world, &grid, shaman, &blocked, &cache), &mut behavior).is_none());
+    }
+
+    #[test]
+    fn test_support_buffs_allies_in_combat_only() {
+        let grid = Grid::filled(10, 10, TileType::Floor);
+        let mut world = World::new();
+        let shaman = spawn_enemy(&mut world, 5, 5, AIState::Idle, Faction::Goblins);
+        let blocked = HashSet::new();
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let tactics = &[Tactic::BuffAllies { range: 3, effect: EffectType::Protected, duration: 5.0, cooldown: 4.0 }];
+        let mut behavior = Behavior::new(tactics, (5, 5));
+        assert!(support_action(&ctx(&world, &grid, shaman, &blocked, &cache), &mut behavior).is_none());
+
+        let fighter = spawn_enemy(&mut world, 6, 5, AIState::Chasing, Faction::Goblins);
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let action = support_action(&ctx(&world, &grid, shaman, &blocked, &cache), &mut behavior);
+        assert!(matches!(action, Some(ActionType::BuffAlly { target, .. }) if target == fighter));
+    }
+}
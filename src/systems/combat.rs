@@ -1,12 +1,14 @@
 //! Combat system functions.
 
 use crate::components::{
-    Actor, Attackable, BlocksMovement, ChaseAI, CompanionAI, Container, Door, Equipment, Experience, Health,
-    ItemType, Position, Sprite, Stats, Weapon,
+    Actor, Attackable, BlocksMovement, ChaseAI, CompanionAI, Container, DamageType, Door, EffectType, Equipment,
+    Experience, Health, ItemType, Position, Resistances, Sprite, Stats, Weapon,
 };
 use crate::constants::*;
 use crate::events::{EventQueue, GameEvent};
+use crate::queries;
 use crate::systems::experience::{calculate_xp_value, grant_xp};
+use crate::systems::item_defs::get_armor_def;
 use crate::tile::tile_ids;
 use crate::time_system::ActionScheduler;
 use hecs::{Entity, World};
@@ -17,6 +19,88 @@ pub fn weapon_damage(weapon: &Weapon) -> i32 {
     weapon.base_damage + weapon.damage_bonus
 }
 
+/// Stats including bonuses from worn armor and accessories (None if the entity has no Stats)
+pub fn effective_stats(world: &World, entity: Entity) -> Option<Stats> {
+    let mut stats = *world.get::<&Stats>(entity).ok()?;
+    if let Ok(equipment) = world.get::<&Equipment>(entity) {
+        for def in equipment.worn_armor().filter_map(get_armor_def) {
+            stats.strength += def.strength;
+            stats.intelligence += def.intelligence;
+            stats.agility += def.agility;
+        }
+    }
+    Some(stats)
+}
+
+/// Total defense of all worn armor
+pub fn armor_defense(world: &World, entity: Entity) -> i32 {
+    world
+        .get::<&Equipment>(entity)
+        .map(|equipment| equipment.worn_armor().filter_map(get_armor_def).map(|def| def.defense).sum())
+        .unwrap_or(0)
+}
+
+/// Reduce a physical hit by the target's armor. Hits that land always deal at least 1 damage.
+pub fn reduce_by_armor(damage: i32, defense: i32) -> i32 {
+    if damage <= 0 {
+        return damage;
+    }
+    (damage - defense).max(1)
+}
+
+/// Final damage a target takes from a raw amount. Every damage source goes through here:
+/// invulnerability, protection buffs, resistances/vulnerabilities, then armor (physical only).
+/// Damage that isn't fully negated always deals at least 1.
+pub fn final_damage(world: &World, target: Entity, amount: i32, damage_type: DamageType, projectile: bool) -> i32 {
+    if amount <= 0 || queries::has_status_effect(world, target, EffectType::Invulnerable) {
+        return 0;
+    }
+
+    let mut damage = amount as f32;
+
+    // Protected/Barkskin reduce all incoming damage
+    if queries::has_status_effect(world, target, EffectType::Protected)
+        || queries::has_status_effect(world, target, EffectType::Barkskin)
+    {
+        damage *= PROTECTION_DAMAGE_REDUCTION;
+    }
+
+    let resistances = world
+        .get::<&Resistances>(target)
+        .map(|r| *r)
+        .unwrap_or(Resistances::NONE);
+    damage *= resistances.multiplier(damage_type);
+    if projectile {
+        damage *= resistances.projectile;
+    }
+
+    let damage = (damage as i32).max(1);
+    if damage_type == DamageType::Physical {
+        reduce_by_armor(damage, armor_defense(world, target))
+    } else {
+        damage
+    }
+}
+
+/// Chance for a melee attack to land, from attacker accuracy vs target evasion (both Agility)
+pub fn melee_hit_chance(attacker_agility: i32, target_agility: i32) -> f32 {
+    let accuracy = COMBAT_BASE_HIT_CHANCE + (attacker_agility - 10) as f32 * COMBAT_ACCURACY_PER_AGILITY;
+    let evasion = (target_agility - 10) as f32 * COMBAT_EVASION_PER_AGILITY;
+    (accuracy - evasion).clamp(COMBAT_MIN_HIT_CHANCE, COMBAT_MAX_HIT_CHANCE)
+}
+
+/// Chance for a ranged attack to land; accuracy falls off past a few tiles
+pub fn ranged_hit_chance(shooter_agility: i32, target_agility: i32, distance: i32) -> f32 {
+    let falloff = (distance - RANGED_ACCURACY_FALLOFF_START).max(0) as f32 * RANGED_ACCURACY_FALLOFF_PER_TILE;
+    (melee_hit_chance(shooter_agility, target_agility) - falloff)
+        .clamp(COMBAT_MIN_HIT_CHANCE, COMBAT_MAX_HIT_CHANCE)
+}
+
+/// Agility used for accuracy/evasion rolls (including armor bonuses)
+pub fn agility_of(world: &World, entity: Entity) -> i32 {
+    effective_stats(world, entity).map(|s| s.agility).unwrap_or(10)
+}
+
 /// Handle a ContainerOpened event - update sprite for containers
 pub fn handle_container_opened(world: &mut World, container_id: Entity) {
     if let Ok(mut sprite) = world.get::<&mut Sprite>(container_id) {
@@ -150,6 +234,10 @@ pub fn remove_dead_entities(
         // Check if enemy had a bow - 50% chance to drop arrows
         let mut loot_items = Vec::new();
         if let Ok(equipment) = world.get::<&Equipment>(id) {
+            // Enemies drop whatever armor they were wearing
+            if id != player_entity {
+                loot_items.extend(equipment.worn_armor());
+            }
             if equipment.get_bow().is_some() {
                 // 50% chance to drop 1-3 arrows
                 if rng.gen::<f32>() < 0.5 {
@@ -162,6 +250,10 @@ pub fn remove_dead_entities(
         }
 
         let _ = world.insert_one(id, Container::corpse(loot_items, gold));
+        // The bones still show up in radius lookups, as a container that blocks nothing
+        if let Some(pos) = queries::get_entity_position(world, id) {
+            spatial_cache.track_item(id, pos);
+        }
     }
 }
 
@@ -180,4 +272,67 @@ mod tests {
         };
         assert_eq!(weapon_damage(&weapon), 7);
     }
+
+    #[test]
+    fn test_armor_reduces_damage_but_not_below_one() {
+        assert_eq!(reduce_by_armor(10, 3), 7);
+        assert_eq!(reduce_by_armor(2, 5), 1);
+        assert_eq!(reduce_by_armor(0, 5), 0);
+    }
+
+    #[test]
+    fn test_final_damage_applies_resistances_and_armor() {
+        use crate::components::ArmorSlot;
+        let mut world = World::new();
+        let mut equipment = Equipment::with_melee(Weapon::claws(1));
+        equipment.equip_armor(ArmorSlot::Body, ItemType::ChainMail);
+        let resistances = Resistances { fire: 1.5, projectile: 0.5, ..Resistances::NONE };
+        let target = world.spawn((equipment, resistances));
+
+        // Armor only soaks physical hits, projectiles are resisted separately
+        assert_eq!(final_damage(&world, target, 10, DamageType::Physical, false), 10 - CHAIN_MAIL_DEFENSE);
+        assert_eq!(final_damage(&world, target, 10, DamageType::Physical, true), 5 - CHAIN_MAIL_DEFENSE);
+        assert_eq!(final_damage(&world, target, 10, DamageType::Fire, false), 15);
+        assert_eq!(final_damage(&world, target, 10, DamageType::Necrotic, false), 10);
+        assert_eq!(final_damage(&world, target, 0, DamageType::Fire, false), 0);
+    }
+
+    #[test]
+    fn test_hit_chance_uses_agility_and_is_clamped() {
+        let even = melee_hit_chance(10, 10);
+        assert!((even - COMBAT_BASE_HIT_CHANCE).abs() < f32::EPSILON);
+        assert!(melee_hit_chance(16, 10) > even);
+        assert!(melee_hit_chance(10, 16) < even);
+        assert_eq!(melee_hit_chance(100, 0), COMBAT_MAX_HIT_CHANCE);
+        assert_eq!(melee_hit_chance(0, 100), COMBAT_MIN_HIT_CHANCE);
+    }
+
+    #[test]
+    fn test_ranged_accuracy_falls_off_with_distance() {
+        let close = ranged_hit_chance(10, 10, RANGED_ACCURACY_FALLOFF_START);
+        assert_eq!(close, melee_hit_chance(10, 10));
+        assert!(ranged_hit_chance(10, 10, 8) < close);
+        assert!(ranged_hit_chance(10, 10, 12) < ranged_hit_chance(10, 10, 8));
+    }
+
+    #[test]
+    fn test_effective_stats_include_worn_accessories() {
+        use crate::components::ArmorSlot;
+        use crate::tile::SpriteSheet;
+        let mut world = World::new();
+        let mut equipment = Equipment::with_melee(Weapon {
+            name: "Test Sword".to_string(),
+            sprite: (SpriteSheet::Items, 0),
+            base_damage: 5,
+            damage_bonus: 0,
+        });
+        equipment.equip_armor(ArmorSlot::Ring, ItemType::RingOfStrength);
+        equipment.equip_armor(ArmorSlot::Body, ItemType::ChainMail);
+        let entity = world.spawn((Stats::new(10, 5, 5), equipment));
+
+        let stats = effective_stats(&world, entity).unwrap();
+        assert_eq!(stats.strength, 10 + RING_STAT_BONUS);
+        assert_eq!(stats.agility, 5 + CHAIN_MAIL_AGILITY_PENALTY);
+        assert_eq!(armor_defense(&world, entity), CHAIN_MAIL_DEFENSE);
+    }
 }
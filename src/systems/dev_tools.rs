@@ -76,9 +76,9 @@ pub fn execute_dev_spawn(
             DevSpawnResult::Spawned(entity)
         }
         DevTool::SpawnEnemy => {
-            let enemy = spawning::enemies::SKELETON.spawn(world, tile_x, tile_y);
-            // Initialize the AI actor's first action
             let mut rng = rand::thread_rng();
+            let enemy = spawning::enemies::SKELETON.spawn(world, tile_x, tile_y, &mut rng);
+            // Initialize the AI actor's first action
             engine::initialize_single_ai_actor(
                 world,
                 grid,
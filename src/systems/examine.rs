@@ -0,0 +1,383 @@
+//! Examine mode: describe what the player knows about a tile.
+//!
+//! Only explored tiles are described. Terrain and decorations are remembered
+//! once seen; doors, containers and creatures can change out of sight, so
+//! they are only described while the tile is in view. Creature details come
+//! from the creature's components, plus its `EnemyDef` for what kind of
+//! fighter it is.
+
+use std::collections::BTreeMap;
+
+use hecs::{Entity, World};
+
+use crate::components::{
+    AIState, Awareness, ChaseAI, CompanionAI, Container, ContainerType, DamageType, Dialogue, Door, EffectType,
+    Health, Name, Position, Resistances, StatusEffects, Tactic, Tameable,
+};
+use crate::grid::Grid;
+use crate::spawning::enemies;
+use crate::systems::items::item_name;
+use crate::tile::{tile_ids, SpriteSheet, TileType};
+
+/// The examine cursor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Examine {
+    /// Tile being examined
+    pub cursor: (i32, i32),
+    /// Tile the mouse was over when the cursor last followed it
+    pub mouse_tile: (i32, i32),
+}
+
+impl Examine {
+    /// Start examining at `cursor`, with the mouse over `mouse_tile`
+    pub fn new(cursor: (i32, i32), mouse_tile: (i32, i32)) -> Self {
+        Self { cursor, mouse_tile }
+    }
+
+    /// Move the cursor by keyboard, staying on the map
+    pub fn step(&mut self, grid: &Grid, (dx, dy): (i32, i32)) {
+        self.cursor = (
+            (self.cursor.0 + dx).clamp(0, grid.width as i32 - 1),
+            (self.cursor.1 + dy).clamp(0, grid.height as i32 - 1),
+        );
+    }
+
+    /// Move the cursor to a tile, if it's on the map
+    pub fn look_at(&mut self, grid: &Grid, tile: (i32, i32)) {
+        if grid.get(tile.0, tile.1).is_some() {
+            self.cursor = tile;
+        }
+    }
+
+    /// Jump the cursor to the mouse when the mouse moves onto another tile
+    pub fn follow_mouse(&mut self, grid: &Grid, mouse_tile: (i32, i32)) {
+        if mouse_tile != self.mouse_tile {
+            self.mouse_tile = mouse_tile;
+            self.look_at(grid, mouse_tile);
+        }
+    }
+}
+
+/// Everything the player knows about a tile
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileDescription {
+    /// Terrain, e.g. "Stone floor"
+    pub terrain: &'static str,
+    /// Doors, stairs and decorations, e.g. "A closed door", "Scattered bones"
+    pub features: Vec<String>,
+    /// Chests, corpses and item piles with what's known to be inside
+    pub containers: Vec<String>,
+    pub creatures: Vec<CreatureDescription>,
+}
+
+/// What the player can tell about a creature by looking at it
+#[derive(Debug, Clone, PartialEq)]
+pub struct CreatureDescription {
+    pub name: String,
+    /// "Unhurt", "Wounded", "Near death"...
+    pub health: &'static str,
+    /// Active status effects, by name
+    pub effects: Vec<&'static str>,
+    /// What it's doing: "Asleep", "Chasing", "Fleeing"...
+    pub state: &'static str,
+    pub tameable: bool,
+    /// What kind of fighter it is (ranged attacks, resistances)
+    pub traits: Vec<String>,
+}
+
+/// Describe a tile, or `None` if the player hasn't explored it
+pub fn describe_tile(world: &World, grid: &Grid, player: Entity, (x, y): (i32, i32)) -> Option<TileDescription> {
+    let tile = grid.get(x, y)?;
+    if !tile.explored && !tile.visible {
+        return None;
+    }
+    // Live state is only shown for tiles in view
+    let at = |pos: &Position| tile.visible && pos.x == x && pos.y == y;
+
+    let mut features = Vec::new();
+    for (_, (pos, door)) in world.query::<(&Position, &Door)>().iter() {
+        if at(pos) {
+            features.push(if door.is_open { "An open door" } else { "A closed door" }.to_string());
+        }
+    }
+    for decal in grid.decals.iter().filter(|decal| decal.x == x && decal.y == y) {
+        if let Some(name) = decal_name((decal.sheet, decal.tile_id)) {
+            features.push(name.to_string());
+        }
+    }
+
+    let containers = world
+        .query::<(&Position, &Container)>()
+        .iter()
+        .filter(|(_, (pos, _))| at(pos))
+        .map(|(_, (_, container))| describe_container(container))
+        .collect();
+
+    let creatures = world
+        .query::<(&Position, &Health)>()
+        .iter()
+        .filter(|(_, (pos, health))| at(pos) && !health.is_dead())
+        .map(|(entity, _)| describe_creature(world, player, entity))
+        .collect();
+
+    Some(TileDescription {
+        terrain: terrain_name(tile.tile_type, tile.doorway),
+        features,
+        containers,
+        creatures,
+    })
+}
+
+/// Describe a creature from its components and enemy definition
+pub fn describe_creature(world: &World, player: Entity, entity: Entity) -> CreatureDescription {
+    let name = if entity == player {
+        "You".to_string()
+    } else if let Ok(name) = world.get::<&Name>(entity) {
+        name.0.to_string()
+    } else if let Ok(dialogue) = world.get::<&Dialogue>(entity) {
+        dialogue.name.clone()
+    } else {
+        "Something".to_string()
+    };
+
+    let health = world
+        .get::<&Health>(entity)
+        .map(|health| health_estimate(health.current, health.max))
+        .unwrap_or("Unhurt");
+
+    let effect_types: Vec<EffectType> = world
+        .get::<&StatusEffects>(entity)
+        .map(|effects| effects.effects.iter().map(|effect| effect.effect_type).collect())
+        .unwrap_or_default();
+
+    let state = if entity == player {
+        ""
+    } else if effect_types.contains(&EffectType::Feared) {
+        "Fleeing"
+    } else if effect_types.contains(&EffectType::Confused) {
+        "Confused"
+    } else if world.get::<&CompanionAI>(entity).is_ok() {
+        "Your companion"
+    } else if let Ok(ai) = world.get::<&ChaseAI>(entity) {
+        match (ai.awareness, ai.state) {
+            (Awareness::Asleep, _) => "Asleep",
+            (Awareness::Unaware, _) => "Unaware",
+            (_, AIState::Idle) => "Idle",
+            (_, AIState::Chasing) => "Chasing",
+            (_, AIState::Investigating) => "Searching",
+        }
+    } else {
+        "Peaceful"
+    };
+
+    let mut traits = Vec::new();
+    if let Some(def) = world.get::<&Name>(entity).ok().and_then(|name| enemies::by_name(name.0)) {
+        if def.ranged.is_some() {
+            traits.push("Attacks from range".to_string());
+        }
+        if def.behavior.iter().any(|tactic| matches!(tactic, Tactic::HealAllies { .. })) {
+            traits.push("Heals its allies".to_string());
+        }
+    }
+    if let Ok(resistances) = world.get::<&Resistances>(entity) {
+        traits.extend(resistance_traits(&resistances));
+    }
+
+    CreatureDescription {
+        name,
+        health,
+        effects: effect_types.into_iter().map(effect_name).collect(),
+        state,
+        tameable: world.get::<&Tameable>(entity).is_ok(),
+        traits,
+    }
+}
+
+/// Rough health from the fraction left, as the player would see it
+pub fn health_estimate(current: i32, max: i32) -> &'static str {
+    let fraction = current as f32 / max.max(1) as f32;
+    match fraction {
+        f if f >= 1.0 => "Unhurt",
+        f if f >= 0.75 => "Lightly wounded",
+        f if f >= 0.5 => "Wounded",
+        f if f >= 0.25 => "Badly wounded",
+        _ => "Near death",
+    }
+}
+
+fn describe_container(container: &Container) -> String {
+    let kind = match container.container_type {
+        ContainerType::Chest => "chest",
+        ContainerType::Coffin => "coffin",
+        ContainerType::Barrel => "barrel",
+        ContainerType::Corpse => "corpse",
+        ContainerType::GroundPile => return format!("On the ground: {}", contents(container)),
+    };
+    if container.is_open {
+        format!("An open {}: {}", kind, contents(container))
+    } else {
+        format!("A closed {}", kind)
+    }
+}
+
+/// "Apple, Arrow x3, 12 gold" or "empty"
+fn contents(container: &Container) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for &item in &container.items {
+        *counts.entry(item_name(item)).or_default() += 1;
+    }
+    let mut parts: Vec<String> = counts
+        .into_iter()
+        .map(|(name, count)| if count > 1 { format!("{} x{}", name, count) } else { name.to_string() })
+        .collect();
+    if container.gold > 0 {
+        parts.push(format!("{} gold", container.gold));
+    }
+    if parts.is_empty() {
+        "empty".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn terrain_name(tile_type: TileType, doorway: bool) -> &'static str {
+    match tile_type {
+        _ if doorway => "Doorway",
+        TileType::Empty => "Nothing",
+        TileType::Floor | TileType::Stone => "Stone floor",
+        TileType::Wall => "Wall",
+        TileType::Water => "Shallow water",
+        TileType::Grass => "Grass",
+        TileType::TallGrass => "Tall grass (hides whatever is in it)",
+        TileType::StairsDown => "Stairs leading down",
+        TileType::StairsUp => "Stairs leading up",
+    }
+}
+
+fn decal_name(sprite: (SpriteSheet, u32)) -> Option<&'static str> {
+    const NAMES: [((SpriteSheet, u32), &str); 19] = [
+        (tile_ids::BONES_1, "Scattered bones"),
+        (tile_ids::BONES_2, "Scattered bones"),
+        (tile_ids::BONES_3, "Scattered bones"),
+        (tile_ids::BONES_4, "Old remains"),
+        (tile_ids::SKULL, "A skull"),
+        (tile_ids::ROCKS, "Rocks"),
+        (tile_ids::ROCKS_2, "Rocks"),
+        (tile_ids::MUSHROOM, "Mushrooms"),
+        (tile_ids::MUSHROOM_LARGE, "A large mushroom"),
+        (tile_ids::FLOWERS, "Flowers"),
+        (tile_ids::PLANT, "Weeds"),
+        (tile_ids::PLANT_FLAX, "Weeds"),
+        (tile_ids::PLANT_PAPYRUS, "Reeds"),
+        (tile_ids::PLANT_RICE, "Weeds"),
+        (tile_ids::PLANT_CORN, "Weeds"),
+        (tile_ids::BLOOD_1, "Dried blood"),
+        (tile_ids::BLOOD_2, "Dried blood"),
+        (tile_ids::SLIME_SMALL, "Slime"),
+        (tile_ids::SLIME_LARGE, "Slime"),
+    ];
+    NAMES.iter().find(|(decal, _)| *decal == sprite).map(|(_, name)| *name)
+}
+
+fn effect_name(effect_type: EffectType) -> &'static str {
+    match effect_type {
+        EffectType::Invisible => "Invisible",
+        EffectType::SpeedBoost => "Hasted",
+        EffectType::Regenerating => "Regenerating",
+        EffectType::Strengthened => "Strengthened",
+        EffectType::Protected => "Protected",
+        EffectType::Barkskin => "Barkskin",
+        EffectType::Confused => "Confused",
+        EffectType::Feared => "Afraid",
+        EffectType::Slowed => "Slowed",
+        EffectType::Burning => "Burning",
+        EffectType::Rooted => "Rooted",
+        EffectType::Invulnerable => "Invulnerable",
+    }
+}
+
+/// "Resists fire", "Weak to arrows"...
+pub fn resistance_traits(resistances: &Resistances) -> Vec<String> {
+    let damage_types = [
+        (DamageType::Physical, "physical harm"),
+        (DamageType::Fire, "fire"),
+        (DamageType::Necrotic, "life drain"),
+        (DamageType::Poison, "poison"),
+        (DamageType::Cold, "cold"),
+    ];
+    let multipliers = damage_types
+        .into_iter()
+        .map(|(damage_type, name)| (resistances.multiplier(damage_type), name))
+        .chain([(resistances.projectile, "arrows")]);
+
+    multipliers
+        .filter_map(|(multiplier, name)| match multiplier {
+            m if m < 1.0 => Some(format!("Resists {}", name)),
+            m if m > 1.0 => Some(format!("Weak to {}", name)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ActiveEffect, ItemType, Player};
+
+    #[test]
+    fn test_describes_skeleton_archer() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(5, 5, true, true);
+        let player = world.spawn((Player, Position::new(0, 0)));
+        let archer = enemies::SKELETON_ARCHER.spawn(&mut world, 2, 2, &mut rand::thread_rng());
+        world.get::<&mut Health>(archer).unwrap().current = 1;
+        world.get::<&mut StatusEffects>(archer).unwrap().effects.push(ActiveEffect {
+            effect_type: EffectType::Feared,
+            remaining_duration: 5.0,
+            last_damage_tick: 0.0,
+        });
+
+        let description = describe_tile(&world, &grid, player, (2, 2)).unwrap();
+        let creature = &description.creatures[0];
+        assert_eq!(creature.name, "Skeleton Archer");
+        assert_eq!(creature.health, "Near death");
+        assert_eq!(creature.effects, vec!["Afraid"]);
+        assert_eq!(creature.state, "Fleeing");
+        assert!(!creature.tameable);
+        assert!(creature.traits.contains(&"Attacks from range".to_string()));
+        assert!(creature.traits.contains(&"Resists arrows".to_string()));
+    }
+
+    #[test]
+    fn test_only_describes_terrain_out_of_view() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(5, 5, true, true);
+        let player = world.spawn((Player, Position::new(0, 0)));
+        enemies::GIANT_BAT.spawn(&mut world, 3, 1, &mut rand::thread_rng());
+        world.spawn((
+            Position::new(3, 1),
+            Container {
+                container_type: ContainerType::GroundPile,
+                items: vec![ItemType::Arrow, ItemType::Apple, ItemType::Arrow],
+                gold: 5,
+                is_open: true,
+                spawn_chance: 0.0,
+            },
+        ));
+
+        let description = describe_tile(&world, &grid, player, (3, 1)).unwrap();
+        assert_eq!(description.creatures[0].state, "Idle");
+        assert!(description.creatures[0].tameable);
+        assert_eq!(description.containers, vec!["On the ground: Apple, Arrow x2, 5 gold".to_string()]);
+
+        // The pile could have been picked up since, so it isn't described out of view
+        grid.tiles[5 + 3].visible = false;
+        let description = describe_tile(&world, &grid, player, (3, 1)).unwrap();
+        assert_eq!(description.terrain, "Stone floor");
+        assert!(description.containers.is_empty());
+        assert!(description.creatures.is_empty());
+
+        grid.tiles[5 + 3].explored = false;
+        assert_eq!(describe_tile(&world, &grid, player, (3, 1)), None);
+    }
+}
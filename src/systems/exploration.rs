@@ -0,0 +1,310 @@
+//! Auto-explore: walk the player toward the nearest unexplored part of the floor.
+//!
+//! Every step re-plans with a Dijkstra map seeded from the frontier (explored
+//! floor next to tiles the player hasn't seen) and from item piles worth
+//! picking up, weighted by the player's hazard costs. The resulting path is
+//! followed like a click-to-move path, so damage and enemies noticing the
+//! player interrupt it the usual way. On top of that, exploring stops when an
+//! enemy is in view or something new (stairs, a chest, the shop) is sighted.
+
+use std::collections::HashSet;
+
+use hecs::{Entity, World};
+
+use crate::components::{BlocksMovement, Container, ContainerType, Health, ItemType, Position, Vendor};
+use crate::dijkstra_map::DijkstraMap;
+use crate::events::EventQueue;
+use crate::grid::Grid;
+use crate::pathfinding::NEIGHBORS;
+use crate::queries;
+use crate::systems::factions;
+use crate::systems::inventory::{take_gold_from_container, take_item_from_container};
+use crate::systems::item_defs::{self, ItemCategory};
+use crate::systems::travel;
+use crate::tile::TileType;
+
+/// Something worth stopping for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sight {
+    StairsDown,
+    StairsUp,
+    Chest,
+    Shop,
+}
+
+/// Why auto-explore stopped on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExploreStop {
+    /// Nothing reachable is left to explore
+    Done,
+    /// A hostile creature is in view
+    EnemyInView(Entity),
+    /// Something new came into sight
+    Spotted(Sight),
+}
+
+/// State of one auto-explore run
+#[derive(Debug, Clone, Default)]
+pub struct AutoExplore {
+    /// Sights already reported (or known when exploring started)
+    noticed: HashSet<(i32, i32)>,
+    /// Frontier tiles already stood on, so unlit corners can't trap us in place
+    reached: HashSet<(i32, i32)>,
+    map: DijkstraMap,
+}
+
+impl AutoExplore {
+    /// Start exploring. Whatever the player already knows about isn't a discovery.
+    pub fn start(world: &World, grid: &Grid) -> Self {
+        Self {
+            noticed: sights(world, grid).into_iter().map(|(pos, _)| pos).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Plan the path to the nearest frontier tile or wanted item pile,
+    /// or say why exploring should stop.
+    pub fn next_path(&mut self, world: &World, grid: &Grid, player: Entity) -> Result<Vec<(i32, i32)>, ExploreStop> {
+        if let Some(enemy) = enemy_in_view(world, grid, player) {
+            return Err(ExploreStop::EnemyInView(enemy));
+        }
+
+        let mut spotted = None;
+        for (pos, sight) in sights(world, grid) {
+            if self.noticed.insert(pos) && spotted.is_none() {
+                spotted = Some(sight);
+            }
+        }
+        if let Some(sight) = spotted {
+            return Err(ExploreStop::Spotted(sight));
+        }
+
+        let player_pos = queries::get_entity_position(world, player).ok_or(ExploreStop::Done)?;
+        self.reached.insert(player_pos);
+
+        let mut goals: Vec<(i32, i32)> = frontier(grid)
+            .into_iter()
+            .filter(|pos| !self.reached.contains(pos))
+            .collect();
+        goals.extend(wanted_piles(world, grid).into_iter().filter(|&pos| pos != player_pos));
+        if goals.is_empty() {
+            return Err(ExploreStop::Done);
+        }
+
+        let path = travel::path_over_known_tiles(world, grid, player, &goals, &mut self.map);
+        if path.is_empty() {
+            return Err(ExploreStop::Done);
+        }
+        Ok(path)
+    }
+}
+
+/// Whether auto-explore picks an item up on its way
+pub fn wants_item(item: ItemType) -> bool {
+    item == ItemType::Arrow
+        || matches!(
+            item_defs::get_def(item).category,
+            ItemCategory::Potion | ItemCategory::Scroll | ItemCategory::Food
+        )
+}
+
+/// A walk-over pile (ground items, bones) under the player holding something worth taking
+pub fn pile_to_loot(world: &World, player: Entity) -> Option<Entity> {
+    let player_pos = queries::get_entity_position(world, player)?;
+    world
+        .query::<(&Position, &Container)>()
+        .without::<&BlocksMovement>()
+        .iter()
+        .find(|(_, (pos, container))| (pos.x, pos.y) == player_pos && pile_is_wanted(container))
+        .map(|(id, _)| id)
+}
+
+/// Take the gold and wanted items from a pile, leaving the rest
+pub fn loot_wanted(world: &mut World, player: Entity, pile: Entity, events: &mut EventQueue) {
+    take_gold_from_container(world, player, pile, Some(events));
+    let wanted: Vec<usize> = match world.get::<&Container>(pile) {
+        Ok(container) => container
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, &item)| wants_item(item))
+            .map(|(index, _)| index)
+            .collect(),
+        Err(_) => return,
+    };
+    // Highest index first so the others don't shift
+    for index in wanted.into_iter().rev() {
+        take_item_from_container(world, player, pile, index, Some(events));
+    }
+}
+
+fn pile_is_wanted(container: &Container) -> bool {
+    container.gold > 0 || container.items.iter().any(|&item| wants_item(item))
+}
+
+/// Positions of explored walk-over piles with something worth taking
+fn wanted_piles(world: &World, grid: &Grid) -> Vec<(i32, i32)> {
+    world
+        .query::<(&Position, &Container)>()
+        .without::<&BlocksMovement>()
+        .iter()
+        .filter(|(_, (pos, container))| {
+            grid.get(pos.x, pos.y).is_some_and(|tile| tile.explored) && pile_is_wanted(container)
+        })
+        .map(|(_, (pos, _))| (pos.x, pos.y))
+        .collect()
+}
+
+/// Explored floor tiles next to at least one unexplored tile
+fn frontier(grid: &Grid) -> Vec<(i32, i32)> {
+    let mut tiles = Vec::new();
+    for y in 0..grid.height as i32 {
+        for x in 0..grid.width as i32 {
+            let Some(tile) = grid.get(x, y) else { continue };
+            if !tile.explored
+                || !tile.tile_type.is_walkable()
+                || matches!(tile.tile_type, TileType::StairsUp | TileType::StairsDown)
+            {
+                continue;
+            }
+            let borders_unknown = NEIGHBORS
+                .iter()
+                .any(|&(dx, dy)| grid.get(x + dx, y + dy).is_some_and(|next| !next.explored));
+            if borders_unknown {
+                tiles.push((x, y));
+            }
+        }
+    }
+    tiles
+}
+
+/// Interesting things on explored tiles
+fn sights(world: &World, grid: &Grid) -> Vec<((i32, i32), Sight)> {
+    let explored = |pos: (i32, i32)| grid.get(pos.0, pos.1).is_some_and(|tile| tile.explored);
+    let mut found = Vec::new();
+
+    if let Some(pos) = grid.stairs_down_pos.filter(|&pos| explored(pos)) {
+        found.push((pos, Sight::StairsDown));
+    }
+    if let Some(pos) = grid.stairs_up_pos.filter(|&pos| explored(pos)) {
+        found.push((pos, Sight::StairsUp));
+    }
+    for (_, (pos, container)) in world.query::<(&Position, &Container)>().iter() {
+        if container.container_type == ContainerType::Chest && !container.is_open && explored((pos.x, pos.y)) {
+            found.push(((pos.x, pos.y), Sight::Chest));
+        }
+    }
+    for (_, pos) in world.query::<&Position>().with::<&Vendor>().iter() {
+        if explored((pos.x, pos.y)) {
+            found.push(((pos.x, pos.y), Sight::Shop));
+        }
+    }
+
+    found
+}
+
+/// A living hostile creature standing on a visible tile
+pub fn enemy_in_view(world: &World, grid: &Grid, player: Entity) -> Option<Entity> {
+    world
+        .query::<(&Position, &Health)>()
+        .iter()
+        .filter(|(_, (pos, health))| {
+            !health.is_dead() && grid.get(pos.x, pos.y).is_some_and(|tile| tile.visible)
+        })
+        .map(|(id, _)| id)
+        .find(|&id| id != player && factions::is_hostile(world, player, id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Faction, GroundItemPile, Inventory, Player};
+
+    /// Mark the tiles with x in `xs` as explored
+    fn explore(grid: &mut Grid, xs: std::ops::Range<i32>) {
+        for y in 0..grid.height as i32 {
+            for x in xs.clone() {
+                let idx = y as usize * grid.width + x as usize;
+                grid.tiles[idx].explored = true;
+            }
+        }
+    }
+
+    #[test]
+    fn test_heads_for_the_unexplored_edge() {
+        let mut world = World::new();
+        let mut grid = Grid::filled(10, 3, TileType::Floor);
+        explore(&mut grid, 0..5);
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 1)));
+
+        let mut explore = AutoExplore::start(&world, &grid);
+        let path = explore.next_path(&world, &grid, player).unwrap();
+        assert_eq!(path.last().map(|pos| pos.0), Some(4));
+        assert!(path.iter().all(|&(x, _)| x <= 4), "stepped onto unexplored tiles: {:?}", path);
+    }
+
+    #[test]
+    fn test_done_when_everything_is_explored() {
+        let mut world = World::new();
+        let mut grid = Grid::filled(6, 3, TileType::Floor);
+        explore(&mut grid, 0..6);
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 1)));
+
+        let mut explore = AutoExplore::start(&world, &grid);
+        assert_eq!(explore.next_path(&world, &grid, player), Err(ExploreStop::Done));
+    }
+
+    #[test]
+    fn test_stops_for_enemies_and_new_sights() {
+        let mut world = World::new();
+        let mut grid = Grid::filled(10, 3, TileType::Floor);
+        explore(&mut grid, 0..5);
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 1)));
+        let mut explore = AutoExplore::start(&world, &grid);
+
+        // Stairs come into sight: stop once, then carry on
+        grid.stairs_down_pos = Some((3, 1));
+        assert_eq!(
+            explore.next_path(&world, &grid, player),
+            Err(ExploreStop::Spotted(Sight::StairsDown))
+        );
+        assert!(explore.next_path(&world, &grid, player).is_ok());
+
+        let rat = world.spawn((Faction::Vermin, Position::new(4, 1), Health::new(5)));
+        grid.tiles[10 + 4].visible = true;
+        assert_eq!(explore.next_path(&world, &grid, player), Err(ExploreStop::EnemyInView(rat)));
+    }
+
+    #[test]
+    fn test_detours_for_wanted_items_only() {
+        let mut world = World::new();
+        let mut grid = Grid::filled(10, 3, TileType::Floor);
+        explore(&mut grid, 0..5);
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 1)));
+        let pile = world.spawn((
+            Position::new(1, 0),
+            GroundItemPile,
+            Container {
+                container_type: ContainerType::GroundPile,
+                items: vec![ItemType::Sword, ItemType::HealthPotion],
+                gold: 0,
+                is_open: true,
+                spawn_chance: 0.0,
+            },
+        ));
+
+        let mut explore = AutoExplore::start(&world, &grid);
+        assert_eq!(explore.next_path(&world, &grid, player).unwrap(), vec![(1, 0)]);
+
+        world.get::<&mut Position>(player).unwrap().x = 1;
+        world.get::<&mut Position>(player).unwrap().y = 0;
+        assert_eq!(pile_to_loot(&world, player), Some(pile));
+
+        // The potion is taken, the sword left behind
+        world.insert_one(player, Inventory::new()).unwrap();
+        loot_wanted(&mut world, player, pile, &mut EventQueue::new());
+        assert_eq!(world.get::<&Inventory>(player).unwrap().items, vec![ItemType::HealthPotion]);
+        assert_eq!(world.get::<&Container>(pile).unwrap().items, vec![ItemType::Sword]);
+        assert_eq!(pile_to_loot(&world, player), None);
+    }
+}
@@ -0,0 +1,122 @@
+//! Factions and how they get along.
+//!
+//! Every creature belongs to a faction, and the relationship table below
+//! decides who an enemy hunts on sight (hostile), leaves alone unless
+//! provoked (neutral) and backs up in a fight (friendly). Once someone has
+//! been hurt, the threat table takes over, so any two creatures can end up
+//! fighting - including allies turned on each other by Fear or Confusion.
+
+use hecs::{Entity, World};
+
+use crate::components::{Faction, Player, TamedBy};
+
+/// How one faction regards another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relation {
+    /// Attacked on sight
+    Hostile,
+    /// Ignored until provoked
+    Neutral,
+    /// Allies: alerted, healed and fought alongside
+    Friendly,
+}
+
+/// The relationship table. Symmetric: `relation(a, b) == relation(b, a)`.
+pub fn relation(a: Faction, b: Faction) -> Relation {
+    use Faction::*;
+
+    if a == b {
+        return Relation::Friendly;
+    }
+    match (a, b) {
+        (Wildlife, _) | (_, Wildlife) => Relation::Neutral,
+        (Player, _) | (_, Player) => Relation::Hostile,
+        // Rats gnaw on bones, skeletons trample rats
+        (Undead, Vermin) | (Vermin, Undead) => Relation::Hostile,
+        // Goblins keep rats; the dead they merely put up with, since those eat rats
+        (Goblins, Vermin) | (Vermin, Goblins) => Relation::Friendly,
+        _ => Relation::Neutral,
+    }
+}
+
+/// Which faction an entity is on. The player and companions are always on the
+/// player's side; other creatures use their `Faction` component, if any.
+pub fn faction_of(world: &World, entity: Entity) -> Option<Faction> {
+    if world.get::<&Player>(entity).is_ok() || world.get::<&TamedBy>(entity).is_ok() {
+        return Some(Faction::Player);
+    }
+    world.get::<&Faction>(entity).ok().map(|f| *f)
+}
+
+/// How two entities regard each other. Creatures without a faction are neutral.
+pub fn relation_between(world: &World, a: Entity, b: Entity) -> Relation {
+    if a == b {
+        return Relation::Friendly;
+    }
+    match (faction_of(world, a), faction_of(world, b)) {
+        (Some(fa), Some(fb)) => relation(fa, fb),
+        _ => Relation::Neutral,
+    }
+}
+
+/// Whether `a` attacks `b` on sight
+pub fn is_hostile(world: &World, a: Entity, b: Entity) -> bool {
+    relation_between(world, a, b) == Relation::Hostile
+}
+
+/// Whether `a` and `b` are on the same side (alerting, healing, surrounding)
+pub fn is_allied(world: &World, a: Entity, b: Entity) -> bool {
+    relation_between(world, a, b) == Relation::Friendly
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::CompanionAI;
+
+    #[test]
+    fn test_relation_table_is_symmetric() {
+        use Faction::*;
+        let all = [Player, Undead, Vermin, Goblins, Wildlife];
+        for a in all {
+            assert_eq!(relation(a, a), Relation::Friendly);
+            for b in all {
+                assert_eq!(relation(a, b), relation(b, a), "{:?} vs {:?}", a, b);
+            }
+        }
+        assert_eq!(relation(Undead, Vermin), Relation::Hostile);
+        assert_eq!(relation(Player, Wildlife), Relation::Neutral);
+        assert_eq!(relation(Goblins, Undead), Relation::Neutral);
+    }
+
+    #[test]
+    fn test_no_faction_backs_both_sides_of_a_feud() {
+        use Faction::*;
+        let all = [Player, Undead, Vermin, Goblins, Wildlife];
+        for a in all {
+            for b in all {
+                if relation(a, b) != Relation::Hostile {
+                    continue;
+                }
+                for c in all {
+                    let backs_both = relation(c, a) == Relation::Friendly && relation(c, b) == Relation::Friendly;
+                    assert!(!backs_both, "{:?} is friendly to both {:?} and {:?}", c, a, b);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tamed_animals_join_the_player() {
+        let mut world = World::new();
+        let player = world.spawn((Player,));
+        let rat = world.spawn((Faction::Vermin,));
+        let skeleton = world.spawn((Faction::Undead,));
+        assert!(is_hostile(&world, rat, player));
+        assert!(is_hostile(&world, rat, skeleton));
+
+        world.insert(rat, (TamedBy { owner: player }, CompanionAI::new(player, 2))).unwrap();
+        assert!(is_allied(&world, rat, player));
+        assert!(is_hostile(&world, skeleton, rat));
+    }
+}
@@ -0,0 +1,342 @@
+//! Hazard-aware path costs.
+//!
+//! Every walker gets a `CostProfile` saying how much it minds each hazard:
+//! the player steers around fire, known traps, deep water and archer fire,
+//! most creatures only keep out of fire, and fire-immune creatures don't
+//! care at all. `HazardCosts` turns a profile into per-tile penalties that
+//! the weighted A* (and the shared Dijkstra maps) add on top of step costs.
+
+use std::collections::{HashMap, HashSet};
+
+use hecs::{Entity, World};
+
+use crate::components::{
+    CausesBurning, ChaseAI, Equipment, PlacedFireTrap, PlacedTrap, Player, Position, Resistances, TamedBy,
+};
+use crate::constants::MAX_ENEMY_BOW_RANGE;
+use crate::grid::Grid;
+use crate::pathfinding::{self, CostProfile, STEP_COST};
+use crate::spatial_cache::SpatialCache;
+use crate::systems::behaviors::chebyshev;
+use crate::systems::factions;
+use crate::tile::TileType;
+
+/// The cost profile an entity paths with
+pub fn profile_for(world: &World, entity: Entity) -> CostProfile {
+    let mut profile = if world.get::<&Player>(entity).is_ok() {
+        CostProfile::CAUTIOUS
+    } else {
+        CostProfile::CREATURE
+    };
+
+    let fire_immune = world
+        .get::<&Resistances>(entity)
+        .is_ok_and(|resistances| resistances.fire <= 0.0);
+    if fire_immune {
+        profile.fire = 0;
+    }
+
+    profile
+}
+
+/// Per-tile path penalties for one walker, in the same units as `STEP_COST`
+pub struct HazardCosts {
+    penalties: HashMap<(i32, i32), i32>,
+    water: i32,
+}
+
+impl HazardCosts {
+    /// Collect the hazards `walker` cares about under `profile`.
+    /// Without a walker, traps count whoever owns them and archers are ignored
+    /// (nobody to be hostile to).
+    pub fn new(world: &World, grid: &Grid, walker: Option<Entity>, profile: CostProfile) -> Self {
+        let mut penalties: HashMap<(i32, i32), i32> = HashMap::new();
+
+        if profile.fire > 0 {
+            for (_, pos) in world.query::<&Position>().with::<&CausesBurning>().iter() {
+                *penalties.entry((pos.x, pos.y)).or_default() += profile.fire * STEP_COST;
+            }
+        }
+
+        if profile.traps > 0 {
+            for pos in known_traps(world, grid, walker, |_| true) {
+                *penalties.entry(pos).or_default() += profile.traps * STEP_COST;
+            }
+        }
+
+        if let (true, Some(walker)) = (profile.archer_range > 0, walker) {
+            // Overlapping archers don't stack: a tile is either covered or not
+            let mut covered: HashSet<(i32, i32)> = HashSet::new();
+            for (archer, (pos, chase, equipment)) in world.query::<(&Position, &ChaseAI, &Equipment)>().iter() {
+                if !is_threatening_archer(world, grid, walker, archer, (pos.x, pos.y), chase, equipment) {
+                    continue;
+                }
+                let range = chase.ranged_max;
+                for y in (pos.y - range)..=(pos.y + range) {
+                    for x in (pos.x - range)..=(pos.x + range) {
+                        if chebyshev((x, y), (pos.x, pos.y)) <= range && grid.is_walkable(x, y) {
+                            covered.insert((x, y));
+                        }
+                    }
+                }
+            }
+            for pos in covered {
+                *penalties.entry(pos).or_default() += profile.archer_range * STEP_COST;
+            }
+        }
+
+        Self {
+            penalties,
+            water: profile.water * STEP_COST,
+        }
+    }
+
+    /// Extra cost of entering a tile
+    pub fn cost(&self, grid: &Grid, pos: (i32, i32)) -> i32 {
+        let mut cost = self.penalties.get(&pos).copied().unwrap_or(0);
+        if self.water > 0 && grid.get(pos.0, pos.1).is_some_and(|tile| tile.tile_type == TileType::Water) {
+            cost += self.water;
+        }
+        cost
+    }
+}
+
+/// Extra cost for `walker` of entering just `pos`, the same as `HazardCosts::cost` would give
+/// but without collecting the hazards of the whole floor. For checking a single step.
+pub fn cost_at(
+    world: &World,
+    grid: &Grid,
+    spatial_cache: &SpatialCache,
+    walker: Entity,
+    profile: CostProfile,
+    pos: (i32, i32),
+) -> i32 {
+    let mut cost = 0;
+
+    if profile.fire > 0 {
+        let burning = world
+            .query::<&Position>()
+            .with::<&CausesBurning>()
+            .iter()
+            .filter(|(_, p)| (p.x, p.y) == pos)
+            .count();
+        cost += burning as i32 * profile.fire * STEP_COST;
+    }
+
+    if profile.traps > 0 {
+        cost += known_traps(world, grid, Some(walker), |trap| trap == pos).len() as i32 * profile.traps * STEP_COST;
+    }
+
+    if profile.archer_range > 0 && grid.is_walkable(pos.0, pos.1) {
+        let covered = spatial_cache.entities_within(pos, MAX_ENEMY_BOW_RANGE).any(|(archer, archer_pos)| {
+            let (Ok(chase), Ok(equipment)) = (world.get::<&ChaseAI>(archer), world.get::<&Equipment>(archer)) else {
+                return false;
+            };
+            is_threatening_archer(world, grid, walker, archer, archer_pos, &chase, &equipment)
+                && chebyshev(pos, archer_pos) <= chase.ranged_max
+        });
+        if covered {
+            cost += profile.archer_range * STEP_COST;
+        }
+    }
+
+    if profile.water > 0 && grid.get(pos.0, pos.1).is_some_and(|tile| tile.tile_type == TileType::Water) {
+        cost += profile.water * STEP_COST;
+    }
+    cost
+}
+
+/// Explored trap tiles accepted by `keep` that `walker` would set off.
+/// Without a walker, traps count whoever owns them.
+fn known_traps(
+    world: &World,
+    grid: &Grid,
+    walker: Option<Entity>,
+    keep: impl Fn((i32, i32)) -> bool,
+) -> Vec<(i32, i32)> {
+    let sets_off = |owner: Entity| match walker {
+        Some(walker) => {
+            walker != owner
+                && !world.get::<&TamedBy>(walker).is_ok_and(|tamed| tamed.owner == owner)
+        }
+        None => true,
+    };
+    let wanted = |pos: &Position| keep((pos.x, pos.y)) && grid.get(pos.x, pos.y).is_some_and(|tile| tile.explored);
+
+    let mut traps: Vec<(i32, i32)> = Vec::new();
+    for (_, (pos, trap)) in world.query::<(&Position, &PlacedFireTrap)>().iter() {
+        if wanted(pos) && sets_off(trap.owner) {
+            traps.push((pos.x, pos.y));
+        }
+    }
+    for (_, (pos, trap)) in world.query::<(&Position, &PlacedTrap)>().iter() {
+        if wanted(pos) && sets_off(trap.owner) {
+            traps.push((pos.x, pos.y));
+        }
+    }
+    traps
+}
+
+/// Whether `archer` is a visible, alert, hostile bowman that `walker` should keep out of range of
+fn is_threatening_archer(
+    world: &World,
+    grid: &Grid,
+    walker: Entity,
+    archer: Entity,
+    pos: (i32, i32),
+    chase: &ChaseAI,
+    equipment: &Equipment,
+) -> bool {
+    if chase.ranged_max <= 0 || !equipment.has_bow() {
+        return false;
+    }
+    let seen = grid.get(pos.0, pos.1).is_some_and(|tile| tile.visible);
+    seen && factions::is_hostile(world, walker, archer) && !chase.is_unaware()
+}
+
+/// Find a path for `entity`, detouring around the hazards its profile minds
+pub fn find_path_for(
+    world: &World,
+    grid: &Grid,
+    entity: Entity,
+    start: (i32, i32),
+    goal: (i32, i32),
+    blocked: &HashSet<(i32, i32)>,
+) -> Option<Vec<(i32, i32)>> {
+    let profile = profile_for(world, entity);
+    if profile.is_none() {
+        return pathfinding::find_path(grid, start, goal, blocked);
+    }
+    let costs = HazardCosts::new(world, grid, Some(entity), profile);
+    pathfinding::find_path_weighted(grid, start, goal, blocked, &|pos| costs.cost(grid, pos))
+}
+
+/// Like `pathfinding::next_step_toward`, using `entity`'s cost profile
+pub fn next_step_for(
+    world: &World,
+    grid: &Grid,
+    entity: Entity,
+    start: (i32, i32),
+    goal: (i32, i32),
+    blocked: &HashSet<(i32, i32)>,
+) -> Option<(i32, i32)> {
+    if start == goal {
+        return None;
+    }
+    let profile = profile_for(world, entity);
+    if profile.is_none() {
+        return pathfinding::next_step_toward(grid, start, goal, blocked);
+    }
+    let costs = HazardCosts::new(world, grid, Some(entity), profile);
+    pathfinding::find_path_weighted(grid, start, goal, blocked, &|pos| costs.cost(grid, pos))
+        .and_then(|path| path.first().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Faction, Health, RangedWeapon};
+    use crate::constants::{PATH_ARCHER_RANGE_PENALTY, PATH_TRAP_PENALTY};
+
+    #[test]
+    fn test_player_walks_around_fire() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(7, 5, true, true);
+        let player = world.spawn((Player, Position::new(0, 2)));
+        world.spawn((Position::new(3, 2), CausesBurning));
+
+        let path = find_path_for(&world, &grid, player, (0, 2), (6, 2), &HashSet::new()).unwrap();
+        assert!(!path.contains(&(3, 2)), "path went through the brazier: {:?}", path);
+    }
+
+    #[test]
+    fn test_fire_immune_creature_walks_through_fire() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(7, 5, true, true);
+        let imp = world.spawn((
+            Position::new(0, 2),
+            Resistances { fire: 0.0, ..Resistances::NONE },
+        ));
+        world.spawn((Position::new(3, 2), CausesBurning));
+
+        assert_eq!(profile_for(&world, imp), CostProfile::NONE);
+        let path = find_path_for(&world, &grid, imp, (0, 2), (6, 2), &HashSet::new()).unwrap();
+        assert!(path.contains(&(3, 2)));
+    }
+
+    #[test]
+    fn test_player_wades_only_when_cheaper() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(9, 3, true, true);
+        let player = world.spawn((Player, Position::new(0, 1)));
+        for y in 0..3 {
+            let idx = y * 9 + 4;
+            grid.tiles[idx].tile_type = TileType::Water;
+        }
+
+        // A full-width river: no way around, so the player still crosses it
+        let path = find_path_for(&world, &grid, player, (0, 1), (8, 1), &HashSet::new()).unwrap();
+        assert_eq!(path.last(), Some(&(8, 1)));
+
+        // A single puddle gets walked around
+        grid.tiles[4].tile_type = TileType::Floor;
+        grid.tiles[2 * 9 + 4].tile_type = TileType::Floor;
+        let path = find_path_for(&world, &grid, player, (0, 1), (8, 1), &HashSet::new()).unwrap();
+        assert!(!path.contains(&(4, 1)));
+    }
+
+    #[test]
+    fn test_own_traps_are_no_hazard() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(5, 5, true, true);
+        let player = world.spawn((Player, Position::new(0, 2)));
+        world.spawn((Position::new(2, 2), PlacedFireTrap { owner: player, burst_damage: 5 }));
+
+        let costs = HazardCosts::new(&world, &grid, Some(player), CostProfile::CAUTIOUS);
+        assert_eq!(costs.cost(&grid, (2, 2)), 0);
+        let costs = HazardCosts::new(&world, &grid, None, CostProfile::CAUTIOUS);
+        assert_eq!(costs.cost(&grid, (2, 2)), PATH_TRAP_PENALTY * STEP_COST);
+    }
+
+    #[test]
+    fn test_archer_range_is_penalised() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(12, 12, true, true);
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 0)));
+        world.spawn((
+            Faction::Undead,
+            Position::new(10, 10),
+            ChaseAI::with_ranged(8, 2, 4),
+            Equipment::with_ranged(RangedWeapon::enemy_bow(3)),
+        ));
+
+        let costs = HazardCosts::new(&world, &grid, Some(player), CostProfile::CAUTIOUS);
+        assert_eq!(costs.cost(&grid, (6, 6)), PATH_ARCHER_RANGE_PENALTY * STEP_COST);
+        assert_eq!(costs.cost(&grid, (5, 5)), 0);
+    }
+
+    #[test]
+    fn test_single_tile_cost_matches_floor_costs() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(12, 12, true, true);
+        grid.tiles[12 + 3].tile_type = TileType::Water;
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 0)));
+        world.spawn((Position::new(2, 2), CausesBurning));
+        world.spawn((Position::new(4, 4), PlacedFireTrap { owner: player, burst_damage: 5 }));
+        world.spawn((Position::new(5, 5), PlacedFireTrap { owner: Entity::DANGLING, burst_damage: 5 }));
+        world.spawn((
+            Faction::Undead,
+            Position::new(10, 10),
+            Health::new(10),
+            ChaseAI::with_ranged(8, 2, 4),
+            Equipment::with_ranged(RangedWeapon::enemy_bow(3)),
+        ));
+        let cache = SpatialCache::rebuild_from_world(&world);
+
+        let costs = HazardCosts::new(&world, &grid, Some(player), CostProfile::CAUTIOUS);
+        for pos in [(2, 2), (3, 1), (4, 4), (5, 5), (6, 6), (1, 1)] {
+            let single = cost_at(&world, &grid, &cache, player, CostProfile::CAUTIOUS, pos);
+            assert_eq!(single, costs.cost(&grid, pos), "at {:?}", pos);
+        }
+    }
+}
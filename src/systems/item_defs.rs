@@ -6,7 +6,7 @@
 
 #![allow(dead_code)] // Fields reserved for future item system expansion
 
-use crate::components::{EffectType, ItemType};
+use crate::components::{ArmorSlot, EffectType, ItemType};
 use crate::constants::*;
 use crate::tile::{tile_ids, SpriteSheet};
 
@@ -14,6 +14,7 @@ use crate::tile::{tile_ids, SpriteSheet};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ItemCategory {
     Weapon,
+    Armor,
     Potion,
     Scroll,
     Food,
@@ -23,7 +24,7 @@ pub enum ItemCategory {
 /// How an item is used when consumed
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UseEffect {
-    /// Cannot be "used" - must be equipped (weapons)
+    /// Cannot be "used" - must be equipped (weapons, armor)
     Equip,
     /// Heals the user for the specified amount
     Heal(i32),
@@ -74,6 +75,22 @@ pub fn get_def(item: ItemType) -> &'static ItemDef {
         .expect("All ItemType variants must have a definition")
 }
 
+/// Defense and stat bonuses of a wearable armor piece or accessory
+pub struct ArmorDef {
+    pub item_type: ItemType,
+    pub slot: ArmorSlot,
+    /// Flat damage reduction applied to every incoming physical hit
+    pub defense: i32,
+    pub strength: i32,
+    pub intelligence: i32,
+    pub agility: i32,
+}
+
+/// Get the armor definition for an item type (None if it can't be worn)
+pub fn get_armor_def(item: ItemType) -> Option<&'static ArmorDef> {
+    ARMOR_DEFS.iter().find(|def| def.item_type == item)
+}
+
 /// Get the base price for an item type
 pub fn get_price(item: ItemType) -> u32 {
     get_def(item).base_price
@@ -134,6 +151,86 @@ pub static ITEM_DEFS: &[ItemDef] = &[
         base_price: 70,
     },
     // =========================================================================
+    // ARMOR & ACCESSORIES
+    // =========================================================================
+    ItemDef {
+        item_type: ItemType::LeatherArmor,
+        name: "Leather Armor",
+        category: ItemCategory::Armor,
+        weight: LEATHER_ARMOR_WEIGHT,
+        sprite: tile_ids::LEATHER_ARMOR,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 60,
+    },
+    ItemDef {
+        item_type: ItemType::ChainMail,
+        name: "Chain Mail",
+        category: ItemCategory::Armor,
+        weight: CHAIN_MAIL_WEIGHT,
+        sprite: tile_ids::CHAIN_MAIL,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 150,
+    },
+    ItemDef {
+        item_type: ItemType::Helmet,
+        name: "Helmet",
+        category: ItemCategory::Armor,
+        weight: HELMET_WEIGHT,
+        sprite: tile_ids::HELMET,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 50,
+    },
+    ItemDef {
+        item_type: ItemType::Shield,
+        name: "Shield",
+        category: ItemCategory::Armor,
+        weight: SHIELD_WEIGHT,
+        sprite: tile_ids::SHIELD,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 70,
+    },
+    ItemDef {
+        item_type: ItemType::RingOfStrength,
+        name: "Ring of Strength",
+        category: ItemCategory::Armor,
+        weight: RING_WEIGHT,
+        sprite: tile_ids::RUBY_RING,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 120,
+    },
+    ItemDef {
+        item_type: ItemType::RingOfAgility,
+        name: "Ring of Agility",
+        category: ItemCategory::Armor,
+        weight: RING_WEIGHT,
+        sprite: tile_ids::EMERALD_RING,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 120,
+    },
+    ItemDef {
+        item_type: ItemType::AmuletOfWisdom,
+        name: "Amulet of Wisdom",
+        category: ItemCategory::Armor,
+        weight: AMULET_WEIGHT,
+        sprite: tile_ids::AMULET,
+        use_effect: UseEffect::Equip,
+        targeting: None,
+        is_throwable: false,
+        base_price: 120,
+    },
+    // =========================================================================
     // POTIONS
     // =========================================================================
     ItemDef {
@@ -369,6 +466,66 @@ pub static ITEM_DEFS: &[ItemDef] = &[
     },
 ];
 
+/// Static table of wearable armor and accessories
+pub static ARMOR_DEFS: &[ArmorDef] = &[
+    ArmorDef {
+        item_type: ItemType::LeatherArmor,
+        slot: ArmorSlot::Body,
+        defense: LEATHER_ARMOR_DEFENSE,
+        strength: 0,
+        intelligence: 0,
+        agility: 0,
+    },
+    ArmorDef {
+        item_type: ItemType::ChainMail,
+        slot: ArmorSlot::Body,
+        defense: CHAIN_MAIL_DEFENSE,
+        strength: 0,
+        intelligence: 0,
+        agility: CHAIN_MAIL_AGILITY_PENALTY,
+    },
+    ArmorDef {
+        item_type: ItemType::Helmet,
+        slot: ArmorSlot::Head,
+        defense: HELMET_DEFENSE,
+        strength: 0,
+        intelligence: 0,
+        agility: 0,
+    },
+    ArmorDef {
+        item_type: ItemType::Shield,
+        slot: ArmorSlot::Shield,
+        defense: SHIELD_DEFENSE,
+        strength: 0,
+        intelligence: 0,
+        agility: 0,
+    },
+    ArmorDef {
+        item_type: ItemType::RingOfStrength,
+        slot: ArmorSlot::Ring,
+        defense: 0,
+        strength: RING_STAT_BONUS,
+        intelligence: 0,
+        agility: 0,
+    },
+    ArmorDef {
+        item_type: ItemType::RingOfAgility,
+        slot: ArmorSlot::Ring,
+        defense: 0,
+        strength: 0,
+        intelligence: 0,
+        agility: RING_STAT_BONUS,
+    },
+    ArmorDef {
+        item_type: ItemType::AmuletOfWisdom,
+        slot: ArmorSlot::Amulet,
+        defense: 0,
+        strength: 0,
+        intelligence: AMULET_STAT_BONUS,
+        agility: 0,
+    },
+];
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -399,6 +556,13 @@ mod tests {
             ItemType::Apple,
             ItemType::FireTrap,
             ItemType::Arrow,
+            ItemType::LeatherArmor,
+            ItemType::ChainMail,
+            ItemType::Helmet,
+            ItemType::Shield,
+            ItemType::RingOfStrength,
+            ItemType::RingOfAgility,
+            ItemType::AmuletOfWisdom,
         ];
 
         for item in all_items {
@@ -415,6 +579,14 @@ mod tests {
         assert!(matches!(get_def(ItemType::Staff).use_effect, UseEffect::Equip));
     }
 
+    #[test]
+    fn test_armor_category_matches_armor_defs() {
+        for def in ITEM_DEFS {
+            let wearable = get_armor_def(def.item_type).is_some();
+            assert_eq!(def.category == ItemCategory::Armor, wearable, "{}", def.name);
+        }
+    }
+
     #[test]
     fn test_potions_are_throwable() {
         assert!(get_def(ItemType::HealthPotion).is_throwable);
@@ -3,7 +3,7 @@
 use crate::components::{Health, Inventory, ItemType};
 use hecs::{Entity, World};
 
-use super::item_defs::{get_def, UseEffect};
+use super::item_defs::{get_armor_def, get_def, UseEffect};
 
 // Re-export TargetingParams from item_defs for external use
 pub use super::item_defs::TargetingParams;
@@ -19,6 +19,8 @@ pub enum ItemUseResult {
     RequiresTarget { item_type: ItemType, item_index: usize },
     /// Item is a weapon that should be equipped
     IsWeapon { item_type: ItemType, item_index: usize },
+    /// Item is armor or an accessory that should be worn
+    IsArmor { item_type: ItemType, item_index: usize },
     /// Scroll of Reveal: show all enemies on floor
     RevealEnemies,
     /// Scroll of Mapping: reveal entire floor layout
@@ -69,6 +71,9 @@ pub fn use_item(world: &mut World, entity: Entity, item_index: usize) -> ItemUse
     // Handle based on use effect from definition
     let result = match def.use_effect {
         UseEffect::Equip => {
+            if get_armor_def(item_type).is_some() {
+                return ItemUseResult::IsArmor { item_type, item_index };
+            }
             return ItemUseResult::IsWeapon { item_type, item_index };
         }
         UseEffect::RequiresTarget => {
@@ -5,29 +5,53 @@
 //! - `actions`: Action effect implementations (move, attack, etc.)
 //! - `ai`: AI decision-making and behavior
 //! - `animation`: Visual interpolation and animation updates
+//! - `behaviors`: Reusable enemy tactics (kiting, ambush, patrol, support)
 //! - `effects`: Status effect application
+//! - `examine`: Describing what the player knows about a tile (look mode)
+//! - `factions`: Faction relationships (who fights whom)
 //! - `experience`: XP, leveling, and stats calculations
+//! - `exploration`: Auto-explore planning and pickup rules
+//! - `hazards`: Hazard-aware path cost profiles
 //! - `items`: Item properties and utilities
 //! - `combat`: Damage, attacks, and death handling
 //! - `inventory`: Container and inventory interactions
+//! - `noise`: Noise propagation and hearing
+//! - `offscreen`: Coarse simulation of dormant enemies far from the player
+//! - `pack`: Group tactics (alerting allies, surrounding, holding back)
+//! - `perception`: Sleeping/unaware enemies noticing intruders
 //! - `rendering`: FOV, visibility, and render data collection
 //! - `projectile`: Arrow and projectile movement
+//! - `rest`: Resting until healed and waiting for a while
+//! - `running`: Shift-running along corridors and across rooms
+//! - `travel`: Travel commands (to the stairs, the shop, known items)
 
 pub mod action_dispatch;
 pub mod actions;
 pub mod ai;
 pub mod animation;
+pub mod behaviors;
 pub mod combat;
 pub mod dev_tools;
 pub mod dialogue;
 pub mod effects;
+pub mod examine;
 pub mod experience;
+pub mod exploration;
+pub mod factions;
+pub mod hazards;
 pub mod inventory;
 pub mod item_defs;
 pub mod items;
+pub mod noise;
+pub mod offscreen;
+pub mod pack;
+pub mod perception;
 pub mod player_input;
 pub mod projectile;
 pub mod rendering;
+pub mod rest;
+pub mod running;
+pub mod travel;
 
 // Re-export commonly used items
 pub use animation::{update_lunge_animations, visual_lerp};
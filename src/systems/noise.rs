@@ -0,0 +1,278 @@
+//! Noise and hearing.
+//!
+//! Loud events (footsteps, doors, fighting, bashing, explosions) spread outward through
+//! the grid, losing one point of loudness per open tile and much more through
+//! walls and closed doors. Enemies that aren't already chasing someone and hear
+//! a noise go to investigate where it came from.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use hecs::{Entity, World};
+
+use crate::components::{AbilityType, AIState, Awareness, ChaseAI, ContainerType, Health, ItemType, Sneaking};
+use crate::constants::*;
+use crate::events::{EventQueue, GameEvent};
+use crate::grid::Grid;
+use crate::spatial_cache::SpatialCache;
+use crate::tile::TileType;
+
+/// A sound made somewhere on the floor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Noise {
+    pub pos: (i32, i32),
+    /// How many open tiles away it can be heard
+    pub loudness: i32,
+}
+
+// =============================================================================
+// SOURCES
+// =============================================================================
+
+/// The noise an event makes, if any.
+/// Enemies moving about or using doors don't alert each other.
+pub fn noise_from_event(world: &World, event: &GameEvent) -> Option<Noise> {
+    let (pos, loudness) = match event {
+        GameEvent::EntityMoved { entity, to, .. } if !is_enemy(world, *entity) => (*to, footstep_loudness(world, *entity)),
+        GameEvent::AttackHit { target_pos, .. } | GameEvent::AttackMissed { target_pos, ranged: false, .. } => {
+            ((target_pos.0 as i32, target_pos.1 as i32), NOISE_COMBAT)
+        }
+        GameEvent::DoorOpened { opener: entity, position, .. } | GameEvent::DoorClosed { closer: entity, position, .. }
+            if !is_enemy(world, *entity) =>
+        {
+            (*position, NOISE_DOOR)
+        }
+        GameEvent::ContainerOpened { opener, container_type: Some(kind), position, .. }
+            if !is_enemy(world, *opener)
+                && matches!(kind, ContainerType::Chest | ContainerType::Coffin | ContainerType::Barrel) =>
+        {
+            (*position, NOISE_BASH)
+        }
+        GameEvent::FearActivated { position, .. } => (*position, NOISE_FEAR),
+        GameEvent::PotionSplash { x, y, .. } => ((*x, *y), NOISE_POTION_SHATTER),
+        GameEvent::FireballExplosion { x, y, .. } => ((*x, *y), NOISE_EXPLOSION),
+        _ => return None,
+    };
+    Some(Noise { pos, loudness })
+}
+
+/// How loud an entity's footsteps are right now
+pub fn footstep_loudness(world: &World, entity: Entity) -> i32 {
+    if world.get::<&Sneaking>(entity).is_ok() {
+        NOISE_SNEAK_FOOTSTEP
+    } else {
+        NOISE_FOOTSTEP
+    }
+}
+
+/// Loudness of using a class ability (for tooltips)
+pub fn ability_loudness(ability: AbilityType) -> i32 {
+    match ability {
+        AbilityType::Cleave => NOISE_COMBAT,
+        AbilityType::Fear => NOISE_FEAR,
+        AbilityType::Disengage | AbilityType::Tumble => NOISE_FOOTSTEP,
+        _ => 0,
+    }
+}
+
+/// Loudness of using an item, if it makes any noise (for tooltips)
+pub fn item_loudness(item_type: ItemType) -> Option<i32> {
+    match item_type {
+        ItemType::ScrollOfFireball => Some(NOISE_EXPLOSION),
+        ItemType::ScrollOfFear => Some(NOISE_FEAR),
+        _ if crate::systems::items::item_is_throwable(item_type) => Some(NOISE_POTION_SHATTER),
+        _ => None,
+    }
+}
+
+/// Tooltip line describing a loudness
+pub fn describe_loudness(loudness: i32) -> String {
+    let label = match loudness {
+        i32::MIN..=0 => return "Noise: silent".to_string(),
+        1..=2 => "quiet",
+        3..=5 => "noticeable",
+        6..=9 => "loud",
+        _ => "deafening",
+    };
+    format!("Noise: {} (heard {} tiles away)", label, loudness)
+}
+
+/// Switch between walking and sneaking
+pub fn toggle_sneak(world: &mut World, entity: Entity, events: &mut EventQueue) {
+    let sneaking = world.get::<&Sneaking>(entity).is_err();
+    if sneaking {
+        let _ = world.insert_one(entity, Sneaking);
+    } else {
+        let _ = world.remove_one::<Sneaking>(entity);
+    }
+    events.push(GameEvent::SneakToggled { entity, sneaking });
+}
+
+fn is_enemy(world: &World, entity: Entity) -> bool {
+    world.get::<&ChaseAI>(entity).is_ok()
+}
+
+// =============================================================================
+// PROPAGATION
+// =============================================================================
+
+/// Loudness left at every tile a noise reaches.
+/// Spreads like a flood fill, paying extra to pass walls and closed doors.
+/// `is_closed_door` is only asked about doorway tiles the noise actually reaches.
+pub fn propagate(grid: &Grid, is_closed_door: impl Fn((i32, i32)) -> bool, noise: Noise) -> HashMap<(i32, i32), i32> {
+    let mut heard: HashMap<(i32, i32), i32> = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+    heard.insert(noise.pos, noise.loudness);
+    frontier.push((noise.loudness, Reverse(noise.pos)));
+
+    while let Some((remaining, Reverse(pos))) = frontier.pop() {
+        if heard.get(&pos).is_some_and(|&best| best > remaining) {
+            continue;
+        }
+        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+            let next = (pos.0 + dx, pos.1 + dy);
+            let Some(tile) = grid.get(next.0, next.1) else { continue };
+            let cost = match tile.tile_type {
+                TileType::Empty => continue,
+                TileType::Wall => NOISE_WALL_DAMPENING,
+                _ if tile.doorway && is_closed_door(next) => NOISE_DOOR_DAMPENING,
+                _ => 1,
+            };
+            let left = remaining - cost;
+            if left < 0 || heard.get(&next).is_some_and(|&best| best >= left) {
+                continue;
+            }
+            heard.insert(next, left);
+            frontier.push((left, Reverse(next)));
+        }
+    }
+
+    heard
+}
+
+/// Let every enemy in earshot react to a noise.
+/// Enemies already chasing ignore it; idle ones start investigating, and
+/// loud enough noises wake sleepers (who are still unaware of who made it).
+pub fn alert_listeners(world: &World, grid: &Grid, spatial_cache: &SpatialCache, noise: Noise, events: &mut EventQueue) {
+    // Closed doors block vision; open ones don't
+    let heard = propagate(grid, |pos| spatial_cache.blocks_vision(pos), noise);
+
+    // Every tile the noise reaches is within `loudness` steps of where it was made
+    for (id, pos) in spatial_cache.entities_within(noise.pos, noise.loudness) {
+        let Some(&remaining) = heard.get(&pos) else { continue };
+        let Ok(mut ai) = world.get::<&mut ChaseAI>(id) else { continue };
+        let alive = world.get::<&Health>(id).is_ok_and(|health| health.current > 0);
+        if !alive || ai.state == AIState::Chasing {
+            continue;
+        }
+        // Sleepers only wake for noises that are still loud when they reach them
+        if ai.awareness == Awareness::Asleep {
+            if remaining < NOISE_WAKE_THRESHOLD {
+                continue;
+            }
+            ai.awareness = Awareness::Unaware;
+        }
+        ai.heard_noise = Some(noise.pos);
+        if ai.state == AIState::Idle {
+            ai.state = AIState::Investigating;
+            events.push(GameEvent::AIStateChanged {
+                entity: id,
+                new_state: AIState::Investigating,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Position;
+    use crate::tile::Tile;
+
+    /// 20x5 floor split by a wall at x=10
+    fn make_walled_grid() -> Grid {
+        let mut grid = Grid::filled(20, 5, TileType::Floor);
+        for y in 0..5 {
+            grid.tiles[y * 20 + 10] = Tile::new(TileType::Wall);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_noise_fades_with_distance() {
+        let grid = Grid::filled(20, 5, TileType::Floor);
+        let heard = propagate(&grid, |_| false, Noise { pos: (2, 2), loudness: 5 });
+        assert_eq!(heard.get(&(2, 2)), Some(&5));
+        assert_eq!(heard.get(&(5, 2)), Some(&2));
+        assert_eq!(heard.get(&(7, 2)), Some(&0));
+        assert!(!heard.contains_key(&(8, 2)));
+    }
+
+    #[test]
+    fn test_walls_and_closed_doors_dampen_noise() {
+        let grid = make_walled_grid();
+        // Heard 2 tiles past an open floor, but not through the wall
+        let quiet = Noise { pos: (8, 2), loudness: 4 };
+        assert!(!propagate(&grid, |_| false, quiet).contains_key(&(11, 2)));
+        let loud = Noise { pos: (8, 2), loudness: 12 };
+        assert_eq!(propagate(&grid, |_| false, loud).get(&(11, 2)), Some(&(12 - 2 - NOISE_WALL_DAMPENING)));
+
+        // A closed door in the wall
+        let mut doorway = make_walled_grid();
+        doorway.tiles[2 * 20 + 10] = Tile::new(TileType::Floor);
+        doorway.tiles[2 * 20 + 10].doorway = true;
+        let through_door = propagate(&doorway, |pos| pos == (10, 2), Noise { pos: (9, 2), loudness: 6 });
+        assert_eq!(through_door.get(&(11, 2)), Some(&(6 - NOISE_DOOR_DAMPENING - 1)));
+    }
+
+    #[test]
+    fn test_idle_enemy_investigates_noise() {
+        let grid = make_walled_grid();
+        let mut world = World::new();
+        let near = world.spawn((Position::new(6, 2), ChaseAI::new(8), Health::new(10)));
+        let behind_wall = world.spawn((Position::new(14, 2), ChaseAI::new(8), Health::new(10)));
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let mut events = EventQueue::new();
+
+        alert_listeners(&world, &grid, &cache, Noise { pos: (3, 2), loudness: NOISE_COMBAT }, &mut events);
+
+        let ai = world.get::<&ChaseAI>(near).unwrap();
+        assert_eq!(ai.state, AIState::Investigating);
+        assert_eq!(ai.heard_noise, Some((3, 2)));
+        assert_eq!(world.get::<&ChaseAI>(behind_wall).unwrap().state, AIState::Idle);
+        assert_eq!(events.drain().count(), 1);
+    }
+
+    #[test]
+    fn test_only_loud_noises_wake_sleepers() {
+        let grid = Grid::filled(20, 5, TileType::Floor);
+        let mut world = World::new();
+        let mut ai = ChaseAI::new(8);
+        ai.awareness = Awareness::Asleep;
+        let sleeper = world.spawn((Position::new(6, 2), ai, Health::new(10)));
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let mut events = EventQueue::new();
+
+        // Reaches the sleeper with almost nothing left
+        let faint = Noise { pos: (2, 2), loudness: 4 + NOISE_WAKE_THRESHOLD - 1 };
+        alert_listeners(&world, &grid, &cache, faint, &mut events);
+        assert_eq!(world.get::<&ChaseAI>(sleeper).unwrap().awareness, Awareness::Asleep);
+
+        alert_listeners(&world, &grid, &cache, Noise { pos: (2, 2), loudness: NOISE_COMBAT }, &mut events);
+        let ai = world.get::<&ChaseAI>(sleeper).unwrap();
+        assert_eq!(ai.awareness, Awareness::Unaware);
+        assert_eq!(ai.state, AIState::Investigating);
+    }
+
+    #[test]
+    fn test_sneaking_is_quieter() {
+        let mut world = World::new();
+        let walker = world.spawn((Position::new(0, 0),));
+        let sneaker = world.spawn((Position::new(0, 0), Sneaking));
+        assert!(footstep_loudness(&world, sneaker) < footstep_loudness(&world, walker));
+        // Enemies' own footsteps don't count as noise
+        let enemy = world.spawn((Position::new(0, 0), ChaseAI::new(8)));
+        let step = GameEvent::EntityMoved { entity: enemy, from: (0, 0), to: (1, 0) };
+        assert!(noise_from_event(&world, &step).is_none());
+    }
+}
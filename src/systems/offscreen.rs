@@ -0,0 +1,410 @@
+//! Coarse off-screen simulation for dormant enemies.
+//!
+//! Enemies beyond `AI_ACTIVE_RADIUS` are not scheduled. Instead, every
+//! `OFFSCREEN_TICK_INTERVAL` game seconds a small batch of them is moved
+//! several tiles at once:
+//! - hunters (chasing someone on the player's side) follow the player's trail
+//! - enemies investigating a noise walk to where it came from
+//! - idle enemies drift between rooms or regroup with nearby allies
+//!
+//! At most `OFFSCREEN_ACTORS_PER_TICK` actors are updated per tick, every
+//! path search is node-limited and only checks the tiles it visits for
+//! obstacles, and allies are found with a radius lookup in the spatial cache,
+//! so the cost stays flat however big the floor is and however many enemies it holds.
+
+use std::collections::VecDeque;
+
+use hecs::{Entity, World};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::active_ai_tracker::ActiveAITracker;
+use crate::components::{AIState, Awareness, Behavior, ChaseAI, CompanionAI, Door, Faction, Position, Tactic, VisualPosition};
+use crate::constants::*;
+use crate::grid::Grid;
+use crate::pathfinding;
+use crate::queries;
+use crate::spatial_cache::SpatialCache;
+use crate::systems::behaviors::chebyshev;
+use crate::systems::factions;
+
+/// What a dormant enemy is doing this update
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Errand {
+    /// Following the trail of a target, last seen at the given position
+    Hunt { last_known: Option<(i32, i32)> },
+    /// Walking to a noise it heard
+    Investigate { noise: (i32, i32) },
+    /// Nothing in particular
+    Roam,
+}
+
+/// The parts of a coarse tick shared by every actor in the batch
+struct Tick<'a> {
+    grid: &'a Grid,
+    player_pos: (i32, i32),
+    time: f32,
+}
+
+/// Run one coarse update if one is due. Called after each player action.
+pub fn simulate(
+    world: &mut World,
+    grid: &Grid,
+    player_pos: (i32, i32),
+    time: f32,
+    tracker: &mut ActiveAITracker,
+    spatial_cache: &mut SpatialCache,
+    rng: &mut impl Rng,
+) {
+    puffin::profile_function!();
+
+    if !tracker.offscreen_tick_due(time, OFFSCREEN_TICK_INTERVAL) {
+        return;
+    }
+
+    let batch = tracker.next_offscreen_batch(OFFSCREEN_ACTORS_PER_TICK);
+    if batch.is_empty() {
+        return;
+    }
+
+    let tick = Tick { grid, player_pos, time };
+    for entity in batch {
+        update_actor(world, &tick, entity, tracker, spatial_cache, rng);
+    }
+}
+
+/// Move one dormant enemy for the time since it was last simulated
+fn update_actor(
+    world: &mut World,
+    tick: &Tick,
+    entity: Entity,
+    tracker: &mut ActiveAITracker,
+    spatial_cache: &mut SpatialCache,
+    rng: &mut impl Rng,
+) {
+    let time = tick.time;
+    let Some(pos) = queries::get_entity_position(world, entity) else { return };
+    let Some(errand) = current_errand(world, entity) else { return };
+
+    let roam = *tracker.roam_state(entity, time - OFFSCREEN_TICK_INTERVAL);
+    let steps = (((time - roam.last_update) * OFFSCREEN_TILES_PER_SECOND) as usize).min(OFFSCREEN_MAX_STEPS);
+    if steps == 0 {
+        return;
+    }
+
+    let mut goal = roam.goal;
+    match errand {
+        Errand::Hunt { last_known } => {
+            let target = scent_goal(tracker.player_trail(), pos).filter(|&g| g != pos).or(last_known);
+            match target {
+                Some(target) if target != pos => {
+                    if travel(world, tick, spatial_cache, entity, pos, target, steps).is_none() {
+                        lose_target(world, entity);
+                    }
+                }
+                // Trail gone cold
+                _ => lose_target(world, entity),
+            }
+        }
+        Errand::Investigate { noise } => {
+            let arrived = travel(world, tick, spatial_cache, entity, pos, noise, steps)
+                .map(|new_pos| chebyshev(new_pos, noise) <= 1)
+                .unwrap_or(true);
+            if arrived {
+                if let Ok(mut chase) = world.get::<&mut ChaseAI>(entity) {
+                    chase.heard_noise = None;
+                    chase.state = AIState::Idle;
+                }
+            }
+        }
+        Errand::Roam => {
+            if goal.is_none() && rng.gen::<f32>() < OFFSCREEN_ROAM_CHANCE {
+                goal = pick_roam_goal(world, tick.grid, spatial_cache, entity, pos, rng);
+            }
+            if let Some(target) = goal {
+                let arrived = travel(world, tick, spatial_cache, entity, pos, target, steps)
+                    .map(|new_pos| chebyshev(new_pos, target) <= 1)
+                    .unwrap_or(true);
+                if arrived {
+                    goal = None;
+                }
+            }
+        }
+    }
+
+    let state = tracker.roam_state(entity, time);
+    state.goal = goal;
+    state.last_update = time;
+}
+
+/// Decide what a dormant enemy is up to. Sleepers, ambushers and patrols stay put.
+fn current_errand(world: &World, entity: Entity) -> Option<Errand> {
+    let chase = world.get::<&ChaseAI>(entity).ok()?;
+    if chase.awareness == Awareness::Asleep {
+        return None;
+    }
+
+    if chase.state != AIState::Idle {
+        if let Some(target) = chase.current_target {
+            if factions::faction_of(world, target) == Some(Faction::Player) {
+                return Some(Errand::Hunt { last_known: chase.last_known_pos_for(target) });
+            }
+        }
+        if let Some(noise) = chase.heard_noise {
+            return Some(Errand::Investigate { noise });
+        }
+    }
+
+    let holds_post = world.get::<&Behavior>(entity).is_ok_and(|behavior| {
+        behavior
+            .tactics
+            .iter()
+            .any(|t| matches!(t, Tactic::Ambush { .. } | Tactic::Patrol { .. }))
+    });
+    (!holds_post).then_some(Errand::Roam)
+}
+
+/// The newest point of the player's trail close enough to pick up the scent
+fn scent_goal(trail: &VecDeque<(i32, i32)>, pos: (i32, i32)) -> Option<(i32, i32)> {
+    trail.iter().rev().copied().find(|&p| manhattan(p, pos) <= OFFSCREEN_SCENT_RADIUS)
+}
+
+/// A hunter that lost the trail goes back to idling
+fn lose_target(world: &World, entity: Entity) {
+    if let Ok(mut chase) = world.get::<&mut ChaseAI>(entity) {
+        chase.state = AIState::Idle;
+        chase.current_target = None;
+    }
+}
+
+/// Head for a nearby ally (regroup) or the middle of a nearby room
+fn pick_roam_goal(
+    world: &World,
+    grid: &Grid,
+    spatial_cache: &SpatialCache,
+    entity: Entity,
+    pos: (i32, i32),
+    rng: &mut impl Rng,
+) -> Option<(i32, i32)> {
+    if rng.gen::<f32>() < OFFSCREEN_REGROUP_CHANCE {
+        if let Some(ally) = nearest_ally(world, spatial_cache, entity, pos) {
+            return Some(ally);
+        }
+    }
+
+    let rooms: Vec<(i32, i32)> = grid
+        .themed_rooms
+        .iter()
+        .map(|room| room.rect.center())
+        .filter(|&center| center != pos && manhattan(center, pos) <= OFFSCREEN_ROAM_RADIUS)
+        .filter(|&(x, y)| grid.get(x, y).is_some_and(|tile| tile.tile_type.is_walkable()))
+        .collect();
+    rooms.choose(rng).copied()
+}
+
+/// Position of the closest allied enemy within `OFFSCREEN_ROAM_RADIUS` (not already adjacent).
+/// Searches growing squares so crowded floors stop at the first ring holding one.
+fn nearest_ally(world: &World, spatial_cache: &SpatialCache, entity: Entity, pos: (i32, i32)) -> Option<(i32, i32)> {
+    let mut radius = OFFSCREEN_REGROUP_FIRST_RADIUS;
+    loop {
+        let radius_now = radius.min(OFFSCREEN_ROAM_RADIUS);
+        let ally = spatial_cache
+            .entities_within(pos, radius_now)
+            .filter(|&(other, p)| {
+                other != entity
+                    && chebyshev(p, pos) > 1
+                    && world.get::<&ChaseAI>(other).is_ok()
+                    && factions::is_allied(world, entity, other)
+            })
+            .map(|(_, p)| p)
+            .min_by_key(|&p| manhattan(p, pos));
+        // Anything outside the square is further than `radius_now` steps away
+        match ally {
+            Some(p) if manhattan(p, pos) <= radius_now => return Some(p),
+            _ if radius_now >= OFFSCREEN_ROAM_RADIUS => return None,
+            _ => radius *= 2,
+        }
+    }
+}
+
+/// Walk up to `steps` tiles toward `goal`, stopping early on reaching the
+/// player's active radius (the regular AI takes over from there).
+/// Returns the new position, or None if the goal can't be reached.
+fn travel(
+    world: &mut World,
+    tick: &Tick,
+    spatial_cache: &mut SpatialCache,
+    entity: Entity,
+    pos: (i32, i32),
+    goal: (i32, i32),
+    steps: usize,
+) -> Option<(i32, i32)> {
+    let path = {
+        let (world, spatial_cache): (&World, &SpatialCache) = (world, spatial_cache);
+        let is_blocked = |tile| is_obstacle(world, spatial_cache, tile);
+        pathfinding::find_path_limited(tick.grid, pos, goal, &is_blocked, OFFSCREEN_PATH_NODE_LIMIT)?
+    };
+
+    let mut stop = pos;
+    for &tile in path.iter().take(steps) {
+        // Pass through allies and doors, but only stop on a free tile
+        if !spatial_cache.is_blocked(tile) {
+            stop = tile;
+        }
+        if manhattan(tile, tick.player_pos) <= AI_ACTIVE_RADIUS {
+            break;
+        }
+    }
+
+    if stop != pos {
+        move_entity(world, spatial_cache, entity, pos, stop);
+    }
+    Some(stop)
+}
+
+/// Whether something the enemy can't pass through stands on `tile`.
+/// Other enemies, companions and closed doors don't stop a path.
+fn is_obstacle(world: &World, spatial_cache: &SpatialCache, tile: (i32, i32)) -> bool {
+    spatial_cache.is_blocked(tile)
+        && !spatial_cache.entities_at(tile).any(|other| {
+            world.get::<&ChaseAI>(other).is_ok()
+                || world.get::<&CompanionAI>(other).is_ok()
+                || world.get::<&Door>(other).is_ok()
+        })
+}
+
+/// Put an entity somewhere else without animating the move
+fn move_entity(world: &mut World, spatial_cache: &mut SpatialCache, entity: Entity, from: (i32, i32), to: (i32, i32)) {
+    if let Ok(mut pos) = world.get::<&mut Position>(entity) {
+        pos.x = to.0;
+        pos.y = to.1;
+    }
+    if let Ok(mut visual) = world.get::<&mut VisualPosition>(entity) {
+        visual.x = to.0 as f32;
+        visual.y = to.1 as f32;
+    }
+    spatial_cache.update_position(entity, from, to);
+}
+
+fn manhattan(a: (i32, i32), b: (i32, i32)) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::components::{BlocksMovement, Player};
+    use crate::dungeon_gen::{Rect, RoomTheme, ThemedRoom};
+    use crate::tile::TileType;
+
+    fn spawn_enemy(world: &mut World, spatial_cache: &mut SpatialCache, pos: (i32, i32)) -> Entity {
+        let entity = world.spawn((
+            Position::new(pos.0, pos.1),
+            VisualPosition { x: pos.0 as f32, y: pos.1 as f32 },
+            ChaseAI::new(8),
+            Faction::Undead,
+            BlocksMovement,
+        ));
+        spatial_cache.register_entity(entity, pos, true, false);
+        entity
+    }
+
+    #[test]
+    fn test_hunter_follows_player_trail() {
+        let grid = Grid::filled(80, 5, TileType::Floor);
+        let mut world = World::new();
+        let mut spatial_cache = SpatialCache::new();
+        let player = world.spawn((Player, Position::new(70, 2)));
+        let hunter = spawn_enemy(&mut world, &mut spatial_cache, (2, 2));
+        {
+            let mut chase = world.get::<&mut ChaseAI>(hunter).unwrap();
+            chase.state = AIState::Investigating;
+            chase.current_target = Some(player);
+        }
+
+        let mut tracker = ActiveAITracker::new();
+        tracker.initialize_from_world(&world, (70, 2));
+        assert!(tracker.is_dormant(hunter));
+        for x in 5..=70 {
+            tracker.record_player_position((x, 2));
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut time = 0.0;
+        for _ in 0..60 {
+            time += OFFSCREEN_TICK_INTERVAL;
+            simulate(&mut world, &grid, (70, 2), time, &mut tracker, &mut spatial_cache, &mut rng);
+        }
+
+        // Walks along the trail until the regular AI can take over
+        let pos = queries::get_entity_position(&world, hunter).unwrap();
+        assert!(manhattan(pos, (70, 2)) <= AI_ACTIVE_RADIUS, "hunter stuck at {:?}", pos);
+        assert_eq!(pos.1, 2);
+        assert_eq!(world.get::<&ChaseAI>(hunter).unwrap().current_target, Some(player));
+    }
+
+    #[test]
+    fn test_sleepers_stay_put_and_cost_is_bounded() {
+        let grid = Grid::filled(100, 100, TileType::Floor);
+        let mut world = World::new();
+        let mut spatial_cache = SpatialCache::new();
+        let mut enemies = Vec::new();
+        for i in 0..100 {
+            let entity = spawn_enemy(&mut world, &mut spatial_cache, (40 + (i % 10) * 5, 40 + (i / 10) * 5));
+            world.get::<&mut ChaseAI>(entity).unwrap().awareness = Awareness::Asleep;
+            enemies.push(entity);
+        }
+        let mut tracker = ActiveAITracker::new();
+        tracker.initialize_from_world(&world, (0, 0));
+
+        // One tick only visits a bounded batch
+        assert_eq!(tracker.next_offscreen_batch(OFFSCREEN_ACTORS_PER_TICK).len(), OFFSCREEN_ACTORS_PER_TICK);
+
+        let mut rng = rand::thread_rng();
+        for tick in 1..=20 {
+            simulate(&mut world, &grid, (0, 0), tick as f32 * OFFSCREEN_TICK_INTERVAL, &mut tracker, &mut spatial_cache, &mut rng);
+        }
+        for (i, &entity) in enemies.iter().enumerate() {
+            let i = i as i32;
+            assert_eq!(queries::get_entity_position(&world, entity), Some((40 + (i % 10) * 5, 40 + (i / 10) * 5)));
+        }
+    }
+
+    #[test]
+    fn test_roamers_move_without_overlapping() {
+        let mut grid = Grid::filled(100, 100, TileType::Floor);
+        grid.themed_rooms = vec![
+            ThemedRoom { rect: Rect::new(35, 55, 10, 10), theme: RoomTheme::Normal },
+            ThemedRoom { rect: Rect::new(55, 35, 10, 10), theme: RoomTheme::Normal },
+        ];
+        let mut world = World::new();
+        let mut spatial_cache = SpatialCache::new();
+        let mut enemies = Vec::new();
+        for i in 0..20 {
+            enemies.push(spawn_enemy(&mut world, &mut spatial_cache, (60 + i % 5, 60 + i / 5)));
+        }
+        let mut tracker = ActiveAITracker::new();
+        tracker.initialize_from_world(&world, (0, 0));
+
+        let mut rng = rand::thread_rng();
+        for tick in 1..=60 {
+            simulate(&mut world, &grid, (0, 0), tick as f32 * OFFSCREEN_TICK_INTERVAL, &mut tracker, &mut spatial_cache, &mut rng);
+        }
+
+        let positions: HashSet<(i32, i32)> =
+            enemies.iter().filter_map(|&e| queries::get_entity_position(&world, e)).collect();
+        let moved = enemies
+            .iter()
+            .enumerate()
+            .filter(|&(i, &e)| {
+                let i = i as i32;
+                queries::get_entity_position(&world, e) != Some((60 + i % 5, 60 + i / 5))
+            })
+            .count();
+        assert!(moved > 0);
+        assert_eq!(positions.len(), enemies.len(), "two enemies ended up on the same tile");
+        assert_eq!(spatial_cache.get_blocking_positions(), &positions);
+    }
+}
@@ -0,0 +1,271 @@
+//! Group tactics shared by all enemies.
+//!
+//! Individual enemies still pick their own targets, but they take their
+//! allies (friendly factions) into account:
+//! - an enemy that starts chasing alerts idle (or sleeping) allies nearby
+//! - melee enemies spread out over the free tiles around the target
+//! - ranged enemies stay behind the melee line
+//! - badly wounded enemies fall back to the rest of the pack
+
+use std::collections::HashSet;
+
+use hecs::{Entity, World};
+
+use crate::components::{ActionType, AIState, Awareness, ChaseAI, Health};
+use crate::constants::*;
+use crate::events::{EventQueue, GameEvent};
+use crate::spatial_cache::SpatialCache;
+use crate::systems::ai::has_clear_shot;
+use crate::systems::behaviors::{allies_within, chebyshev, move_action, step_away, TacticContext};
+use crate::systems::hazards;
+
+/// Wake up allies near an enemy that just spotted `target`.
+/// Idle allies start investigating the target's position.
+pub fn alert_allies(
+    world: &World,
+    spatial_cache: &SpatialCache,
+    entity: Entity,
+    pos: (i32, i32),
+    target: Entity,
+    target_pos: (i32, i32),
+    events: &mut EventQueue,
+) {
+    let allies: Vec<Entity> = allies_within(world, spatial_cache, entity, pos, PACK_ALERT_RADIUS)
+        .filter(|&(id, _)| world.get::<&ChaseAI>(id).is_ok_and(|ai| ai.state != AIState::Chasing))
+        .map(|(id, _)| id)
+        .collect();
+
+    for ally in allies {
+        let Ok(mut ai) = world.get::<&mut ChaseAI>(ally) else { continue };
+        ai.add_threat(target, PACK_ALERT_THREAT);
+        ai.update_target_pos(target, target_pos);
+        ai.awareness = Awareness::Alert;
+        if ai.state == AIState::Idle {
+            ai.state = AIState::Investigating;
+            events.push(GameEvent::AIStateChanged {
+                entity: ally,
+                new_state: AIState::Investigating,
+            });
+        }
+    }
+}
+
+/// Badly wounded: fall back to the pack member furthest from the fight.
+/// Once there, hold unless the target follows into melee range.
+pub fn retreat(ctx: &TacticContext, target_pos: (i32, i32)) -> Option<ActionType> {
+    let wounded = ctx
+        .world
+        .get::<&Health>(ctx.entity)
+        .map(|h| (h.current as f32) < h.max as f32 * PACK_RETREAT_HEALTH)
+        .unwrap_or(false);
+    if !wounded {
+        return None;
+    }
+
+    let distance = chebyshev(ctx.pos, target_pos);
+    let rally = allies_within(ctx.world, ctx.spatial_cache, ctx.entity, ctx.pos, PACK_REGROUP_RADIUS)
+        .filter(|&(id, _)| ctx.world.get::<&ChaseAI>(id).is_ok())
+        .map(|(_, pos)| pos)
+        .max_by_key(|&pos| chebyshev(pos, target_pos))
+        .filter(|&pos| chebyshev(pos, target_pos) > distance)?;
+
+    if chebyshev(ctx.pos, rally) <= 1 {
+        return if distance <= 1 { None } else { Some(ActionType::Wait) };
+    }
+    hazards::next_step_for(ctx.world, ctx.grid, ctx.entity, ctx.pos, rally, ctx.path_blocked).map(|step| move_action(ctx, step))
+}
+
+/// Melee: head for a free tile around the target that no ally is heading for,
+/// routing around allies instead of queueing behind them.
+/// Only allies within `radius` of the target that are chasing it count.
+pub fn surround_step(ctx: &TacticContext, target: Entity, target_pos: (i32, i32), radius: i32) -> Option<ActionType> {
+    if chebyshev(ctx.pos, target_pos) <= 1 {
+        return None;
+    }
+
+    let allies: Vec<(i32, i32)> = allies_within(ctx.world, ctx.spatial_cache, ctx.entity, target_pos, radius)
+        .filter(|&(id, _)| ctx.world.get::<&ChaseAI>(id).is_ok_and(|ai| chasing(&ai, target)))
+        .map(|(_, pos)| pos)
+        .collect();
+
+    let slots: Vec<(i32, i32)> = (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| (target_pos.0 + dx, target_pos.1 + dy)))
+        .filter(|&tile| tile != target_pos && ctx.grid.is_walkable(tile.0, tile.1) && !ctx.blocked.contains(&tile))
+        .collect();
+
+    // Each approaching ally is assumed to take the free slot nearest to it
+    let claimed: HashSet<(i32, i32)> = allies
+        .iter()
+        .filter(|&&ally| chebyshev(ally, target_pos) > 1)
+        .filter_map(|&ally| slots.iter().copied().min_by_key(|&slot| chebyshev(ally, slot)))
+        .collect();
+    let slot = slots
+        .iter()
+        .copied()
+        .min_by_key(|slot| (claimed.contains(slot), chebyshev(ctx.pos, *slot)))?;
+
+    let mut blocked: HashSet<(i32, i32)> = ctx.path_blocked.clone();
+    blocked.extend(allies.iter().copied());
+    hazards::next_step_for(ctx.world, ctx.grid, ctx.entity, ctx.pos, slot, &blocked).map(|step| move_action(ctx, step))
+}
+
+/// Ranged: stay behind the closest melee ally fighting the same target,
+/// sidestepping for a clear shot.
+/// Returns None when there is no melee line to hide behind.
+pub fn hold_back(ctx: &TacticContext, target: Entity, target_pos: (i32, i32), range: i32) -> Option<ActionType> {
+    let front = allies_within(ctx.world, ctx.spatial_cache, ctx.entity, ctx.pos, PACK_REGROUP_RADIUS)
+        .filter(|&(id, _)| {
+            ctx.world
+                .get::<&ChaseAI>(id)
+                .is_ok_and(|ai| ai.ranged_max == 0 && chasing(&ai, target))
+        })
+        .map(|(_, pos)| chebyshev(pos, target_pos))
+        .min()?;
+
+    let line = front + 1;
+    let distance = chebyshev(ctx.pos, target_pos);
+    if distance < line {
+        return step_away(ctx, target_pos);
+    }
+    if distance > range {
+        // Close in, but not past the line
+        return if distance > line { None } else { Some(ActionType::Wait) };
+    }
+    if has_clear_shot(ctx.pos, target_pos, ctx.blocked) {
+        // In position, waiting for the bow to be ready
+        return Some(ActionType::Wait);
+    }
+
+    let firing_lane = (-1..=1)
+        .flat_map(|dy| (-1..=1).map(move |dx| (ctx.pos.0 + dx, ctx.pos.1 + dy)))
+        .filter(|&tile| {
+            let d = chebyshev(tile, target_pos);
+            tile != ctx.pos
+                && ctx.grid.is_walkable(tile.0, tile.1)
+                && !ctx.blocked.contains(&tile)
+                && (line..=range).contains(&d)
+                && has_clear_shot(tile, target_pos, ctx.blocked)
+        })
+        .min_by_key(|&tile| chebyshev(tile, target_pos));
+    Some(firing_lane.map(|tile| move_action(ctx, tile)).unwrap_or(ActionType::Wait))
+}
+
+fn chasing(ai: &ChaseAI, target: Entity) -> bool {
+    ai.state == AIState::Chasing && ai.current_target == Some(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{Faction, Position};
+    use crate::grid::Grid;
+    use crate::systems::behaviors::test_support::{ctx, spawn_enemy};
+    use crate::tile::TileType;
+
+    fn spawn_chaser(world: &mut World, x: i32, y: i32, target: Entity) -> Entity {
+        let enemy = spawn_enemy(world, x, y, AIState::Chasing, Faction::Undead);
+        world.get::<&mut ChaseAI>(enemy).unwrap().current_target = Some(target);
+        enemy
+    }
+
+    fn blocked_by_entities(world: &World) -> HashSet<(i32, i32)> {
+        world.query::<&Position>().iter().map(|(_, p)| (p.x, p.y)).collect()
+    }
+
+    #[test]
+    fn test_alert_wakes_idle_allies_in_radius() {
+        let mut world = World::new();
+        let player = world.spawn((Position::new(0, 0),));
+        let spotter = spawn_enemy(&mut world, 5, 5, AIState::Chasing, Faction::Undead);
+        let near = spawn_enemy(&mut world, 7, 5, AIState::Idle, Faction::Undead);
+        let far = spawn_enemy(&mut world, 5 + PACK_ALERT_RADIUS + 1, 5, AIState::Idle, Faction::Undead);
+        let cache = SpatialCache::rebuild_from_world(&world);
+        let mut events = EventQueue::new();
+
+        alert_allies(&world, &cache, spotter, (5, 5), player, (0, 0), &mut events);
+
+        let near_ai = world.get::<&ChaseAI>(near).unwrap();
+        assert_eq!(near_ai.state, AIState::Investigating);
+        assert_eq!(near_ai.last_known_pos_for(player), Some((0, 0)));
+        assert_eq!(world.get::<&ChaseAI>(far).unwrap().state, AIState::Idle);
+        let alerted: Vec<_> = events
+            .drain()
+            .filter_map(|e| match e {
+                GameEvent::AIStateChanged { entity, new_state: AIState::Investigating } => Some(entity),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(alerted, vec![near]);
+    }
+
+    #[test]
+    fn test_surround_takes_a_different_side() {
+        let grid = Grid::filled(12, 12, TileType::Floor);
+        let mut world = World::new();
+        let target = world.spawn((Position::new(5, 5),));
+        // An ally already heading for the west side of the target
+        spawn_chaser(&mut world, 3, 5, target);
+        let flanker = spawn_chaser(&mut world, 2, 5, target);
+        let blocked = blocked_by_entities(&world);
+        let cache = SpatialCache::rebuild_from_world(&world);
+
+        let action = surround_step(&ctx(&world, &grid, flanker, &blocked, &cache), target, (5, 5), 8);
+        // Routes around the ally in front (diagonally) instead of queueing behind it
+        assert!(matches!(action, Some(ActionType::Move { dx: 1, dy: -1 | 1, .. })));
+    }
+
+    #[test]
+    fn test_surround_ignores_allies_chasing_someone_else() {
+        let grid = Grid::filled(12, 12, TileType::Floor);
+        let mut world = World::new();
+        let target = world.spawn((Position::new(5, 5),));
+        let other = world.spawn((Position::new(11, 11),));
+        // Next to the north-west slot, but busy with a different fight
+        let ally = spawn_chaser(&mut world, 3, 3, other);
+        let flanker = spawn_chaser(&mut world, 6, 2, target);
+        let blocked = blocked_by_entities(&world);
+        let cache = SpatialCache::rebuild_from_world(&world);
+
+        // The north-west slot is still free for the taking
+        let action = surround_step(&ctx(&world, &grid, flanker, &blocked, &cache), target, (5, 5), 8);
+        assert!(matches!(action, Some(ActionType::Move { dx: -1, dy: 1, .. })));
+
+        // Once it turns on the same target it claims that slot
+        world.get::<&mut ChaseAI>(ally).unwrap().current_target = Some(target);
+        let action = surround_step(&ctx(&world, &grid, flanker, &blocked, &cache), target, (5, 5), 8);
+        assert!(matches!(action, Some(ActionType::Move { dx: 0, dy: 1, .. })));
+    }
+
+    #[test]
+    fn test_archer_holds_behind_melee_line() {
+        let grid = Grid::filled(12, 12, TileType::Floor);
+        let mut world = World::new();
+        let target = world.spawn((Position::new(1, 5),));
+        spawn_chaser(&mut world, 3, 5, target);
+        let archer = spawn_chaser(&mut world, 3, 6, target);
+        let blocked = blocked_by_entities(&world);
+        let cache = SpatialCache::rebuild_from_world(&world);
+
+        // Level with the melee ally: back off behind it
+        let action = hold_back(&ctx(&world, &grid, archer, &blocked, &cache), target, (1, 5), 8);
+        assert!(matches!(action, Some(ActionType::Move { dx: 1, .. })));
+    }
+
+    #[test]
+    fn test_wounded_enemy_falls_back_to_pack() {
+        let grid = Grid::filled(12, 12, TileType::Floor);
+        let mut world = World::new();
+        let wounded = spawn_enemy(&mut world, 3, 5, AIState::Chasing, Faction::Undead);
+        world.get::<&mut Health>(wounded).unwrap().current = 2;
+        spawn_enemy(&mut world, 8, 5, AIState::Chasing, Faction::Undead);
+        let blocked = blocked_by_entities(&world);
+        let cache = SpatialCache::rebuild_from_world(&world);
+
+        let action = retreat(&ctx(&world, &grid, wounded, &blocked, &cache), (1, 5));
+        assert!(matches!(action, Some(ActionType::Move { dx: 1, dy: 0, .. })));
+
+        // Healthy enemies keep fighting
+        world.get::<&mut Health>(wounded).unwrap().current = 20;
+        assert!(retreat(&ctx(&world, &grid, wounded, &blocked, &cache), (1, 5)).is_none());
+    }
+}
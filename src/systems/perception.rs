@@ -0,0 +1,136 @@
+//! Perception for sleeping and unaware enemies.
+//!
+//! Alert enemies react to anything in sight. Asleep and unaware ones roll
+//! each turn to notice the targets they could see, with better odds against
+//! targets that are close, well lit and not sneaking. Hitting an enemy before
+//! it has noticed anyone is a sneak attack.
+
+use hecs::{Entity, World};
+use rand::Rng;
+
+use crate::components::{Awareness, ChaseAI, Sneaking};
+use crate::constants::*;
+use crate::grid::Grid;
+use crate::systems::behaviors::chebyshev;
+
+/// Roll how alert a freshly spawned enemy is
+pub fn roll_spawn_awareness(rng: &mut impl Rng) -> Awareness {
+    let roll = rng.gen::<f32>();
+    if roll < ENEMY_ASLEEP_CHANCE {
+        Awareness::Asleep
+    } else if roll < ENEMY_ASLEEP_CHANCE + ENEMY_UNAWARE_CHANCE {
+        Awareness::Unaware
+    } else {
+        Awareness::Alert
+    }
+}
+
+/// Whether an entity is an enemy that hasn't noticed anyone yet
+pub fn is_unaware(world: &World, entity: Entity) -> bool {
+    world.get::<&ChaseAI>(entity).map(|ai| ai.is_unaware()).unwrap_or(false)
+}
+
+/// Chance per turn to notice a target in line of sight.
+/// Light comes from the illumination at the target's tile.
+pub fn notice_chance(
+    grid: &Grid,
+    from: (i32, i32),
+    target_pos: (i32, i32),
+    sight_radius: i32,
+    awareness: Awareness,
+    sneaking: bool,
+) -> f32 {
+    if awareness == Awareness::Alert {
+        return 1.0;
+    }
+
+    let illumination = grid
+        .get(target_pos.0, target_pos.1)
+        .map(|_| grid.illumination[target_pos.1 as usize * grid.width + target_pos.0 as usize])
+        .unwrap_or(0.0);
+    let light = (illumination / PERCEPTION_FULL_LIGHT).clamp(PERCEPTION_MIN_LIGHT, 1.0);
+
+    // Adjacent targets are hard to miss; at the edge of sight they are barely noticed
+    let distance = (chebyshev(from, target_pos) - 1).max(0) as f32;
+    let closeness = (1.0 - distance / sight_radius.max(1) as f32).max(0.0);
+
+    let mut chance = PERCEPTION_BASE_CHANCE * light * closeness;
+    if awareness == Awareness::Asleep {
+        chance *= PERCEPTION_ASLEEP_MULTIPLIER;
+    }
+    if sneaking {
+        chance *= PERCEPTION_SNEAK_MULTIPLIER;
+    }
+    chance
+}
+
+/// Roll perception for an enemy against the targets it can see this turn
+/// (invisible targets are never among them). Returns true if it is alert.
+pub fn check_awareness(
+    world: &World,
+    grid: &Grid,
+    entity: Entity,
+    pos: (i32, i32),
+    sight_radius: i32,
+    in_sight: &[(Entity, (i32, i32))],
+    rng: &mut impl Rng,
+) -> bool {
+    let Ok(mut ai) = world.get::<&mut ChaseAI>(entity) else { return false };
+    if !ai.is_unaware() {
+        return true;
+    }
+
+    let awareness = ai.awareness;
+    let noticed = in_sight.iter().any(|&(target, target_pos)| {
+        let sneaking = world.get::<&Sneaking>(target).is_ok();
+        rng.gen::<f32>() < notice_chance(grid, pos, target_pos, sight_radius, awareness, sneaking)
+    });
+    if noticed {
+        ai.awareness = Awareness::Alert;
+    }
+    noticed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tile::TileType;
+
+    #[test]
+    fn test_light_distance_and_sleep_lower_the_odds() {
+        let mut grid = Grid::filled(10, 1, TileType::Floor);
+        grid.illumination = vec![PERCEPTION_FULL_LIGHT; 10];
+        grid.illumination[2] = 0.0;
+
+        let lit_adjacent = notice_chance(&grid, (0, 0), (1, 0), 8, Awareness::Unaware, false);
+        assert!((lit_adjacent - PERCEPTION_BASE_CHANCE).abs() < 1e-6);
+        assert!(notice_chance(&grid, (0, 0), (2, 0), 8, Awareness::Unaware, false) < lit_adjacent * 0.5);
+        assert!(notice_chance(&grid, (0, 0), (6, 0), 8, Awareness::Unaware, false) < lit_adjacent);
+        assert!(notice_chance(&grid, (0, 0), (1, 0), 8, Awareness::Asleep, false) < lit_adjacent);
+        assert!(notice_chance(&grid, (0, 0), (1, 0), 8, Awareness::Unaware, true) < lit_adjacent);
+        assert_eq!(notice_chance(&grid, (0, 0), (9, 0), 8, Awareness::Alert, false), 1.0);
+    }
+
+    #[test]
+    fn test_noticing_makes_enemy_alert() {
+        let mut grid = Grid::filled(10, 1, TileType::Floor);
+        grid.illumination = vec![PERCEPTION_FULL_LIGHT; 10];
+        let mut world = World::new();
+        let player = world.spawn(());
+        let mut ai = ChaseAI::new(8);
+        ai.awareness = Awareness::Unaware;
+        let enemy = world.spawn((ai,));
+        let mut rng = rand::thread_rng();
+
+        // Nobody in sight: stays unaware however many turns pass
+        for _ in 0..50 {
+            assert!(!check_awareness(&world, &grid, enemy, (0, 0), 8, &[], &mut rng));
+        }
+        assert!(is_unaware(&world, enemy));
+
+        // Someone standing right next to it in the light gets noticed sooner or later
+        let noticed = (0..200).any(|_| check_awareness(&world, &grid, enemy, (0, 0), 8, &[(player, (1, 0))], &mut rng));
+        assert!(noticed);
+        assert_eq!(world.get::<&ChaseAI>(enemy).unwrap().awareness, Awareness::Alert);
+    }
+}
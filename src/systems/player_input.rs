@@ -6,7 +6,7 @@
 
 use hecs::{Entity, World};
 
-use crate::components::{ActionType, BlocksMovement, Equipment, EquippedWeapon, ItemType, Position};
+use crate::components::{ActionType, BlocksMovement, CompanionOrder, Equipment, EquippedWeapon, ItemType, Position};
 use crate::grid::Grid;
 use crate::input::TargetingMode;
 
@@ -21,7 +21,7 @@ pub enum PlayerIntent {
     Wait,
     /// Move in a direction
     Move { dx: i32, dy: i32 },
-    /// Force attack in a direction (Shift+move)
+    /// Force attack in a direction (Alt+move)
     AttackDirection { dx: i32, dy: i32 },
     /// Interact with something in a direction (Ctrl+move)
     InteractDirection { dx: i32, dy: i32 },
@@ -46,6 +46,8 @@ pub enum PlayerIntent {
     PlaceSnareTrap { target_x: i32, target_y: i32 },
     /// Ranger: Shoot crippling shot that slows target
     ShootCripplingShot { target_x: i32, target_y: i32 },
+    /// Give companions a new order (only `companion`, if set)
+    CommandCompanions { order: CompanionOrder, companion: Option<Entity> },
 }
 
 /// Result of validating a targeting action
@@ -242,6 +244,11 @@ pub fn intent_to_action(
             target_x: *target_x,
             target_y: *target_y,
         }),
+
+        PlayerIntent::CommandCompanions { order, companion } => Some(ActionType::CommandCompanions {
+            order: *order,
+            companion: *companion,
+        }),
     }
 }
 
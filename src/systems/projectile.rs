@@ -9,13 +9,16 @@
 //! 4. Real-time visual lerp animates the arrow to its final position
 //! 5. Once visual catches up, the arrow is despawned
 
-use crate::components::{Attackable, EffectType, Health, ItemType, Position, Projectile, ProjectileMarker, VisualPosition};
+use crate::components::{Attackable, DamageType, EffectType, Health, ItemType, Position, Projectile, ProjectileMarker, VisualPosition};
+use crate::constants::SNEAK_ATTACK_MULTIPLIER;
 use crate::events::{EventQueue, GameEvent};
 use crate::grid::Grid;
-use crate::queries;
 use crate::systems::actions::apply_potion_splash;
+use crate::systems::combat::{agility_of, final_damage, ranged_hit_chance};
 use crate::systems::effects;
+use crate::systems::perception;
 use hecs::{Entity, World};
+use rand::Rng;
 
 /// Update all projectiles based on the current game time.
 /// This should be called when game time advances.
@@ -29,6 +32,13 @@ pub fn update_projectiles(
     // (projectile_entity, target_entity, position, damage, on_hit_effect, source_entity)
     let mut hits: Vec<(Entity, Option<Entity>, (i32, i32), i32, Option<(EffectType, f32)>, Entity)> = Vec::new();
     let mut finished_projectiles: Vec<(Entity, i32, i32, Option<ItemType>)> = Vec::new();
+    // (target_entity, position, source_entity) for arrows that flew past their target
+    let mut misses: Vec<(Entity, (i32, i32), Entity)> = Vec::new();
+    let mut rng = rand::thread_rng();
+
+    if !world.query::<&Projectile>().iter().any(|(_, projectile)| projectile.finished.is_none()) {
+        return;
+    }
 
     // Get all attackable entities and their positions for collision checking
     let attackables: Vec<(Entity, i32, i32)> = world
@@ -39,7 +49,7 @@ pub fn update_projectiles(
 
     // Update positions and check for collisions (walls AND enemies)
     for (projectile_entity, (pos, projectile)) in
-        world.query_mut::<(&mut Position, &mut Projectile)>()
+        world.query::<(&mut Position, &mut Projectile)>().iter()
     {
         // Skip already finished projectiles
         if projectile.finished.is_some() {
@@ -89,6 +99,17 @@ pub fn update_projectiles(
                     continue;
                 }
                 if tile_x == *target_x && tile_y == *target_y {
+                    // Arrows roll to hit (tiles travelled = distance); a miss flies on past the target.
+                    // Enemies that haven't noticed anyone can't dodge.
+                    let dodges = !perception::is_unaware(world, *target_entity);
+                    if let Some(shooter_agility) = projectile.shooter_agility.filter(|_| dodges) {
+                        let distance = i as i32 + 1;
+                        let target_agility = agility_of(world, *target_entity);
+                        if rng.gen::<f32>() >= ranged_hit_chance(shooter_agility, target_agility, distance) {
+                            misses.push((*target_entity, (tile_x, tile_y), projectile.source));
+                            continue;
+                        }
+                    }
                     hits.push((
                         projectile_entity,
                         Some(*target_entity),
@@ -132,16 +153,22 @@ pub fn update_projectiles(
         let mut actual_damage = damage;
         if let Some(target_entity) = target {
             // Mark that this projectile hit an enemy (for arrow recovery)
+            let mut is_arrow = false;
             if let Ok(mut projectile) = world.get::<&mut Projectile>(projectile_entity) {
                 projectile.hit_enemy = true;
+                is_arrow = projectile.potion_type.is_none();
             }
 
-            // Check for invulnerability
-            let is_invulnerable = queries::has_status_effect(world, target_entity, EffectType::Invulnerable);
-            if is_invulnerable {
-                actual_damage = 0;
+            // Arrows from the shadows hit harder (thrown potions just splash)
+            let mut damage = damage;
+            if is_arrow && perception::is_unaware(world, target_entity) {
+                damage = (damage as f32 * SNEAK_ATTACK_MULTIPLIER) as i32;
+                events.push(GameEvent::SneakAttack { attacker: source, target: target_entity });
             }
 
+            // Invulnerability, protection, resistances (including arrow resistance) and armor
+            actual_damage = final_damage(world, target_entity, damage, DamageType::Physical, true);
+
             // Apply damage
             if actual_damage > 0 {
                 if let Ok(mut health) = world.get::<&mut Health>(target_entity) {
@@ -171,6 +198,20 @@ pub fn update_projectiles(
         });
     }
 
+    for (target_entity, position, source) in misses {
+        // A near miss still draws the target's attention
+        use crate::constants::THREAT_PER_DAMAGE;
+        crate::systems::ai::generate_threat(world, target_entity, source, THREAT_PER_DAMAGE);
+        crate::systems::ai::generate_companion_threat(world, target_entity, source, THREAT_PER_DAMAGE);
+
+        events.push(GameEvent::AttackMissed {
+            attacker: source,
+            target: target_entity,
+            target_pos: (position.0 as f32 + 0.5, position.1 as f32 + 0.5),
+            ranged: true,
+        });
+    }
+
     // Mark projectiles as finished (don't despawn yet - wait for visual catch-up)
     // Also apply potion splash effects for potion projectiles
     for (entity, final_x, final_y, potion_type) in finished_projectiles {
@@ -43,26 +43,48 @@ pub struct RenderEntity {
 ///
 /// This means you can see distant lit areas (campfires) as long as nothing blocks your view.
 /// Also applies magical reveal effects (Scroll of Reveal) based on current time.
-pub fn update_fov(world: &World, grid: &mut Grid, player_entity: Entity, radius: i32, current_time: f32) {
+/// Light sources whose lit area falls entirely outside `view_bounds` (see
+/// `Camera::get_visible_bounds`) are skipped.
+pub fn update_fov(
+    world: &World,
+    grid: &mut Grid,
+    player_entity: Entity,
+    radius: i32,
+    view_bounds: (i32, i32, i32, i32),
+    current_time: f32,
+) {
     let Ok(player_pos) = world.get::<&Position>(player_entity) else {
         return;
     };
     let player_x = player_pos.x;
     let player_y = player_pos.y;
 
-    // Clear visibility, but apply magical reveal for tiles that haven't expired
-    for tile in &mut grid.tiles {
-        tile.visible = false;
-        // Check if tile is magically revealed
-        if let Some(reveal_time) = tile.revealed_until {
-            if reveal_time > current_time {
-                tile.visible = true;
-            } else {
+    // Clear last update's visibility. Only tiles that were visible need touching,
+    // so this stays proportional to the view rather than the map size.
+    for idx in std::mem::take(&mut grid.visible_tiles) {
+        grid.tiles[idx].visible = false;
+    }
+
+    // Apply magical reveal for tiles that haven't expired
+    let mut revealed_tiles = std::mem::take(&mut grid.revealed_tiles);
+    revealed_tiles.retain(|&idx| {
+        let tile = &mut grid.tiles[idx];
+        match tile.revealed_until {
+            Some(reveal_time) if reveal_time > current_time => {
+                if !tile.visible {
+                    tile.visible = true;
+                    grid.visible_tiles.push(idx);
+                }
+                true
+            }
+            _ => {
                 // Reveal expired, clear it
                 tile.revealed_until = None;
+                false
             }
         }
-    }
+    });
+    grid.revealed_tiles = revealed_tiles;
 
     // Collect positions of entities that block vision
     let blocking_positions: HashSet<(i32, i32)> = world
@@ -80,10 +102,7 @@ pub fn update_fov(world: &World, grid: &mut Grid, player_entity: Entity, radius:
         Some(|x: i32, y: i32| blocking_positions.contains(&(x, y))),
     );
     for (x, y) in player_lit_tiles {
-        if let Some(tile) = grid.get_mut(x, y) {
-            tile.visible = true;
-            tile.explored = true;
-        }
+        mark_visible(grid, x, y);
     }
 
     // 2. External light sources - tiles are visible if:
@@ -94,6 +113,7 @@ pub fn update_fov(world: &World, grid: &mut Grid, player_entity: Entity, radius:
         .query::<(&Position, &LightSource)>()
         .iter()
         .map(|(_, (pos, light))| (pos.x, pos.y, light.radius))
+        .filter(|&(x, y, radius)| light_in_view_range(view_bounds, x, y, radius))
         .collect();
 
     for (light_x, light_y, light_radius) in light_sources {
@@ -128,14 +148,35 @@ pub fn update_fov(world: &World, grid: &mut Grid, player_entity: Entity, radius:
 
                 // Check if player can see this tile
                 if has_line_of_sight(grid, &blocking_positions, player_x, player_y, x, y) {
-                    if let Some(tile) = grid.get_mut(x, y) {
-                        tile.visible = true;
-                        tile.explored = true;
-                    }
+                    mark_visible(grid, x, y);
                 }
             }
         }
     }
+
+    grid.mark_view_changed();
+}
+
+/// Mark a tile visible and explored, recording it so the next update can clear it.
+fn mark_visible(grid: &mut Grid, x: i32, y: i32) {
+    let Some(idx) = grid.index(x, y) else {
+        return;
+    };
+    let tile = &mut grid.tiles[idx];
+    if !tile.visible {
+        tile.visible = true;
+        grid.visible_tiles.push(idx);
+    }
+    tile.explored = true;
+}
+
+/// Whether any tile a light source can reach lies inside the view bounds.
+/// Lights that can't light anything on screen are skipped, which keeps lighting
+/// cost independent of how many light sources the floor has.
+fn light_in_view_range(view_bounds: (i32, i32, i32, i32), light_x: i32, light_y: i32, light_radius: f32) -> bool {
+    let (min_x, max_x, min_y, max_y) = view_bounds;
+    let r = light_radius.ceil() as i32;
+    light_x + r >= min_x && light_x - r <= max_x && light_y + r >= min_y && light_y - r <= max_y
 }
 
 /// Check if there's a clear line of sight between two points.
@@ -192,18 +233,18 @@ fn has_line_of_sight(
 /// Calculate per-tile illumination from player and light sources.
 /// Light sources contribute if they have LOS to the tile (light propagation).
 /// Must be called after update_fov (requires visible flags to be set).
+/// Only tiles visible now or on the previous update are touched.
 pub fn calculate_illumination(
     world: &World,
     grid: &mut Grid,
     player_entity: Entity,
     fov_radius: i32,
+    view_bounds: (i32, i32, i32, i32),
 ) {
-    let width = grid.width;
-    let height = grid.height;
-
-    // Reset illumination
-    for illum in &mut grid.illumination {
-        *illum = 0.0;
+    // Return last update's lit tiles to fog (or darkness if never explored).
+    // Everything else already holds its fog value, so no full-map reset is needed.
+    for idx in std::mem::take(&mut grid.lit_tiles) {
+        grid.illumination[idx] = if grid.tiles[idx].explored { FOG_BRIGHTNESS } else { 0.0 };
     }
 
     // Get player position
@@ -214,11 +255,12 @@ pub fn calculate_illumination(
     let player_y = player_pos.y;
     let player_light_radius = fov_radius as f32;
 
-    // Collect all light sources (we'll check LOS per-tile)
+    // Collect nearby light sources (we'll check LOS per-tile)
     let light_sources: Vec<(i32, i32, f32, f32)> = world
         .query::<(&Position, &LightSource)>()
         .iter()
         .map(|(_, (pos, light))| (pos.x, pos.y, light.radius, light.intensity))
+        .filter(|&(x, y, radius, _)| light_in_view_range(view_bounds, x, y, radius))
         .collect();
 
     // Collect positions of entities that block vision (for light LOS checks)
@@ -228,63 +270,63 @@ pub fn calculate_illumination(
         .map(|(_, (pos, _))| (pos.x, pos.y))
         .collect();
 
-    // Calculate illumination for each tile
-    for y in 0..height as i32 {
-        for x in 0..width as i32 {
-            let idx = y as usize * width + x as usize;
-            let tile = &grid.tiles[idx];
-
-            if !tile.visible && !tile.explored {
-                // Completely unexplored - no illumination
+    // Calculate illumination for each visible tile
+    let visible_tiles = grid.visible_tiles.clone();
+    for &idx in &visible_tiles {
+        let x = (idx % grid.width) as i32;
+        let y = (idx / grid.width) as i32;
+
+        // Tile is visible - calculate illumination from player
+        let dx = (x - player_x) as f32;
+        let dy = (y - player_y) as f32;
+        let distance = (dx * dx + dy * dy).sqrt();
+
+        // Player light: smooth quadratic falloff
+        let t = (1.0 - distance / player_light_radius).max(0.0);
+        let player_contrib = AMBIENT_BRIGHTNESS + (1.0 - AMBIENT_BRIGHTNESS) * t * t;
+        let mut total_illumination = player_contrib;
+
+        // Add contributions from light sources that can reach this tile
+        for &(light_x, light_y, radius, intensity) in &light_sources {
+            let ldx = (x - light_x) as f32;
+            let ldy = (y - light_y) as f32;
+            let light_dist = (ldx * ldx + ldy * ldy).sqrt();
+
+            // Check if within light radius
+            if light_dist > radius {
                 continue;
             }
 
-            if !tile.visible {
-                // Explored but not visible - fog of war brightness
-                grid.illumination[idx] = FOG_BRIGHTNESS;
+            // Check if light can reach this tile (LOS from light to tile)
+            if !has_line_of_sight(grid, &blocking_positions, light_x, light_y, x, y) {
                 continue;
             }
 
-            // Tile is visible - calculate illumination from player
-            let dx = (x - player_x) as f32;
-            let dy = (y - player_y) as f32;
-            let distance = (dx * dx + dy * dy).sqrt();
-
-            // Player light: smooth quadratic falloff
-            let t = (1.0 - distance / player_light_radius).max(0.0);
-            let player_contrib = AMBIENT_BRIGHTNESS + (1.0 - AMBIENT_BRIGHTNESS) * t * t;
-            let mut total_illumination = player_contrib;
-
-            // Add contributions from light sources that can reach this tile
-            for &(light_x, light_y, radius, intensity) in &light_sources {
-                let ldx = (x - light_x) as f32;
-                let ldy = (y - light_y) as f32;
-                let light_dist = (ldx * ldx + ldy * ldy).sqrt();
-
-                // Check if within light radius
-                if light_dist > radius {
-                    continue;
-                }
-
-                // Check if light can reach this tile (LOS from light to tile)
-                if !has_line_of_sight(grid, &blocking_positions, light_x, light_y, x, y) {
-                    continue;
-                }
-
-                let lt = 1.0 - (light_dist / radius);
-                let contribution = intensity * lt * lt;
-                total_illumination += contribution;
-            }
-
-            // Clamp to reasonable range (allow slight overbrightness for bloom-like effect)
-            grid.illumination[idx] = total_illumination.min(1.5);
+            let lt = 1.0 - (light_dist / radius);
+            let contribution = intensity * lt * lt;
+            total_illumination += contribution;
         }
+
+        // Clamp to reasonable range (allow slight overbrightness for bloom-like effect)
+        grid.illumination[idx] = total_illumination.min(1.5);
     }
+    grid.lit_tiles = visible_tiles;
 }
 
 /// Collect entities that should be rendered, with fog of war applied.
 /// Entities are sorted by layer: ground items first, then actors, then player on top.
-pub fn collect_renderables(world: &World, grid: &Grid, player_entity: Entity, real_time: f32) -> Vec<RenderEntity> {
+/// Entities outside `view_bounds` (min_x, max_x, min_y, max_y, as returned by
+/// `Camera::get_visible_bounds`) are skipped before any per-entity lookups.
+pub fn collect_renderables(
+    world: &World,
+    grid: &Grid,
+    player_entity: Entity,
+    real_time: f32,
+    view_bounds: (i32, i32, i32, i32),
+) -> Vec<RenderEntity> {
+    let (min_x, max_x, min_y, max_y) = view_bounds;
+    let in_view = |x: i32, y: i32| x >= min_x && x <= max_x && y >= min_y && y <= max_y;
+
     // Separate entities by render layer
     let mut ground_layer: Vec<RenderEntity> = Vec::new();  // Bones, items on ground, campfires
     let mut actor_layer: Vec<RenderEntity> = Vec::new();   // Enemies and NPCs
@@ -294,6 +336,10 @@ pub fn collect_renderables(world: &World, grid: &Grid, player_entity: Entity, re
     for (id, (pos, vis_pos, sprite)) in
         world.query::<(&Position, &VisualPosition, &Sprite)>().iter()
     {
+        if id != player_entity && !in_view(pos.x, pos.y) {
+            continue;
+        }
+
         let (is_explored, is_visible) = grid
             .get(pos.x, pos.y)
             .map(|tile| (tile.explored, tile.visible))
@@ -373,6 +419,7 @@ pub fn collect_renderables(world: &World, grid: &Grid, player_entity: Entity, re
     let mut animated_entities: Vec<_> = world
         .query::<(&Position, &VisualPosition, &AnimatedSprite)>()
         .iter()
+        .filter(|(_, (pos, _, _))| in_view(pos.x, pos.y))
         .map(|(_, (pos, vis_pos, anim))| (pos.x, pos.y, vis_pos.x, vis_pos.y, anim.clone()))
         .collect();
     animated_entities.sort_by_key(|(_, _, _, _, anim)| anim.z_order);
@@ -428,6 +475,10 @@ pub fn collect_renderables(world: &World, grid: &Grid, player_entity: Entity, re
     for (_, (pos, vis_pos, status)) in
         world.query::<(&Position, &VisualPosition, &StatusEffects)>().iter()
     {
+        if !in_view(pos.x, pos.y) {
+            continue;
+        }
+
         // Check if entity is burning
         if status.effects.iter().any(|e| e.effect_type == EffectType::Burning) {
             let is_visible = grid
@@ -465,6 +516,10 @@ pub fn collect_renderables(world: &World, grid: &Grid, player_entity: Entity, re
     for (_, (pos, vis_pos, _trap)) in
         world.query::<(&Position, &VisualPosition, &PlacedFireTrap)>().iter()
     {
+        if !in_view(pos.x, pos.y) {
+            continue;
+        }
+
         let is_visible = grid
             .get(pos.x, pos.y)
             .map(|tile| tile.visible)
@@ -502,9 +557,14 @@ pub fn collect_renderables(world: &World, grid: &Grid, player_entity: Entity, re
 
 /// Reveal all tiles on the map (Scroll of Mapping effect)
 pub fn reveal_entire_map(grid: &mut Grid) {
-    for tile in &mut grid.tiles {
+    for (tile, illum) in grid.tiles.iter_mut().zip(grid.illumination.iter_mut()) {
         tile.explored = true;
+        // Newly explored tiles start at fog brightness until they are next seen
+        if *illum == 0.0 {
+            *illum = FOG_BRIGHTNESS;
+        }
     }
+    grid.mark_map_changed();
 }
 
 /// Magically reveal tiles around all enemies (Scroll of Reveal effect).
@@ -520,16 +580,22 @@ pub fn reveal_enemies(world: &World, grid: &mut Grid, current_time: f32) {
         // Reveal tiles in a radius around each enemy
         for dy in -REVEAL_RADIUS..=REVEAL_RADIUS {
             for dx in -REVEAL_RADIUS..=REVEAL_RADIUS {
-                if let Some(tile) = grid.get_mut(pos.x + dx, pos.y + dy) {
-                    tile.explored = true;
-                    // Set or extend the magical reveal time
-                    tile.revealed_until = Some(
-                        tile.revealed_until
-                            .map(|t| t.max(reveal_until))
-                            .unwrap_or(reveal_until)
-                    );
+                let Some(idx) = grid.index(pos.x + dx, pos.y + dy) else {
+                    continue;
+                };
+                let tile = &mut grid.tiles[idx];
+                if tile.revealed_until.is_none() {
+                    grid.revealed_tiles.push(idx);
                 }
+                tile.explored = true;
+                // Set or extend the magical reveal time
+                tile.revealed_until = Some(
+                    tile.revealed_until
+                        .map(|t| t.max(reveal_until))
+                        .unwrap_or(reveal_until)
+                );
             }
         }
     }
+    grid.mark_map_changed();
 }
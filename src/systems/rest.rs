@@ -0,0 +1,217 @@
+//! Resting and waiting: keep taking the wait action until something happens.
+//!
+//! Resting waits until the player's HP and ability cooldowns are full;
+//! waiting stops after a set stretch of game time. Both give up as soon as a
+//! hostile comes into view, the player gets hurt or one of the player's
+//! status effects wears off, and report why they stopped.
+
+use hecs::{Entity, World};
+
+use crate::components::{
+    ClassAbility, EffectType, Health, RangedCooldown, RangerAbilities, SecondaryAbility, StatusEffects,
+};
+use crate::constants::REST_MAX_DURATION;
+use crate::grid::Grid;
+use crate::systems::exploration::enemy_in_view;
+
+/// What the player is waiting for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestKind {
+    /// Until HP and cooldowns are full
+    UntilRested,
+    /// For this many seconds of game time
+    For(f32),
+}
+
+/// Why resting or waiting stopped (or never started)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestStop {
+    /// HP and cooldowns are full
+    Rested,
+    /// The wait is over, or resting went on for `REST_MAX_DURATION`
+    TimeUp,
+    /// Asked to rest with nothing to recover
+    AlreadyRested,
+    /// A hostile creature is in view
+    EnemyInView(Entity),
+    /// The player lost HP
+    TookDamage,
+    /// One of the player's status effects wore off
+    EffectEnded(EffectType),
+}
+
+/// A rest or wait in progress
+#[derive(Debug, Clone)]
+pub struct Rest {
+    pub kind: RestKind,
+    /// Game time at which to give up
+    until: f32,
+    /// HP at the last check (regen only ever raises it)
+    last_hp: i32,
+    /// Status effects active at the last check
+    effects: Vec<EffectType>,
+}
+
+impl Rest {
+    /// Start resting or waiting at game time `now`
+    pub fn start(world: &World, grid: &Grid, player: Entity, kind: RestKind, now: f32) -> Result<Self, RestStop> {
+        if let Some(enemy) = enemy_in_view(world, grid, player) {
+            return Err(RestStop::EnemyInView(enemy));
+        }
+        if kind == RestKind::UntilRested && is_rested(world, player) {
+            return Err(RestStop::AlreadyRested);
+        }
+
+        let duration = match kind {
+            RestKind::UntilRested => REST_MAX_DURATION,
+            RestKind::For(seconds) => seconds,
+        };
+        Ok(Self {
+            kind,
+            until: now + duration,
+            last_hp: current_hp(world, player),
+            effects: active_effects(world, player),
+        })
+    }
+
+    /// Decide whether to keep waiting. Call whenever the player is ready to act;
+    /// `None` means wait another turn.
+    pub fn check(&mut self, world: &World, grid: &Grid, player: Entity, now: f32) -> Option<RestStop> {
+        let hp = current_hp(world, player);
+        if hp < self.last_hp {
+            return Some(RestStop::TookDamage);
+        }
+        self.last_hp = hp;
+
+        let effects = active_effects(world, player);
+        if let Some(&ended) = self.effects.iter().find(|effect| !effects.contains(effect)) {
+            return Some(RestStop::EffectEnded(ended));
+        }
+        self.effects = effects;
+
+        if let Some(enemy) = enemy_in_view(world, grid, player) {
+            return Some(RestStop::EnemyInView(enemy));
+        }
+        if self.kind == RestKind::UntilRested && is_rested(world, player) {
+            return Some(RestStop::Rested);
+        }
+        if now >= self.until {
+            return Some(RestStop::TimeUp);
+        }
+        None
+    }
+}
+
+/// Whether `player` has full HP and every ability off cooldown
+pub fn is_rested(world: &World, player: Entity) -> bool {
+    let healed = world
+        .get::<&Health>(player)
+        .is_ok_and(|health| health.current >= health.max);
+    let ability_ready = world.get::<&ClassAbility>(player).map_or(true, |ability| ability.is_ready());
+    let secondary_ready = world
+        .get::<&SecondaryAbility>(player)
+        .map_or(true, |ability| ability.is_ready());
+    let ranger_ready = world
+        .get::<&RangerAbilities>(player)
+        .map_or(true, |ranger| (0..ranger.abilities.len()).all(|i| ranger.is_ready(i)));
+    let ranged_ready = world
+        .get::<&RangedCooldown>(player)
+        .map_or(true, |cooldown| cooldown.remaining <= 0.0);
+
+    healed && ability_ready && secondary_ready && ranger_ready && ranged_ready
+}
+
+fn current_hp(world: &World, player: Entity) -> i32 {
+    world.get::<&Health>(player).map_or(0, |health| health.current)
+}
+
+fn active_effects(world: &World, player: Entity) -> Vec<EffectType> {
+    world
+        .get::<&StatusEffects>(player)
+        .map(|status| status.effects.iter().map(|effect| effect.effect_type).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{AbilityType, ActiveEffect, Faction, Player, Position};
+
+    fn hurt_player(world: &mut World) -> Entity {
+        let mut health = Health::new(10);
+        health.current = 5;
+        world.spawn((Player, Faction::Player, Position::new(0, 0), health))
+    }
+
+    #[test]
+    fn test_rest_until_healed_and_off_cooldown() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(5, 5, false, true);
+        let player = hurt_player(&mut world);
+        let mut ability = ClassAbility::new(AbilityType::Cleave, 20.0);
+        ability.start_cooldown();
+        world.insert_one(player, ability).unwrap();
+
+        let mut rest = Rest::start(&world, &grid, player, RestKind::UntilRested, 0.0).unwrap();
+        assert_eq!(rest.check(&world, &grid, player, 1.0), None);
+
+        world.get::<&mut Health>(player).unwrap().current = 10;
+        assert_eq!(rest.check(&world, &grid, player, 2.0), None, "stopped with the ability on cooldown");
+
+        world.get::<&mut ClassAbility>(player).unwrap().cooldown_remaining = 0.0;
+        assert_eq!(rest.check(&world, &grid, player, 3.0), Some(RestStop::Rested));
+        assert_eq!(
+            Rest::start(&world, &grid, player, RestKind::UntilRested, 3.0).unwrap_err(),
+            RestStop::AlreadyRested
+        );
+    }
+
+    #[test]
+    fn test_wait_runs_out() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(5, 5, false, true);
+        let player = world.spawn((Player, Faction::Player, Position::new(0, 0), Health::new(10)));
+
+        let mut rest = Rest::start(&world, &grid, player, RestKind::For(30.0), 10.0).unwrap();
+        assert_eq!(rest.check(&world, &grid, player, 39.5), None);
+        assert_eq!(rest.check(&world, &grid, player, 40.0), Some(RestStop::TimeUp));
+    }
+
+    #[test]
+    fn test_interrupted_by_damage_effects_and_enemies() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(5, 5, false, true);
+        let player = hurt_player(&mut world);
+        world
+            .insert_one(
+                player,
+                StatusEffects {
+                    effects: vec![ActiveEffect {
+                        effect_type: EffectType::SpeedBoost,
+                        remaining_duration: 5.0,
+                        last_damage_tick: 0.0,
+                    }],
+                },
+            )
+            .unwrap();
+
+        let mut rest = Rest::start(&world, &grid, player, RestKind::UntilRested, 0.0).unwrap();
+        world.get::<&mut Health>(player).unwrap().current = 4;
+        assert_eq!(rest.check(&world, &grid, player, 1.0), Some(RestStop::TookDamage));
+
+        let mut rest = Rest::start(&world, &grid, player, RestKind::UntilRested, 0.0).unwrap();
+        world.get::<&mut StatusEffects>(player).unwrap().effects.clear();
+        assert_eq!(
+            rest.check(&world, &grid, player, 1.0),
+            Some(RestStop::EffectEnded(EffectType::SpeedBoost))
+        );
+
+        let mut rest = Rest::start(&world, &grid, player, RestKind::For(60.0), 0.0).unwrap();
+        let rat = world.spawn((Faction::Vermin, Position::new(3, 3), Health::new(5)));
+        assert_eq!(rest.check(&world, &grid, player, 1.0), Some(RestStop::EnemyInView(rat)));
+        assert_eq!(
+            Rest::start(&world, &grid, player, RestKind::For(60.0), 1.0).unwrap_err(),
+            RestStop::EnemyInView(rat)
+        );
+    }
+}
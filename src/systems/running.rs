@@ -0,0 +1,239 @@
+//! Shift-running: keep stepping in one direction until something interesting.
+//!
+//! In a corridor the run follows bends and stops at junctions, dead ends and
+//! where the corridor opens into a room. In the open it goes straight and
+//! stops at the far wall or when an opening appears alongside (a doorway or
+//! a side passage). Either way it stops when a hostile comes into view, when a
+//! door, item or staircase turns up next to the player, and in front of
+//! hazards the player would path around.
+
+use std::collections::HashSet;
+
+use hecs::{Entity, World};
+
+use crate::components::{Container, Vendor};
+use crate::grid::Grid;
+use crate::pathfinding::CostProfile;
+use crate::queries;
+use crate::spatial_cache::SpatialCache;
+use crate::systems::exploration::enemy_in_view;
+use crate::systems::hazards;
+use crate::tile::TileType;
+
+const CARDINALS: [(i32, i32); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+
+/// A run in progress
+#[derive(Debug, Clone)]
+pub struct Run {
+    /// Direction of the last step
+    dir: (i32, i32),
+    /// Following a corridor (walls on both sides)
+    corridor: bool,
+    /// Whether the tiles to the left and right were open at the last step
+    sides: (bool, bool),
+    /// Doors, items and stairs already next to the player
+    features: HashSet<(i32, i32)>,
+    /// Where the player stood when the last step was chosen
+    last_pos: (i32, i32),
+}
+
+impl Run {
+    /// Start running from the player's position. The caller takes the first step in `dir`.
+    pub fn start(
+        world: &World,
+        grid: &Grid,
+        spatial_cache: &SpatialCache,
+        player: Entity,
+        dir: (i32, i32),
+    ) -> Option<Self> {
+        let pos = queries::get_entity_position(world, player)?;
+        let sides = open_sides(grid, pos, dir);
+        Some(Self {
+            dir,
+            corridor: sides == (false, false),
+            sides,
+            features: features_near(world, grid, spatial_cache, pos),
+            last_pos: pos,
+        })
+    }
+
+    /// The next step to take, or `None` to stop. Call whenever the player is ready to act.
+    pub fn next_step(
+        &mut self,
+        world: &World,
+        grid: &Grid,
+        spatial_cache: &SpatialCache,
+        player: Entity,
+    ) -> Option<(i32, i32)> {
+        let pos = queries::get_entity_position(world, player)?;
+        // The last step didn't get us anywhere (bumped something, opened a door)
+        if pos == self.last_pos {
+            return None;
+        }
+        self.last_pos = pos;
+
+        if enemy_in_view(world, grid, player).is_some() {
+            return None;
+        }
+
+        let features = features_near(world, grid, spatial_cache, pos);
+        if features.iter().any(|feature| !self.features.contains(feature)) {
+            return None;
+        }
+        self.features = features;
+
+        let dir = if self.corridor {
+            corridor_turn(grid, pos, self.dir)?
+        } else {
+            // A new opening alongside (doorway, side passage) is worth a look
+            let sides = open_sides(grid, pos, self.dir);
+            if (sides.0 && !self.sides.0) || (sides.1 && !self.sides.1) {
+                return None;
+            }
+            // Walls on both sides: we've run into a corridor
+            self.corridor = sides == (false, false);
+            self.sides = sides;
+            self.dir
+        };
+
+        let next = (pos.0 + dir.0, pos.1 + dir.1);
+        if !grid.is_walkable(next.0, next.1) || spatial_cache.is_blocked(next) {
+            return None;
+        }
+        if hazards::cost_at(world, grid, spatial_cache, player, CostProfile::CAUTIOUS, next) > 0 {
+            return None;
+        }
+
+        self.dir = dir;
+        Some(dir)
+    }
+}
+
+/// The only way on from a corridor tile, not counting the way back.
+/// `None` at dead ends, junctions and room entrances.
+fn corridor_turn(grid: &Grid, pos: (i32, i32), dir: (i32, i32)) -> Option<(i32, i32)> {
+    let mut ways = CARDINALS
+        .into_iter()
+        .filter(|&way| way != (-dir.0, -dir.1) && grid.is_walkable(pos.0 + way.0, pos.1 + way.1));
+    match (ways.next(), ways.next()) {
+        (Some(way), None) => Some(way),
+        _ => None,
+    }
+}
+
+/// Whether the tiles to the left and right of `pos` (facing `dir`) are open
+fn open_sides(grid: &Grid, pos: (i32, i32), dir: (i32, i32)) -> (bool, bool) {
+    let (dx, dy) = dir;
+    (
+        grid.is_walkable(pos.0 - dy, pos.1 + dx),
+        grid.is_walkable(pos.0 + dy, pos.1 - dx),
+    )
+}
+
+/// Doorways, stairs, containers and vendors on or next to `pos`
+fn features_near(world: &World, grid: &Grid, spatial_cache: &SpatialCache, pos: (i32, i32)) -> HashSet<(i32, i32)> {
+    let mut features = HashSet::new();
+
+    for y in (pos.1 - 1)..=(pos.1 + 1) {
+        for x in (pos.0 - 1)..=(pos.0 + 1) {
+            let Some(tile) = grid.get(x, y) else { continue };
+            if tile.doorway || matches!(tile.tile_type, TileType::StairsDown | TileType::StairsUp) {
+                features.insert((x, y));
+            }
+        }
+    }
+    for (entity, entity_pos) in spatial_cache.entities_within(pos, 1) {
+        if world.get::<&Container>(entity).is_ok() || world.get::<&Vendor>(entity).is_ok() {
+            features.insert(entity_pos);
+        }
+    }
+
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ContainerType, Faction, GroundItemPile, Health, ItemType, Player, Position};
+    use crate::tile::Tile;
+
+    /// A grid of walls with floor carved at the given tiles
+    fn make_grid(width: usize, height: usize, floor: &[(i32, i32)]) -> Grid {
+        let mut grid = Grid::filled(width, height, TileType::Wall);
+        for &(x, y) in floor {
+            grid.tiles[y as usize * width + x as usize] = Tile::new(TileType::Floor);
+        }
+        grid
+    }
+
+    /// Run from the player's position, teleporting along each step; returns where it stopped
+    fn run(world: &mut World, grid: &Grid, player: Entity, dir: (i32, i32)) -> (i32, i32) {
+        let cache = SpatialCache::rebuild_from_world(world);
+        let mut run = Run::start(world, grid, &cache, player, dir).unwrap();
+        let mut step = Some(dir);
+        while let Some((dx, dy)) = step {
+            let mut pos = world.get::<&mut Position>(player).unwrap();
+            pos.x += dx;
+            pos.y += dy;
+            drop(pos);
+            step = run.next_step(world, grid, &cache, player);
+        }
+        queries::get_entity_position(world, player).unwrap()
+    }
+
+    #[test]
+    fn test_follows_corridor_bends_to_a_junction() {
+        // An L-shaped corridor east then north, with a side branch at the top
+        let mut floor: Vec<(i32, i32)> = (1..=5).map(|x| (x, 1)).collect();
+        floor.extend((2..=5).map(|y| (5, y)));
+        floor.push((6, 4));
+        let grid = make_grid(8, 7, &floor);
+        let mut world = World::new();
+        let player = world.spawn((Player, Faction::Player, Position::new(1, 1)));
+
+        assert_eq!(run(&mut world, &grid, player, (1, 0)), (5, 4));
+    }
+
+    #[test]
+    fn test_open_run_stops_at_doorway_and_wall() {
+        // A room from x=1..=6, y=1..=3 with a gap in the top wall at x=4
+        let mut floor: Vec<(i32, i32)> = (1..=6).flat_map(|x| (1..=3).map(move |y| (x, y))).collect();
+        floor.push((4, 4));
+        let grid = make_grid(8, 6, &floor);
+        let mut world = World::new();
+        let player = world.spawn((Player, Faction::Player, Position::new(1, 3)));
+
+        // Along the top wall: stops under the gap
+        assert_eq!(run(&mut world, &grid, player, (1, 0)), (4, 3));
+        // Then on to the far wall
+        assert_eq!(run(&mut world, &grid, player, (1, 0)), (6, 3));
+    }
+
+    #[test]
+    fn test_stops_for_items_and_enemies() {
+        let floor: Vec<(i32, i32)> = (1..=10).map(|x| (x, 1)).collect();
+        let mut grid = make_grid(12, 3, &floor);
+        let mut world = World::new();
+        let player = world.spawn((Player, Faction::Player, Position::new(1, 1)));
+        world.spawn((
+            Position::new(6, 1),
+            GroundItemPile,
+            Container {
+                container_type: ContainerType::GroundPile,
+                items: vec![ItemType::Apple],
+                gold: 0,
+                is_open: true,
+                spawn_chance: 0.0,
+            },
+        ));
+        assert_eq!(run(&mut world, &grid, player, (1, 0)), (5, 1));
+
+        // A rat in view ends the run after the first step
+        for x in 0..12 {
+            grid.tiles[12 + x].visible = true;
+        }
+        world.spawn((Faction::Vermin, Position::new(10, 1), Health::new(5)));
+        world.get::<&mut Position>(player).unwrap().x = 1;
+        assert_eq!(run(&mut world, &grid, player, (1, 0)), (2, 1));
+    }
+}
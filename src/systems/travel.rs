@@ -0,0 +1,207 @@
+//! Travel commands: walk to the stairs, the shop, a remembered item pile or a
+//! tile picked on the map.
+//!
+//! Travel only crosses tiles the player has explored, avoids hazards the way
+//! click-to-move does, and hands back a path that is followed (and
+//! interrupted) like a click-to-move path. Stairs and the vendor are walked
+//! up to, not into, so travelling never changes floors or opens the shop by
+//! itself; travelling again from next to them takes the last step.
+
+use hecs::{Entity, World};
+
+use crate::components::{BlocksMovement, Container, Door, Position, Vendor};
+use crate::dijkstra_map::DijkstraMap;
+use crate::grid::Grid;
+use crate::pathfinding::CostProfile;
+use crate::queries;
+use crate::systems::hazards::HazardCosts;
+
+/// Where a travel command goes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TravelTarget {
+    StairsDown,
+    StairsUp,
+    Shop,
+    /// The nearest item pile the player has seen
+    ItemPile,
+    /// A tile picked on the map
+    Tile(i32, i32),
+}
+
+/// Why a travel command couldn't start
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TravelFailure {
+    /// The player hasn't found the target yet
+    Unexplored,
+    /// No known path leads there
+    Unreachable,
+}
+
+/// A planned trip
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trip {
+    /// Steps to walk, excluding the start
+    pub path: Vec<(i32, i32)>,
+    /// The target tile (one step past the path's end for stairs and the vendor)
+    pub destination: (i32, i32),
+}
+
+/// Plan a trip to `target`
+pub fn plan(world: &World, grid: &Grid, player: Entity, target: TravelTarget) -> Result<Trip, TravelFailure> {
+    let player_pos = queries::get_entity_position(world, player).ok_or(TravelFailure::Unreachable)?;
+    let goals: Vec<(i32, i32)> = known_targets(world, grid, target)
+        .into_iter()
+        .filter(|&pos| pos != player_pos)
+        .collect();
+    if goals.is_empty() {
+        return Err(TravelFailure::Unexplored);
+    }
+
+    let mut path = path_over_known_tiles(world, grid, player, &goals, &mut DijkstraMap::default());
+    let destination = *path.last().ok_or(TravelFailure::Unreachable)?;
+
+    // Stop next to stairs and the vendor unless we're already there
+    let walk_up_to = matches!(target, TravelTarget::StairsDown | TravelTarget::StairsUp | TravelTarget::Shop);
+    if walk_up_to && path.len() > 1 {
+        path.pop();
+    }
+    Ok(Trip { path, destination })
+}
+
+/// Path to the nearest of `goals`, over explored tiles only and weighted by
+/// the player's hazard costs. Closed doors are walked into (which opens them).
+/// Empty if no goal can be reached.
+pub fn path_over_known_tiles(
+    world: &World,
+    grid: &Grid,
+    player: Entity,
+    goals: &[(i32, i32)],
+    map: &mut DijkstraMap,
+) -> Vec<(i32, i32)> {
+    let Some(start) = queries::get_entity_position(world, player) else {
+        return Vec::new();
+    };
+
+    let mut blocked = queries::get_blocking_positions(world, Some(player));
+    for (_, (pos, door)) in world.query::<(&Position, &Door)>().iter() {
+        if !door.is_open {
+            blocked.remove(&(pos.x, pos.y));
+        }
+    }
+
+    let costs = HazardCosts::new(world, grid, Some(player), CostProfile::CAUTIOUS);
+    let known = |pos: (i32, i32)| grid.get(pos.0, pos.1).is_some_and(|tile| tile.explored) && !blocked.contains(&pos);
+    map.build_within(grid, goals, i32::MAX, known, &|pos| costs.cost(grid, pos));
+
+    let mut path = Vec::new();
+    let mut pos = start;
+    while let Some(next) = map.next_step(grid, pos, &blocked) {
+        path.push(next);
+        pos = next;
+    }
+    path
+}
+
+/// Explored tiles holding the target
+fn known_targets(world: &World, grid: &Grid, target: TravelTarget) -> Vec<(i32, i32)> {
+    let explored = |pos: &(i32, i32)| grid.get(pos.0, pos.1).is_some_and(|tile| tile.explored);
+    match target {
+        TravelTarget::StairsDown => grid.stairs_down_pos.into_iter().filter(explored).collect(),
+        TravelTarget::StairsUp => grid.stairs_up_pos.into_iter().filter(explored).collect(),
+        TravelTarget::Shop => world
+            .query::<&Position>()
+            .with::<&Vendor>()
+            .iter()
+            .map(|(_, pos)| (pos.x, pos.y))
+            .filter(explored)
+            .collect(),
+        TravelTarget::ItemPile => world
+            .query::<(&Position, &Container)>()
+            .without::<&BlocksMovement>()
+            .iter()
+            .filter(|(_, (_, container))| !container.is_empty())
+            .map(|(_, (pos, _))| (pos.x, pos.y))
+            .filter(explored)
+            .collect(),
+        TravelTarget::Tile(x, y) => [(x, y)].into_iter().filter(explored).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::{ContainerType, GroundItemPile, ItemType, Player};
+    use crate::tile::{Tile, TileType};
+
+    #[test]
+    fn test_travel_stops_next_to_the_stairs() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(8, 3, true, false);
+        grid.tiles[8 + 6].tile_type = TileType::StairsDown;
+        grid.stairs_down_pos = Some((6, 1));
+        let player = world.spawn((Player, Position::new(0, 1)));
+
+        let trip = plan(&world, &grid, player, TravelTarget::StairsDown).unwrap();
+        assert_eq!(trip.destination, (6, 1));
+        assert_eq!(trip.path.last(), Some(&(5, 1)));
+
+        // From next to the stairs, travelling takes them
+        world.get::<&mut Position>(player).unwrap().x = 5;
+        let trip = plan(&world, &grid, player, TravelTarget::StairsDown).unwrap();
+        assert_eq!(trip.path, vec![(6, 1)]);
+    }
+
+    #[test]
+    fn test_travel_reports_unexplored_and_unreachable() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(8, 3, true, false);
+        let player = world.spawn((Player, Position::new(0, 1)));
+        assert_eq!(plan(&world, &grid, player, TravelTarget::StairsUp), Err(TravelFailure::Unexplored));
+
+        // Stairs seen across a wall with no explored way round
+        grid.stairs_up_pos = Some((6, 1));
+        grid.tiles[8 + 6].tile_type = TileType::StairsUp;
+        for y in 0..3 {
+            grid.tiles[y * 8 + 3] = Tile::new(TileType::Wall);
+        }
+        assert_eq!(plan(&world, &grid, player, TravelTarget::StairsUp), Err(TravelFailure::Unreachable));
+    }
+
+    #[test]
+    fn test_travel_to_nearest_item_pile() {
+        let mut world = World::new();
+        let grid = Grid::filled_known(8, 3, true, false);
+        let player = world.spawn((Player, Position::new(0, 1)));
+        for x in [3, 7] {
+            world.spawn((
+                Position::new(x, 1),
+                GroundItemPile,
+                Container {
+                    container_type: ContainerType::GroundPile,
+                    items: vec![ItemType::Apple],
+                    gold: 0,
+                    is_open: true,
+                    spawn_chance: 0.0,
+                },
+            ));
+        }
+
+        let trip = plan(&world, &grid, player, TravelTarget::ItemPile).unwrap();
+        assert_eq!(trip.destination, (3, 1));
+        assert_eq!(trip.path.last(), Some(&(3, 1)));
+    }
+
+    #[test]
+    fn test_travel_to_map_tile_walks_onto_it() {
+        let mut world = World::new();
+        let mut grid = Grid::filled_known(8, 3, true, false);
+        let player = world.spawn((Player, Position::new(0, 1)));
+
+        let trip = plan(&world, &grid, player, TravelTarget::Tile(5, 2)).unwrap();
+        assert_eq!(trip.destination, (5, 2));
+        assert_eq!(trip.path.last(), Some(&(5, 2)));
+
+        grid.tiles[2 * 8 + 5].explored = false;
+        assert_eq!(plan(&world, &grid, player, TravelTarget::Tile(5, 2)), Err(TravelFailure::Unexplored));
+    }
+}
@@ -155,6 +155,15 @@ pub mod tile_ids {
     pub const AXE: (SpriteSheet, u32) = (SpriteSheet::Items, rc(4, 1, ITEMS_COLS)); // 4.b battle axe
     pub const STAFF: (SpriteSheet, u32) = (SpriteSheet::Items, rc(11, 0, ITEMS_COLS)); // 11.a crystal staff
 
+    // Armor and accessories
+    pub const SHIELD: (SpriteSheet, u32) = (SpriteSheet::Items, rc(12, 1, ITEMS_COLS)); // 12.b kite shield
+    pub const LEATHER_ARMOR: (SpriteSheet, u32) = (SpriteSheet::Items, rc(13, 1, ITEMS_COLS)); // 13.b leather armor
+    pub const CHAIN_MAIL: (SpriteSheet, u32) = (SpriteSheet::Items, rc(13, 3, ITEMS_COLS)); // 13.d chain mail
+    pub const HELMET: (SpriteSheet, u32) = (SpriteSheet::Items, rc(16, 1, ITEMS_COLS)); // 16.b helm
+    pub const AMULET: (SpriteSheet, u32) = (SpriteSheet::Items, rc(17, 1, ITEMS_COLS)); // 17.b metal pendant
+    pub const RUBY_RING: (SpriteSheet, u32) = (SpriteSheet::Items, rc(18, 1, ITEMS_COLS)); // 18.b gold ruby ring
+    pub const EMERALD_RING: (SpriteSheet, u32) = (SpriteSheet::Items, rc(18, 0, ITEMS_COLS)); // 18.a gold emerald ring
+
     // Potions
     pub const RED_POTION: (SpriteSheet, u32) = (SpriteSheet::Items, rc(20, 1, ITEMS_COLS)); // 20.b red potion
     pub const BLUE_POTION: (SpriteSheet, u32) = (SpriteSheet::Items, rc(21, 3, ITEMS_COLS)); // 21.d blue potion
@@ -257,6 +266,8 @@ pub struct Tile {
     pub revealed_until: Option<f32>,
     /// Optional sprite override (for oriented walls, etc.)
     pub sprite_override: Option<(SpriteSheet, u32)>,
+    /// A door stands here; paths go through it straight, never diagonally
+    pub doorway: bool,
 }
 
 impl Tile {
@@ -267,6 +278,7 @@ impl Tile {
             visible: false,
             revealed_until: None,
             sprite_override: None,
+            doorway: false,
         }
     }
 
@@ -4,7 +4,7 @@
 //! jumps forward to the next action completion rather than ticking.
 
 use crate::components::{
-    ActionInProgress, ActionType, Actor, EffectType, Health, RangedCooldown, StatusEffects,
+    ActionInProgress, ActionType, Actor, Behavior, EffectType, Health, RangedCooldown, Sneaking, StatusEffects,
 };
 use crate::constants::*;
 use crate::events::{EventQueue, GameEvent};
@@ -174,6 +174,7 @@ pub fn start_action_with_events(
     // Check for speed-modifying effects before borrowing Actor
     let has_speed_boost = effects::entity_has_effect(world, entity, EffectType::SpeedBoost);
     let has_slow = effects::entity_has_effect(world, entity, EffectType::Slowed);
+    let is_sneaking = world.get::<&Sneaking>(entity).is_ok();
 
     // Get actor component
     let mut actor = world
@@ -204,7 +205,10 @@ pub fn start_action_with_events(
     };
 
     // Calculate completion time
-    let duration = action_dispatch::calculate_action_duration(&action_type, effective_speed);
+    let mut duration = action_dispatch::calculate_action_duration(&action_type, effective_speed);
+    if is_sneaking && matches!(action_type, ActionType::Move { .. }) {
+        duration *= SNEAK_MOVE_DURATION_MULTIPLIER;
+    }
     let completion_time = clock.time + duration;
 
     // Record action in progress
@@ -300,7 +304,7 @@ fn apply_action_effects(
         ActionType::OpenDoor { door } => actions::apply_open_door(world, entity, *door, events),
         ActionType::OpenChest { chest } => actions::apply_open_chest(world, entity, *chest, events),
         ActionType::Wait => {
-            actions::apply_wait(world, entity, events)
+            actions::apply_wait(world, entity, spatial_cache, events)
         }
         ActionType::ShootBow { target_x, target_y } => {
             actions::apply_shoot_bow(world, grid, entity, *target_x, *target_y, events, current_time)
@@ -367,6 +371,15 @@ fn apply_action_effects(
             // Recovery is just a time delay, no effects
             ActionResult::Completed
         }
+        ActionType::HealAlly { target, amount } => {
+            actions::apply_heal_ally(world, entity, *target, *amount, events)
+        }
+        ActionType::BuffAlly { target, effect, duration } => {
+            actions::apply_buff_ally(world, entity, *target, *effect, *duration, events)
+        }
+        ActionType::CommandCompanions { order, companion } => {
+            actions::apply_command_companions(world, entity, *companion, *order, events)
+        }
     }
 }
 
@@ -492,15 +505,22 @@ pub fn tick_energy_regen(world: &mut World, current_time: f32, events: Option<&m
 }
 
 /// Process status effect duration ticks, removing expired effects
-pub fn tick_status_effects(world: &mut World, elapsed: f32) {
+pub fn tick_status_effects(world: &mut World, elapsed: f32, events: &mut EventQueue) {
     if elapsed <= 0.0 {
         return;
     }
 
-    for (_, effects) in world.query_mut::<&mut StatusEffects>() {
+    for (entity, effects) in world.query_mut::<&mut StatusEffects>() {
         effects.effects.retain_mut(|effect| {
             effect.remaining_duration -= elapsed;
-            effect.remaining_duration > 0.0
+            let active = effect.remaining_duration > 0.0;
+            if !active {
+                events.push(GameEvent::StatusEffectExpired {
+                    entity,
+                    effect_type: effect.effect_type,
+                });
+            }
+            active
         });
     }
 }
@@ -549,17 +569,31 @@ pub fn tick_ranged_cooldowns(world: &mut World, elapsed: f32) {
     }
 }
 
+/// Process heal/buff cooldown ticks (for support casters)
+pub fn tick_support_cooldowns(world: &mut World, elapsed: f32) {
+    if elapsed <= 0.0 {
+        return;
+    }
+
+    for (_, behavior) in world.query_mut::<&mut Behavior>() {
+        if behavior.support_cooldown > 0.0 {
+            behavior.support_cooldown = (behavior.support_cooldown - elapsed).max(0.0);
+        }
+    }
+}
+
 /// Process burn damage for entities with the Burning effect
 pub fn tick_burn_damage(world: &mut World, current_time: f32, events: &mut EventQueue) {
     use crate::components::Position;
 
     // Collect entities that need to take burn damage
+    let mut burning: Vec<(Entity, (f32, f32))> = Vec::new();
     let mut burn_events: Vec<(Entity, (f32, f32), i32)> = Vec::new();
     let mut deaths: Vec<Entity> = Vec::new();
 
     // First pass: find burning entities and check if they should take damage
-    for (entity, (health, effects, pos)) in
-        world.query_mut::<(&mut Health, &mut StatusEffects, &Position)>()
+    for (entity, (_, effects, pos)) in
+        world.query_mut::<(&Health, &mut StatusEffects, &Position)>()
     {
         // Find the burning effect
         if let Some(burn_effect) = effects
@@ -569,18 +603,31 @@ pub fn tick_burn_damage(world: &mut World, current_time: f32, events: &mut Event
         {
             let time_since_last = current_time - burn_effect.last_damage_tick;
             if time_since_last >= BURNING_DAMAGE_INTERVAL {
-                // Deal damage
-                let damage = BURNING_DAMAGE_PER_SECOND;
-                health.current = (health.current - damage).max(0);
                 burn_effect.last_damage_tick = current_time;
+                burning.push((entity, (pos.x as f32 + 0.5, pos.y as f32 + 0.5)));
+            }
+        }
+    }
 
-                burn_events.push((entity, (pos.x as f32 + 0.5, pos.y as f32 + 0.5), damage));
-
-                if health.current <= 0 {
-                    deaths.push(entity);
-                }
+    // Deal fire damage (after resistances/vulnerabilities)
+    for (entity, position) in burning {
+        let damage = crate::systems::combat::final_damage(
+            world,
+            entity,
+            BURNING_DAMAGE_PER_SECOND,
+            crate::components::DamageType::Fire,
+            false,
+        );
+        if damage <= 0 {
+            continue;
+        }
+        if let Ok(mut health) = world.get::<&mut Health>(entity) {
+            health.current = (health.current - damage).max(0);
+            if health.current <= 0 {
+                deaths.push(entity);
             }
         }
+        burn_events.push((entity, position, damage));
     }
 
     // Emit burn damage events and interrupt channeling
@@ -5,6 +5,16 @@
 use super::icons::UiIcons;
 use super::style;
 use crate::components::AbilityType;
+use crate::systems::noise::{ability_loudness, describe_loudness};
+
+/// Add the ability's noise line to a tooltip, just above the hotkey hint
+fn tooltip_with_noise(tooltip: &str, ability: AbilityType) -> String {
+    let noise = describe_loudness(ability_loudness(ability));
+    match tooltip.split_once("\n\n") {
+        Some((description, hotkey)) => format!("{}\n{}\n\n{}", description, noise, hotkey),
+        None => format!("{}\n{}", tooltip, noise),
+    }
+}
 
 /// Data for a single Ranger ability slot
 pub struct RangerAbilitySlot {
@@ -152,7 +162,7 @@ pub fn draw_ability_bar(ctx: &egui::Context, data: &AbilityBarData, icons: &UiIc
                 }
 
                 // Tooltip
-                response.on_hover_text(tooltip);
+                response.on_hover_text(tooltip_with_noise(tooltip, data.ability_type));
 
                 // Hotkey hint
                 ui.label(
@@ -257,7 +267,7 @@ pub fn draw_secondary_ability_bar(ctx: &egui::Context, data: &AbilityBarData, ic
                 }
 
                 // Tooltip
-                response.on_hover_text(tooltip);
+                response.on_hover_text(tooltip_with_noise(tooltip, data.ability_type));
 
                 // Hotkey hint
                 ui.label(
@@ -379,7 +389,7 @@ pub fn draw_ranger_ability_bar(ctx: &egui::Context, data: &RangerAbilityBarData,
                     }
 
                     // Tooltip
-                    response.on_hover_text(tooltip);
+                    response.on_hover_text(tooltip_with_noise(tooltip, slot.ability_type));
 
                     // Add spacing between buttons
                     if index < 3 {
@@ -0,0 +1,118 @@
+//! Bestiary window: every monster type, with what the player has learned about it.
+//!
+//! Monsters not yet met are listed as unknown. Met ones show their sprite and
+//! kill counts, and more of their stats as the player kills more of them.
+
+use super::icons::UiIcons;
+use super::{style, UiActions};
+use crate::bestiary::{Bestiary, Knowledge};
+use crate::components::Tactic;
+use crate::multi_tileset::MultiTileset;
+use crate::spawning::{enemies, EnemyDef};
+use crate::systems::examine::resistance_traits;
+
+/// Size of each monster's sprite (points)
+const SPRITE_SIZE: f32 = 48.0;
+
+/// Draw the bestiary window. Closing it sets `actions.close_bestiary`.
+pub fn draw_bestiary_window(
+    ctx: &egui::Context,
+    bestiary: &Bestiary,
+    tileset: &MultiTileset,
+    icons: &UiIcons,
+    actions: &mut UiActions,
+) {
+    let mut open = true;
+    let met = enemies::ALL.iter().filter(|def| bestiary.entry(def.name).is_some()).count();
+
+    egui::Window::new("Bestiary")
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .fixed_size([420.0, 480.0])
+        .collapsible(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new(format!("{} of {} monsters met", met, enemies::ALL.len()))
+                    .color(style::colors::TEXT_MUTED),
+            );
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                for (i, def) in enemies::ALL.into_iter().enumerate() {
+                    if i > 0 {
+                        ui.separator();
+                    }
+                    ui.horizontal_top(|ui| {
+                        let (rect, _) = ui.allocate_exact_size(egui::vec2(SPRITE_SIZE, SPRITE_SIZE), egui::Sense::hover());
+                        let Some(entry) = bestiary.entry(def.name) else {
+                            ui.label(egui::RichText::new("???").color(style::colors::TEXT_MUTED).size(16.0));
+                            return;
+                        };
+                        for sprite in std::iter::once(def.sprite).chain(def.overlay_sprite) {
+                            let uv = tileset.get_egui_uv(sprite.0, sprite.1);
+                            ui.painter().image(icons.texture_for_sheet(sprite.0), rect, uv, egui::Color32::WHITE);
+                        }
+
+                        ui.vertical(|ui| {
+                            ui.label(egui::RichText::new(def.name).color(style::colors::TEXT_ACCENT).size(16.0));
+                            ui.label(
+                                egui::RichText::new(format!("Killed: {}   Killed you: {}", entry.kills, entry.player_deaths))
+                                    .color(style::colors::TEXT_PRIMARY),
+                            );
+                            for fact in known_facts(def, entry.knowledge()) {
+                                ui.label(egui::RichText::new(fact).color(style::colors::TEXT_MUTED));
+                            }
+                            if let Some(kills) = entry.kills_to_next() {
+                                ui.label(
+                                    egui::RichText::new(format!("Kill {} more to learn more", kills))
+                                        .color(style::colors::TEXT_MUTED)
+                                        .small(),
+                                );
+                            }
+                        });
+                    });
+                }
+            });
+        });
+
+    if !open {
+        actions.close_bestiary = true;
+    }
+}
+
+/// What the bestiary reveals about a monster type at this level of knowledge
+fn known_facts(def: &EnemyDef, knowledge: Knowledge) -> Vec<String> {
+    let mut facts = Vec::new();
+    if knowledge >= Knowledge::Fought {
+        facts.push(format!("Health {}, hits for {}", def.health, def.damage));
+    }
+    if knowledge >= Knowledge::Studied {
+        facts.push(format!("Speed {:.0}%, sees {} tiles", def.speed * 100.0, def.sight_radius));
+        if let Some(ranged) = &def.ranged {
+            facts.push(format!(
+                "Shoots for {} at {}-{} tiles",
+                ranged.damage, ranged.min_range, ranged.max_range
+            ));
+        }
+        if def.tameable {
+            facts.push("Can be tamed".to_string());
+        }
+    }
+    if knowledge >= Knowledge::Mastered {
+        facts.extend(resistance_traits(&def.resistances));
+        facts.extend(def.behavior.iter().map(|tactic| tactic_description(tactic).to_string()));
+    }
+    facts
+}
+
+fn tactic_description(tactic: &Tactic) -> &'static str {
+    match tactic {
+        Tactic::KeepDistance { .. } => "Keeps its distance",
+        Tactic::Ambush { .. } => "Lies in wait in tall grass",
+        Tactic::Patrol { .. } => "Patrols around its post",
+        Tactic::WaitForAllies { .. } => "Holds back until its pack joins in",
+        Tactic::HealAllies { .. } => "Heals its allies",
+        Tactic::BuffAllies { .. } => "Casts spells on its allies",
+    }
+}
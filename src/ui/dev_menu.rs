@@ -71,6 +71,17 @@ const ALL_ITEMS: [ItemType; 14] = [
     ItemType::FireTrap,
 ];
 
+/// Wearable armor and accessories for the dev menu
+const ALL_ARMOR: [ItemType; 7] = [
+    ItemType::LeatherArmor,
+    ItemType::ChainMail,
+    ItemType::Helmet,
+    ItemType::Shield,
+    ItemType::RingOfStrength,
+    ItemType::RingOfAgility,
+    ItemType::AmuletOfWisdom,
+];
+
 /// State for the developer menu
 pub struct DevMenu {
     pub visible: bool,
@@ -272,5 +283,20 @@ pub fn draw_dev_menu(
                     dev_menu.item_to_give = Some(*item);
                 }
             }
+
+            ui.add_space(4.0);
+
+            // Armor
+            ui.label("Armor:");
+            for item in ALL_ARMOR.iter() {
+                let sprite = item_sprite(*item);
+                let texture_id = icons.texture_for_sheet(sprite.0);
+                let uv_rect = tileset.get_egui_uv(sprite.0, sprite.1);
+                let response = draw_list_item(ui, texture_id, uv_rect, false, item_name(*item));
+
+                if response.clicked() {
+                    dev_menu.item_to_give = Some(*item);
+                }
+            }
         });
 }
@@ -0,0 +1,68 @@
+//! Look mode UI: the cursor over the examined tile and a panel describing it.
+
+use super::style;
+use crate::camera::Camera;
+use crate::constants::MINIMAP_SIZE;
+use crate::systems::examine::TileDescription;
+
+/// Draw the look cursor and the description of the tile under it
+pub fn draw_examine(ctx: &egui::Context, camera: &Camera, cursor: (i32, i32), description: Option<&TileDescription>) {
+    // Cursor outline on the examined tile
+    let ppp = ctx.pixels_per_point();
+    let tile_size = camera.zoom / ppp;
+    let screen_pos = camera.world_to_screen(cursor.0 as f32, cursor.1 as f32);
+    let rect = egui::Rect::from_min_size(
+        egui::pos2(screen_pos.0 / ppp, screen_pos.1 / ppp - tile_size),
+        egui::vec2(tile_size, tile_size),
+    );
+    ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("examine_cursor")))
+        .rect_stroke(rect, 2.0, egui::Stroke::new(2.0, style::colors::DUNGEON_GOLD));
+
+    egui::Window::new("Look")
+        .anchor(egui::Align2::RIGHT_TOP, [-10.0, MINIMAP_SIZE + 40.0])
+        .fixed_size([240.0, 0.0])
+        .title_bar(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            let Some(description) = description else {
+                ui.label(egui::RichText::new("Unexplored").color(style::colors::TEXT_MUTED));
+                hint(ui);
+                return;
+            };
+
+            ui.label(egui::RichText::new(description.terrain).color(style::colors::TEXT_ACCENT));
+            for line in description.features.iter().chain(&description.containers) {
+                ui.label(egui::RichText::new(line).color(style::colors::TEXT_PRIMARY));
+            }
+
+            for creature in &description.creatures {
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(&creature.name).color(style::colors::TEXT_PRIMARY).strong());
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(egui::RichText::new(creature.state).color(style::colors::TEXT_ACCENT).small());
+                    });
+                });
+                ui.label(egui::RichText::new(creature.health).color(style::colors::TEXT_MUTED));
+                if !creature.effects.is_empty() {
+                    ui.label(egui::RichText::new(creature.effects.join(", ")).color(style::colors::TEXT_MUTED));
+                }
+                if creature.tameable {
+                    ui.label(egui::RichText::new("Can be tamed").color(style::colors::DUNGEON_GREEN));
+                }
+                for line in &creature.traits {
+                    ui.label(egui::RichText::new(line).color(style::colors::TEXT_MUTED));
+                }
+            }
+            hint(ui);
+        });
+}
+
+fn hint(ui: &mut egui::Ui) {
+    ui.separator();
+    ui.label(
+        egui::RichText::new("Move the cursor with the movement keys or the mouse. Esc to stop looking.")
+            .color(style::colors::TEXT_MUTED)
+            .small(),
+    );
+}
@@ -37,6 +37,13 @@ pub struct UiIcons {
     pub apple_uv: egui::Rect,
     pub fire_trap_uv: egui::Rect,
     pub arrow_uv: egui::Rect,
+    pub leather_armor_uv: egui::Rect,
+    pub chain_mail_uv: egui::Rect,
+    pub helmet_uv: egui::Rect,
+    pub shield_uv: egui::Rect,
+    pub ruby_ring_uv: egui::Rect,
+    pub emerald_ring_uv: egui::Rect,
+    pub amulet_uv: egui::Rect,
     // Ability icons
     pub cleave_uv: egui::Rect,
     pub sprint_uv: egui::Rect,
@@ -82,6 +89,13 @@ impl UiIcons {
             apple_uv: tileset.get_egui_uv(tile_ids::APPLE.0, tile_ids::APPLE.1),
             fire_trap_uv: tileset.get_egui_uv(tile_ids::FIRE_TRAP.0, tile_ids::FIRE_TRAP.1),
             arrow_uv: tileset.get_egui_uv(tile_ids::ARROW.0, tile_ids::ARROW.1),
+            leather_armor_uv: tileset.get_egui_uv(tile_ids::LEATHER_ARMOR.0, tile_ids::LEATHER_ARMOR.1),
+            chain_mail_uv: tileset.get_egui_uv(tile_ids::CHAIN_MAIL.0, tile_ids::CHAIN_MAIL.1),
+            helmet_uv: tileset.get_egui_uv(tile_ids::HELMET.0, tile_ids::HELMET.1),
+            shield_uv: tileset.get_egui_uv(tile_ids::SHIELD.0, tile_ids::SHIELD.1),
+            ruby_ring_uv: tileset.get_egui_uv(tile_ids::RUBY_RING.0, tile_ids::RUBY_RING.1),
+            emerald_ring_uv: tileset.get_egui_uv(tile_ids::EMERALD_RING.0, tile_ids::EMERALD_RING.1),
+            amulet_uv: tileset.get_egui_uv(tile_ids::AMULET.0, tile_ids::AMULET.1),
             // Ability icons: AXE for Cleave, BLUE_POTION for Sprint, AMBER_POTION for Barkskin (brown)
             cleave_uv: tileset.get_egui_uv(tile_ids::AXE.0, tile_ids::AXE.1),
             sprint_uv: tileset.get_egui_uv(tile_ids::BLUE_POTION.0, tile_ids::BLUE_POTION.1),
@@ -133,6 +147,13 @@ impl UiIcons {
             ItemType::Apple => self.apple_uv,
             ItemType::FireTrap => self.fire_trap_uv,
             ItemType::Arrow => self.arrow_uv,
+            ItemType::LeatherArmor => self.leather_armor_uv,
+            ItemType::ChainMail => self.chain_mail_uv,
+            ItemType::Helmet => self.helmet_uv,
+            ItemType::Shield => self.shield_uv,
+            ItemType::RingOfStrength => self.ruby_ring_uv,
+            ItemType::RingOfAgility => self.emerald_ring_uv,
+            ItemType::AmuletOfWisdom => self.amulet_uv,
         }
     }
 
@@ -6,8 +6,9 @@ use super::icons::UiIcons;
 use super::style;
 use super::GameUiState;
 use super::UiActions;
-use crate::components::{Equipment, Inventory, Stats};
+use crate::components::{ArmorSlot, Equipment, Inventory, ItemType, Stats};
 use crate::systems;
+use crate::systems::item_defs::{get_armor_def, ArmorDef};
 use hecs::World;
 
 /// Data needed to render the inventory window
@@ -79,18 +80,23 @@ fn draw_stats_column(
     actions: &mut UiActions,
 ) {
     ui.vertical(|ui| {
+        // Base stats plus bonuses from worn armor and accessories
+        let effective = systems::combat::effective_stats(world, player_entity).unwrap_or(*stats);
+
         ui.heading("CHARACTER STATS");
         ui.separator();
         ui.add_space(10.0);
-        ui.label(format!("Strength: {}", stats.strength));
+        ui.label(stat_line("Strength", stats.strength, effective.strength));
+        ui.add_space(5.0);
+        ui.label(stat_line("Intelligence", stats.intelligence, effective.intelligence));
         ui.add_space(5.0);
-        ui.label(format!("Intelligence: {}", stats.intelligence));
+        ui.label(stat_line("Agility", stats.agility, effective.agility));
         ui.add_space(5.0);
-        ui.label(format!("Agility: {}", stats.agility));
+        ui.label(format!("Defense: {}", systems::combat::armor_defense(world, player_entity)));
         ui.add_space(10.0);
         ui.separator();
 
-        let carry_capacity = stats.strength as f32 * 2.0;
+        let carry_capacity = effective.strength as f32 * 2.0;
         if let Ok(inventory) = world.get::<&Inventory>(player_entity) {
             ui.label(format!(
                 "Weight: {:.1} / {:.1} kg",
@@ -159,7 +165,7 @@ fn draw_stats_column(
 
                         // Right-click opens context menu
                         if response.secondary_clicked() {
-                            ui_state.equipped_context_menu = Some(response.rect.right_top());
+                            ui_state.equipped_context_menu = Some((None, response.rect.right_top()));
                         }
                     }
                     Some(crate::components::EquippedWeapon::Ranged(bow)) => {
@@ -187,7 +193,7 @@ fn draw_stats_column(
 
                         // Right-click opens context menu
                         if response.secondary_clicked() {
-                            ui_state.equipped_context_menu = Some(response.rect.right_top());
+                            ui_state.equipped_context_menu = Some((None, response.rect.right_top()));
                         }
                     }
                     None => {
@@ -199,10 +205,118 @@ fn draw_stats_column(
                     }
                 }
             });
+
+            // Armor and accessory slots
+            for slot in ArmorSlot::ALL {
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label(format!("{}:", slot.name()));
+                    match equipment.armor_in(slot) {
+                        Some(item_type) => {
+                            let size = egui::vec2(48.0, 48.0);
+                            let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+
+                            ui.painter().rect_filled(rect, 0.0, egui::Color32::BLACK);
+
+                            let image = egui::Image::new(egui::load::SizedTexture::new(
+                                icons.items_texture_id,
+                                size,
+                            ))
+                            .uv(icons.get_item_uv(item_type));
+                            image.paint_at(ui, rect);
+
+                            let response = response.on_hover_text(format!(
+                                "{}\n\n{}\n\nClick to unequip\nRight-click for options",
+                                systems::item_name(item_type),
+                                armor_description(item_type)
+                            ));
+
+                            // Left-click unequips
+                            if response.clicked() {
+                                actions.unequip_armor = Some(slot);
+                            }
+
+                            // Right-click opens context menu
+                            if response.secondary_clicked() {
+                                ui_state.equipped_context_menu = Some((Some(slot), response.rect.right_top()));
+                            }
+                        }
+                        None => {
+                            ui.label(
+                                egui::RichText::new("(none)")
+                                    .italics()
+                                    .color(style::colors::TEXT_MUTED),
+                            );
+                        }
+                    }
+                });
+            }
         }
     });
 }
 
+/// Format a stat with its armor bonus, e.g. "Strength: 12 (+2)"
+fn stat_line(name: &str, base: i32, effective: i32) -> String {
+    let bonus = effective - base;
+    if bonus == 0 {
+        format!("{}: {}", name, base)
+    } else {
+        format!("{}: {} ({:+})", name, effective, bonus)
+    }
+}
+
+/// Bonuses granted by an armor piece, one per line
+fn armor_bonuses(def: &ArmorDef) -> Vec<String> {
+    [
+        ("Defense", def.defense),
+        ("Strength", def.strength),
+        ("Intelligence", def.intelligence),
+        ("Agility", def.agility),
+    ]
+    .into_iter()
+    .filter(|(_, value)| *value != 0)
+    .map(|(name, value)| format!("{} {:+}", name, value))
+    .collect()
+}
+
+/// Describe an armor piece's slot and bonuses for tooltips
+fn armor_description(item_type: ItemType) -> String {
+    match get_armor_def(item_type) {
+        Some(def) => format!("Slot: {}\n{}", def.slot.name(), armor_bonuses(def).join("\n")),
+        None => String::new(),
+    }
+}
+
+/// Compare an armor piece against whatever is currently worn in its slot
+fn armor_comparison(item_type: ItemType, equipment: Option<&Equipment>) -> String {
+    let Some(def) = get_armor_def(item_type) else {
+        return String::new();
+    };
+    let Some(worn) = equipment
+        .and_then(|eq| eq.armor_in(def.slot))
+        .and_then(get_armor_def)
+    else {
+        return format!("{} slot is empty", def.slot.name());
+    };
+
+    let changes: Vec<String> = [
+        ("Defense", def.defense - worn.defense),
+        ("Strength", def.strength - worn.strength),
+        ("Intelligence", def.intelligence - worn.intelligence),
+        ("Agility", def.agility - worn.agility),
+    ]
+    .into_iter()
+    .filter(|(_, diff)| *diff != 0)
+    .map(|(name, diff)| format!("{} {:+}", name, diff))
+    .collect();
+
+    if changes.is_empty() {
+        format!("Same as worn {}", systems::item_name(worn.item_type))
+    } else {
+        format!("vs {}: {}", systems::item_name(worn.item_type), changes.join(", "))
+    }
+}
+
 /// Represents an inventory slot for display (may be a stack or single item)
 struct InventorySlot {
     item_type: crate::components::ItemType,
@@ -223,6 +337,7 @@ fn draw_inventory_column(
         ui.separator();
         ui.add_space(10.0);
 
+        let equipment = world.get::<&Equipment>(player_entity).ok();
         if let Ok(inventory) = world.get::<&Inventory>(player_entity) {
             if inventory.items.is_empty() {
                 ui.label(
@@ -282,6 +397,13 @@ fn draw_inventory_column(
                                 systems::item_name(slot.item_type),
                                 slot.count
                             )
+                        } else if get_armor_def(slot.item_type).is_some() {
+                            format!(
+                                "{}\n\n{}\n{}\n\nLeft-click to equip\nRight-click for options",
+                                systems::item_name(slot.item_type),
+                                armor_description(slot.item_type),
+                                armor_comparison(slot.item_type, equipment.as_deref())
+                            )
                         } else if is_throwable {
                             format!(
                                 "{}\n\nLeft-click to drink\nRight-click for options",
@@ -294,6 +416,16 @@ fn draw_inventory_column(
                             )
                         };
 
+                        // Items that make a racket say how far they can be heard
+                        let hover_text = match systems::noise::item_loudness(slot.item_type) {
+                            Some(loudness) => hover_text.replacen(
+                                "\n\n",
+                                &format!("\n{}\n\n", systems::noise::describe_loudness(loudness)),
+                                1,
+                            ),
+                            None => hover_text,
+                        };
+
                         let response = response.on_hover_text(hover_text);
 
                         // Left-click: use/drink the item (only for single items or non-stackables)
@@ -416,12 +548,11 @@ fn draw_item_context_menu(
                                 }
                             } else {
                                 // Non-throwable items: Use/Equip
-                                let is_weapon = matches!(
+                                let is_equippable = matches!(
                                     item_type,
-                                    crate::components::ItemType::Sword
-                                        | crate::components::ItemType::Bow
-                                );
-                                let button_text = if is_weapon { "Equip" } else { "Use" };
+                                    ItemType::Sword | ItemType::Bow
+                                ) || get_armor_def(item_type).is_some();
+                                let button_text = if is_equippable { "Equip" } else { "Use" };
                                 if ui.button(button_text).clicked() {
                                     actions.item_to_use = Some(item_idx);
                                     ui_state.item_context_menu = None;
@@ -472,14 +603,17 @@ fn draw_equipped_context_menu(
     ui_state: &mut GameUiState,
     actions: &mut UiActions,
 ) {
-    if let Some(pos) = ui_state.equipped_context_menu {
-        // Check if player still has a weapon equipped
-        let has_weapon = world
+    if let Some((armor_slot, pos)) = ui_state.equipped_context_menu {
+        // Check if player still has something equipped in that slot
+        let has_item = world
             .get::<&Equipment>(player_entity)
-            .map(|eq| eq.weapon.is_some())
+            .map(|eq| match armor_slot {
+                Some(slot) => eq.armor_in(slot).is_some(),
+                None => eq.weapon.is_some(),
+            })
             .unwrap_or(false);
 
-        if has_weapon {
+        if has_item {
             egui::Area::new(egui::Id::new("equipped_context_menu"))
                 .fixed_pos(pos)
                 .order(egui::Order::Foreground)
@@ -488,11 +622,17 @@ fn draw_equipped_context_menu(
                         ui.set_min_width(120.0);
 
                         if ui.button("Unequip").clicked() {
-                            actions.unequip_weapon = true;
+                            match armor_slot {
+                                Some(slot) => actions.unequip_armor = Some(slot),
+                                None => actions.unequip_weapon = true,
+                            }
                             ui_state.equipped_context_menu = None;
                         }
                         if ui.button("Drop").clicked() {
-                            actions.drop_equipped_weapon = true;
+                            match armor_slot {
+                                Some(slot) => actions.drop_equipped_armor = Some(slot),
+                                None => actions.drop_equipped_weapon = true,
+                            }
                             ui_state.equipped_context_menu = None;
                         }
 
@@ -518,7 +658,7 @@ fn draw_equipped_context_menu(
                 }
             }
         } else {
-            // Weapon no longer equipped, close menu
+            // Item no longer equipped, close menu
             ui_state.equipped_context_menu = None;
         }
     }
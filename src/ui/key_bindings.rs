@@ -0,0 +1,64 @@
+//! Key bindings screen: see and change which keys trigger which command.
+
+use super::{style, GameUiState, UiActions};
+use crate::keybindings::{KeyBindings, COMMAND_GROUPS};
+
+/// Draw the key bindings window. Clicking a key removes it; "+" waits for the
+/// next key press (picked up by the engine) and adds it to that command.
+pub fn draw_key_bindings_window(
+    ctx: &egui::Context,
+    bindings: &KeyBindings,
+    viewport_width: f32,
+    viewport_height: f32,
+    ui_state: &mut GameUiState,
+    actions: &mut UiActions,
+) {
+    let mut open = true;
+    egui::Window::new("Key Bindings")
+        .open(&mut open)
+        .default_pos([viewport_width / 2.0 - 220.0, viewport_height / 2.0 - 260.0])
+        .default_size([440.0, 520.0])
+        .collapsible(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            ui.label(
+                egui::RichText::new("Click a key to remove it, + to add one (Esc cancels).")
+                    .color(style::colors::TEXT_MUTED),
+            );
+            ui.separator();
+
+            egui::ScrollArea::vertical().auto_shrink([false, false]).max_height(420.0).show(ui, |ui| {
+                for (heading, commands) in COMMAND_GROUPS {
+                    ui.label(egui::RichText::new(heading).color(style::colors::TEXT_ACCENT).strong());
+                    egui::Grid::new(("key_bindings", heading)).num_columns(2).striped(true).show(ui, |ui| {
+                        for &command in commands {
+                            ui.label(command.label());
+                            ui.horizontal(|ui| {
+                                for &combo in bindings.keys(command) {
+                                    if ui.button(combo.name()).on_hover_text("Remove").clicked() {
+                                        actions.unbind_key = Some((command, combo));
+                                    }
+                                }
+                                if ui_state.capturing_binding == Some(command) {
+                                    ui.label(egui::RichText::new("Press a key...").color(style::colors::DUNGEON_GOLD));
+                                } else if ui.small_button("+").on_hover_text("Add a key").clicked() {
+                                    ui_state.capturing_binding = Some(command);
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+                    ui.add_space(6.0);
+                }
+            });
+
+            ui.separator();
+            if ui.button("Reset to defaults").clicked() {
+                actions.reset_key_bindings = true;
+            }
+        });
+
+    if !open {
+        ui_state.toggle_key_bindings();
+    }
+}
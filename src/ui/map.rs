@@ -0,0 +1,357 @@
+//! Maps: the corner minimap and the full-screen map (M).
+//!
+//! Both draw the same texture of the floor, built from the grid's explored and
+//! visible flags, with the player, companions and visible enemies marked on top.
+//! The texture is only touched when the grid's revisions change, and an FOV
+//! update only redraws the tiles around what was and is now in view. Clicking a known tile on the full-screen map travels there.
+
+use hecs::{Entity, World};
+
+use super::style;
+use super::UiActions;
+use crate::components::{CompanionAI, Health, Position};
+use crate::constants::{MAP_MAX_ZOOM, MAP_MIN_ZOOM, MINIMAP_SIZE, MINIMAP_VIEW_TILES};
+use crate::grid::Grid;
+use crate::systems::factions;
+use crate::tile::TileType;
+use egui::Color32;
+
+const WALL: Color32 = Color32::from_rgb(120, 110, 98);
+const FLOOR: Color32 = Color32::from_rgb(62, 54, 47);
+const GRASS: Color32 = Color32::from_rgb(52, 84, 46);
+const WATER: Color32 = Color32::from_rgb(42, 72, 130);
+const DOOR: Color32 = Color32::from_rgb(150, 96, 46);
+const STAIRS: Color32 = Color32::from_rgb(90, 200, 220);
+const SHOP: Color32 = style::colors::DUNGEON_GOLD;
+const PLAYER: Color32 = Color32::WHITE;
+const COMPANION: Color32 = Color32::from_rgb(110, 210, 110);
+const ENEMY: Color32 = Color32::from_rgb(220, 60, 50);
+
+/// Creatures marked on the maps
+pub struct MapMarkers {
+    pub player: Option<(i32, i32)>,
+    /// The player's companions in view
+    pub companions: Vec<(i32, i32)>,
+    /// Hostile creatures in view
+    pub enemies: Vec<(i32, i32)>,
+}
+
+/// Where the full-screen map is looking
+#[derive(Debug, Clone, Copy)]
+pub struct MapView {
+    /// Tile coordinates at the middle of the screen
+    pub center: egui::Vec2,
+    /// Points per tile
+    pub zoom: f32,
+}
+
+impl MapView {
+    /// Centered on the player, zoomed to fit the whole floor on screen
+    fn fit(grid: &Grid, screen: egui::Rect, player: Option<(i32, i32)>) -> Self {
+        let zoom = (screen.width() / grid.width as f32).min(screen.height() / grid.height as f32) * 0.9;
+        let center = match player {
+            Some((x, y)) => egui::vec2(x as f32 + 0.5, y as f32 + 0.5),
+            None => egui::vec2(grid.width as f32, grid.height as f32) / 2.0,
+        };
+        Self {
+            center,
+            zoom: zoom.clamp(MAP_MIN_ZOOM, MAP_MAX_ZOOM),
+        }
+    }
+
+    /// Screen position of a point in tile coordinates (tile y grows upwards)
+    fn to_screen(self, rect: egui::Rect, x: f32, y: f32) -> egui::Pos2 {
+        rect.center() + egui::vec2(x - self.center.x, self.center.y - y) * self.zoom
+    }
+
+    /// Tile coordinates under a screen position
+    fn to_tile(self, rect: egui::Rect, pos: egui::Pos2) -> egui::Vec2 {
+        let offset = (pos - rect.center()) / self.zoom;
+        egui::vec2(self.center.x + offset.x, self.center.y - offset.y)
+    }
+}
+
+/// The picture of the floor shared by both maps, and the grid revisions it shows
+#[derive(Default)]
+pub struct MapTexture {
+    handle: Option<egui::TextureHandle>,
+    view_revision: u64,
+    map_revision: u64,
+    /// Tiles drawn as in view, which need dimming once they leave it
+    drawn_visible: Vec<usize>,
+}
+
+/// Find the creatures to mark on the maps
+pub fn get_map_markers(world: &World, grid: &Grid, player_entity: Entity) -> MapMarkers {
+    let in_view = |pos: &Position| grid.get(pos.x, pos.y).is_some_and(|tile| tile.visible);
+
+    let companions = world
+        .query::<(&Position, &CompanionAI)>()
+        .iter()
+        .filter(|(_, (pos, ai))| ai.owner == player_entity && in_view(pos))
+        .map(|(_, (pos, _))| (pos.x, pos.y))
+        .collect();
+    let enemies = world
+        .query::<(&Position, &Health)>()
+        .iter()
+        .filter(|(id, (pos, health))| {
+            *id != player_entity
+                && !health.is_dead()
+                && in_view(pos)
+                && factions::is_hostile(world, player_entity, *id)
+        })
+        .map(|(_, (pos, _))| (pos.x, pos.y))
+        .collect();
+
+    MapMarkers {
+        player: world.get::<&Position>(player_entity).ok().map(|pos| (pos.x, pos.y)),
+        companions,
+        enemies,
+    }
+}
+
+/// Bring the picture of the floor up to date with the grid and return its texture
+pub fn update_map_texture(ctx: &egui::Context, grid: &Grid, texture: &mut MapTexture) -> egui::TextureId {
+    let options = egui::TextureOptions::NEAREST;
+    match &mut texture.handle {
+        // Same floor, nothing explored out of view: redraw what entered or left the view
+        Some(handle) if texture.map_revision == grid.map_revision => {
+            if texture.view_revision != grid.view_revision {
+                let changed = texture.drawn_visible.iter().chain(&grid.visible_tiles).copied();
+                if let Some((min, max)) = tile_bounds(grid, changed) {
+                    let corner = [min.0 as usize, grid.height - 1 - max.1 as usize];
+                    handle.set_partial(corner, map_image_region(grid, min, max), options);
+                }
+            }
+        }
+        Some(handle) => handle.set(map_image(grid), options),
+        None => texture.handle = Some(ctx.load_texture("map", map_image(grid), options)),
+    }
+
+    texture.view_revision = grid.view_revision;
+    texture.map_revision = grid.map_revision;
+    texture.drawn_visible.clone_from(&grid.visible_tiles);
+    texture.handle.as_ref().map_or(egui::TextureId::default(), |handle| handle.id())
+}
+
+/// Smallest and largest tile coordinates among some tile indices
+fn tile_bounds(grid: &Grid, tiles: impl Iterator<Item = usize>) -> Option<((i32, i32), (i32, i32))> {
+    tiles
+        .map(|idx| ((idx % grid.width) as i32, (idx / grid.width) as i32))
+        .fold(None, |bounds, (x, y)| match bounds {
+            None => Some(((x, y), (x, y))),
+            Some((min, max)) => Some(((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))),
+        })
+}
+
+/// Draw the minimap in the top-right corner. Returns true if it was clicked.
+pub fn draw_minimap(ctx: &egui::Context, grid: &Grid, texture: egui::TextureId, markers: &MapMarkers) -> bool {
+    // Bigger floors show the part around the player
+    let span_x = grid.width.min(MINIMAP_VIEW_TILES) as i32;
+    let span_y = grid.height.min(MINIMAP_VIEW_TILES) as i32;
+    let (px, py) = markers.player.unwrap_or((0, 0));
+    let x0 = (px - span_x / 2).clamp(0, grid.width as i32 - span_x);
+    let y0 = (py - span_y / 2).clamp(0, grid.height as i32 - span_y);
+
+    let scale = MINIMAP_SIZE / span_x.max(span_y) as f32;
+    let size = egui::vec2(span_x as f32, span_y as f32) * scale;
+    // Image rows run top to bottom, tile y bottom to top
+    let (w, h) = (grid.width as f32, grid.height as f32);
+    let uv = egui::Rect::from_min_max(
+        egui::pos2(x0 as f32 / w, (h - (y0 + span_y) as f32) / h),
+        egui::pos2((x0 + span_x) as f32 / w, (h - y0 as f32) / h),
+    );
+
+    let mut clicked = false;
+    egui::Area::new(egui::Id::new("minimap"))
+        .anchor(egui::Align2::RIGHT_TOP, [-10.0, 10.0])
+        .show(ctx, |ui| {
+            style::dungeon_window_frame().show(ui, |ui| {
+                let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+                let painter = ui.painter_at(rect);
+                painter.rect_filled(rect, 0.0, Color32::BLACK);
+                painter.image(texture, rect, uv, Color32::WHITE);
+
+                let to_screen = |(x, y): (i32, i32)| {
+                    rect.min + egui::vec2((x - x0) as f32 + 0.5, (y0 + span_y - y) as f32 - 0.5) * scale
+                };
+                let radius = (scale * 0.6).max(2.0);
+                draw_markers(&painter, markers, radius, to_screen);
+
+                clicked = response.on_hover_text("Open the map").clicked();
+            });
+        });
+    clicked
+}
+
+/// Draw the full-screen map. Drag to pan, scroll to zoom, click a tile to travel there.
+pub fn draw_map(
+    ctx: &egui::Context,
+    grid: &Grid,
+    texture: egui::TextureId,
+    markers: &MapMarkers,
+    view: &mut Option<MapView>,
+    actions: &mut UiActions,
+) {
+    let screen = ctx.screen_rect();
+    let view = view.get_or_insert_with(|| MapView::fit(grid, screen, markers.player));
+
+    egui::Area::new(egui::Id::new("full_map"))
+        .fixed_pos(screen.min)
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            let (rect, response) = ui.allocate_exact_size(screen.size(), egui::Sense::click_and_drag());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, Color32::from_rgb(12, 10, 9));
+
+            // Pan by dragging, zoom around the cursor with the wheel
+            if response.dragged() {
+                let delta = response.drag_delta() / view.zoom;
+                view.center += egui::vec2(-delta.x, delta.y);
+            }
+            if let Some(hover) = response.hover_pos() {
+                let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+                if scroll != 0.0 {
+                    let before = view.to_tile(rect, hover);
+                    view.zoom = (view.zoom * (scroll * 0.005).exp()).clamp(MAP_MIN_ZOOM, MAP_MAX_ZOOM);
+                    view.center += before - view.to_tile(rect, hover);
+                }
+            }
+
+            let map_rect = egui::Rect::from_two_pos(
+                view.to_screen(rect, 0.0, grid.height as f32),
+                view.to_screen(rect, grid.width as f32, 0.0),
+            );
+            let full_uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+            painter.image(texture, map_rect, full_uv, Color32::WHITE);
+
+            let to_screen = |(x, y): (i32, i32)| view.to_screen(rect, x as f32 + 0.5, y as f32 + 0.5);
+            let radius = (view.zoom * 0.35).max(2.5);
+            draw_markers(&painter, markers, radius, to_screen);
+
+            // Outline the known tile under the cursor; clicking it travels there
+            let hovered_tile = response.hover_pos().map(|pos| {
+                let tile = view.to_tile(rect, pos);
+                (tile.x.floor() as i32, tile.y.floor() as i32)
+            });
+            if let Some((x, y)) = hovered_tile.filter(|&(x, y)| grid.get(x, y).is_some_and(|tile| tile.explored)) {
+                let tile_rect = egui::Rect::from_two_pos(
+                    view.to_screen(rect, x as f32, y as f32 + 1.0),
+                    view.to_screen(rect, x as f32 + 1.0, y as f32),
+                );
+                painter.rect_stroke(tile_rect, 0.0, egui::Stroke::new(1.5, style::colors::DUNGEON_GOLD));
+                if response.clicked() && markers.player != Some((x, y)) {
+                    actions.travel_to = Some((x, y));
+                }
+            }
+
+            painter.text(
+                rect.center_bottom() - egui::vec2(0.0, 16.0),
+                egui::Align2::CENTER_BOTTOM,
+                "Click to travel  -  drag to pan  -  scroll to zoom  -  M or Esc to close",
+                egui::FontId::proportional(14.0),
+                style::colors::TEXT_MUTED,
+            );
+        });
+}
+
+/// Dots for companions, enemies and (on top) the player
+fn draw_markers(painter: &egui::Painter, markers: &MapMarkers, radius: f32, to_screen: impl Fn((i32, i32)) -> egui::Pos2) {
+    for &pos in &markers.companions {
+        painter.circle_filled(to_screen(pos), radius, COMPANION);
+    }
+    for &pos in &markers.enemies {
+        painter.circle_filled(to_screen(pos), radius, ENEMY);
+    }
+    if let Some(pos) = markers.player {
+        painter.circle(to_screen(pos), radius, PLAYER, egui::Stroke::new(1.0, Color32::BLACK));
+    }
+}
+
+/// One pixel per tile: known tiles in their map colour, remembered ones dimmer
+fn map_image(grid: &Grid) -> egui::ColorImage {
+    map_image_region(grid, (0, 0), (grid.width as i32 - 1, grid.height as i32 - 1))
+}
+
+/// The part of the map image covering the tiles from `min` to `max` (inclusive)
+fn map_image_region(grid: &Grid, min: (i32, i32), max: (i32, i32)) -> egui::ColorImage {
+    let width = (max.0 - min.0 + 1) as usize;
+    let height = (max.1 - min.1 + 1) as usize;
+    let mut image = egui::ColorImage::new([width, height], Color32::TRANSPARENT);
+    for y in min.1..=max.1 {
+        for x in min.0..=max.0 {
+            let Some(tile) = grid.get(x, y) else { continue };
+            if !tile.explored && !tile.visible && tile.revealed_until.is_none() {
+                continue;
+            }
+
+            let is_shop = grid.shop_position == Some((x, y));
+            let color = match tile.tile_type {
+                _ if is_shop => SHOP,
+                _ if tile.doorway => DOOR,
+                TileType::StairsDown | TileType::StairsUp => STAIRS,
+                TileType::Wall => WALL,
+                TileType::Water => WATER,
+                TileType::Grass | TileType::TallGrass => GRASS,
+                TileType::Floor | TileType::Stone => FLOOR,
+                TileType::Empty => continue,
+            };
+            let color = if tile.visible { color } else { dim(color) };
+            image.pixels[(max.1 - y) as usize * width + (x - min.0) as usize] = color;
+        }
+    }
+    image
+}
+
+/// A remembered (not currently visible) tile's colour
+fn dim(color: Color32) -> Color32 {
+    let [r, g, b, _] = color.to_array();
+    Color32::from_rgb(r / 2 + r / 8, g / 2 + g / 8, b / 2 + b / 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_image_shows_known_tiles_flipped() {
+        let mut grid = Grid::filled(4, 3, TileType::Floor);
+        grid.tiles[0].explored = true; // (0, 0): bottom-left, remembered
+        grid.tiles[2 * 4 + 3].visible = true; // (3, 2): top-right, in view
+        grid.tiles[2 * 4 + 3].tile_type = TileType::StairsDown;
+
+        let image = map_image(&grid);
+        assert_eq!(image.pixels[2 * 4], dim(FLOOR));
+        assert_eq!(image.pixels[3], STAIRS);
+        assert_eq!(image.pixels.iter().filter(|&&pixel| pixel != Color32::TRANSPARENT).count(), 2);
+    }
+
+    #[test]
+    fn test_map_image_region_matches_full_image() {
+        let mut grid = Grid::filled(6, 5, TileType::Floor);
+        for idx in [7, 8, 14, 20, 27] {
+            grid.tiles[idx].explored = true;
+        }
+        grid.tiles[14].visible = true;
+        grid.visible_tiles.push(14);
+
+        let full = map_image(&grid);
+        let (min, max) = tile_bounds(&grid, [7, 27, 14].into_iter()).unwrap();
+        assert_eq!((min, max), ((1, 1), (3, 4)));
+        let region = map_image_region(&grid, min, max);
+        assert_eq!(region.size, [3, 4]);
+        // Region rows are the full image's rows 0..4 (tile y 4 down to 1), columns 1..4
+        for row in 0..4 {
+            assert_eq!(region.pixels[row * 3..row * 3 + 3], full.pixels[row * 6 + 1..row * 6 + 4]);
+        }
+    }
+
+    #[test]
+    fn test_map_view_round_trips_screen_positions() {
+        let rect = egui::Rect::from_min_size(egui::pos2(0.0, 0.0), egui::vec2(800.0, 600.0));
+        let view = MapView { center: egui::vec2(10.0, 20.0), zoom: 8.0 };
+        let screen = view.to_screen(rect, 12.5, 18.0);
+        assert_eq!(screen, egui::pos2(420.0, 316.0));
+        assert_eq!(view.to_tile(rect, screen), egui::vec2(12.5, 18.0));
+    }
+}
@@ -0,0 +1,93 @@
+//! Message log UI components.
+//!
+//! A compact panel with the latest messages and a scrollable history window.
+
+use super::style;
+use crate::constants::MESSAGE_LOG_VISIBLE_LINES;
+use crate::message_log::{LogMessage, MessageCategory, MessageLog};
+
+const PANEL_WIDTH: f32 = 420.0;
+const LINE_HEIGHT: f32 = 16.0;
+
+/// Text colour for a message category
+fn category_color(category: MessageCategory) -> egui::Color32 {
+    match category {
+        MessageCategory::Attack => egui::Color32::from_rgb(220, 210, 195),
+        MessageCategory::Critical => egui::Color32::from_rgb(255, 220, 80),
+        MessageCategory::Hurt => egui::Color32::from_rgb(255, 100, 100),
+        MessageCategory::Miss => egui::Color32::from_rgb(150, 140, 125),
+        MessageCategory::Status => egui::Color32::from_rgb(170, 150, 255),
+        MessageCategory::Danger => egui::Color32::from_rgb(255, 150, 50),
+        MessageCategory::Item => egui::Color32::from_rgb(210, 180, 100),
+        MessageCategory::Progress => egui::Color32::from_rgb(100, 220, 100),
+        MessageCategory::General => egui::Color32::from_rgb(180, 190, 200),
+    }
+}
+
+fn message_label(message: &LogMessage, alpha: f32) -> egui::RichText {
+    egui::RichText::new(message.display_text())
+        .color(category_color(message.category).gamma_multiply(alpha))
+        .size(13.0)
+}
+
+/// Render the latest messages in the bottom-right corner.
+/// Returns true if the panel was clicked (to open the full history).
+pub fn draw_message_log(ctx: &egui::Context, log: &MessageLog, viewport_width: f32, viewport_height: f32) -> bool {
+    let panel_height = MESSAGE_LOG_VISIBLE_LINES as f32 * LINE_HEIGHT + 8.0;
+    let mut clicked = false;
+
+    egui::Window::new("Messages")
+        .fixed_pos([viewport_width - PANEL_WIDTH - 10.0, viewport_height - panel_height - 20.0])
+        .fixed_size([PANEL_WIDTH, panel_height])
+        .title_bar(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            let response = ui
+                .vertical(|ui| {
+                    ui.set_min_size(egui::vec2(PANEL_WIDTH, panel_height));
+                    let recent: Vec<_> = log.recent(MESSAGE_LOG_VISIBLE_LINES).collect();
+                    let count = recent.len();
+                    for (i, message) in recent.into_iter().enumerate() {
+                        // Older lines fade so the newest stands out
+                        let age = count - 1 - i;
+                        let alpha = 1.0 - age as f32 * 0.12;
+                        ui.label(message_label(message, alpha));
+                    }
+                })
+                .response
+                .interact(egui::Sense::click())
+                .on_hover_text("Click or press [L] for message history");
+            clicked = response.clicked();
+        });
+
+    clicked
+}
+
+/// Render the scrollable full message history
+pub fn draw_message_history(
+    ctx: &egui::Context,
+    log: &MessageLog,
+    viewport_width: f32,
+    viewport_height: f32,
+    open: &mut bool,
+) {
+    egui::Window::new("Message History")
+        .open(open)
+        .default_pos([viewport_width / 2.0 - 300.0, viewport_height / 2.0 - 250.0])
+        .default_size([600.0, 500.0])
+        .collapsible(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    if log.is_empty() {
+                        ui.label(egui::RichText::new("Nothing has happened yet.").color(style::colors::TEXT_MUTED));
+                    }
+                    for message in log.messages() {
+                        ui.label(message_label(message, 1.0));
+                    }
+                });
+        });
+}
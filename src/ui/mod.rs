@@ -5,40 +5,64 @@
 pub mod style;
 
 mod ability_bar;
+mod bestiary;
 mod dev_menu;
 mod dialogue;
+mod examine;
 mod icons;
 mod inventory;
+mod key_bindings;
 mod loot_window;
+mod map;
+mod message_log;
+mod options;
+mod party;
+mod pause_menu;
 mod shop_window;
 mod start_screen;
 mod status_bar;
 mod targeting;
 mod vfx;
+mod wait_prompt;
 
 // Re-export public items from submodules
 pub use ability_bar::{draw_ability_bar, draw_secondary_ability_bar, draw_ranger_ability_bar, AbilityBarData, RangerAbilityBarData, RangerAbilitySlot};
+pub use bestiary::draw_bestiary_window;
 pub use dev_menu::{draw_dev_menu, DevMenu, DevTool};
 pub use dialogue::{draw_dialogue_window, get_dialogue_window_data, DialogueWindowData};
+pub use examine::draw_examine;
 pub use icons::UiIcons;
 pub use inventory::{draw_inventory_window, InventoryWindowData};
+pub use key_bindings::draw_key_bindings_window;
 pub use loot_window::{draw_loot_window, get_loot_window_data, LootWindowData};
+pub use map::{draw_map, draw_minimap, get_map_markers, update_map_texture, MapTexture, MapView};
+pub use message_log::{draw_message_history, draw_message_log};
+pub use options::draw_options_window;
+pub use party::{draw_command_menu, draw_party_panel, get_party_data};
+pub use pause_menu::draw_pause_menu;
 pub use shop_window::{draw_shop_window, get_shop_window_data, ShopWindowData};
 pub use start_screen::run_start_screen;
 pub use status_bar::{draw_status_bar, get_status_bar_data, StatusBarData};
 pub use targeting::{draw_targeting_overlay, get_ability_targeting_overlay_data, get_targeting_overlay_data, TargetingOverlayData};
 pub use vfx::{
-    draw_alert_indicators, draw_damage_numbers, draw_enemy_health_bars,
+    draw_alert_indicators, draw_awareness_indicators, draw_damage_numbers, draw_enemy_health_bars,
     draw_enemy_status_indicators, draw_explosions, draw_life_drain_beams, draw_player_buff_auras,
-    draw_potion_splashes, get_buff_aura_data, get_enemy_health_data, get_enemy_status_data,
-    get_life_drain_beam_data, EnemyHealthData, EnemyStatusData, LifeDrainBeamData, PlayerBuffAuraData,
+    draw_potion_splashes, get_buff_aura_data, get_enemy_awareness_data, get_enemy_health_data,
+    get_enemy_status_data, get_life_drain_beam_data, EnemyHealthData, EnemyStatusData,
+    LifeDrainBeamData, PlayerBuffAuraData,
 };
+pub use wait_prompt::draw_wait_prompt;
 
+use crate::bestiary::{Bestiary, MonsterNotes};
 use crate::camera::Camera;
 use crate::events::GameEvent;
 use crate::grid::Grid;
-use crate::input::{AbilityTargetingMode, TargetingMode};
+use crate::input::{AbilityTargetingMode, CommandMenu, TargetingMode};
+use crate::keybindings::KeyBindings;
+use crate::message_log::MessageLog;
 use crate::multi_tileset::MultiTileset;
+use crate::settings::Settings;
+use crate::systems::examine::{describe_tile, Examine};
 use crate::vfx::VisualEffect;
 use egui_glow::EguiGlow;
 use hecs::{Entity, World};
@@ -56,6 +80,10 @@ pub struct UiActions {
     pub drop_equipped_weapon: bool,
     /// Unequip the currently equipped weapon (put back in inventory)
     pub unequip_weapon: bool,
+    /// Take off the armor worn in a slot (put back in inventory)
+    pub unequip_armor: Option<crate::components::ArmorSlot>,
+    /// Drop the armor worn in a slot onto the ground
+    pub drop_equipped_armor: Option<crate::components::ArmorSlot>,
     pub chest_item_to_take: Option<usize>,
     pub chest_take_all: bool,
     pub chest_take_gold: bool,
@@ -76,6 +104,32 @@ pub struct UiActions {
     pub sell_item: Option<usize>,
     /// Close the shop window
     pub close_shop: bool,
+    /// Order picked from the companion command menu
+    pub companion_order: Option<crate::components::CompanionOrder>,
+    /// The command menu should order the next companion
+    pub next_companion: bool,
+    /// Seconds of game time picked from the wait prompt
+    pub wait_for: Option<f32>,
+    /// Key removed from a command on the rebinding screen
+    pub unbind_key: Option<(crate::keybindings::Command, crate::keybindings::KeyCombo)>,
+    /// Put every key binding back to its default
+    pub reset_key_bindings: bool,
+    /// Settings changed on the options screen
+    pub settings: Option<crate::settings::Settings>,
+    /// Open the options screen (from the start screen)
+    pub open_options: bool,
+    /// Close the options screen
+    pub close_options: bool,
+    /// Open the bestiary (from the start screen or pause menu)
+    pub open_bestiary: bool,
+    /// Close the bestiary
+    pub close_bestiary: bool,
+    /// Give up on the current run and go back to the start screen
+    pub abandon_run: bool,
+    /// Close the game (from the pause menu)
+    pub quit: bool,
+    /// Tile clicked on the full-screen map to travel to
+    pub travel_to: Option<(i32, i32)>,
 }
 
 // =============================================================================
@@ -95,12 +149,32 @@ pub struct GameUiState {
     pub shopping_at: Option<Entity>,
     /// Show inventory window
     pub show_inventory: bool,
-    /// Show grid overlay
-    pub show_grid_lines: bool,
     /// Context menu for inventory item (item index, screen position)
     pub item_context_menu: Option<(usize, egui::Pos2)>,
-    /// Context menu for equipped weapon (screen position)
-    pub equipped_context_menu: Option<egui::Pos2>,
+    /// Context menu for an equipped item (armor slot or None for the weapon, screen position)
+    pub equipped_context_menu: Option<(Option<crate::components::ArmorSlot>, egui::Pos2)>,
+    /// Readable history of what happened this run
+    pub message_log: MessageLog,
+    /// Monsters met and killed this run, for the bestiary
+    pub monster_notes: MonsterNotes,
+    /// Show the full message history window
+    pub show_message_history: bool,
+    /// Show the "wait how long?" prompt
+    pub show_wait_prompt: bool,
+    /// Show the key bindings screen
+    pub show_key_bindings: bool,
+    /// Command waiting for a new key on the key bindings screen
+    pub capturing_binding: Option<crate::keybindings::Command>,
+    /// Show the pause menu (the game is paused while it's open)
+    pub show_pause_menu: bool,
+    /// The pause menu is asking whether to really abandon the run
+    pub confirm_abandon: bool,
+    /// Show the full-screen map
+    pub show_map: bool,
+    /// Pan and zoom of the full-screen map (`None` fits it to the screen when drawn)
+    pub map_view: Option<MapView>,
+    /// Picture of the floor shared by the minimap and the full-screen map
+    pub map_texture: MapTexture,
     /// The player entity (needed to filter events)
     player_entity: Entity,
 }
@@ -112,9 +186,19 @@ impl GameUiState {
             talking_to: None,
             shopping_at: None,
             show_inventory: false,
-            show_grid_lines: false,
             item_context_menu: None,
             equipped_context_menu: None,
+            message_log: MessageLog::new(),
+            monster_notes: MonsterNotes::default(),
+            show_message_history: false,
+            show_wait_prompt: false,
+            show_key_bindings: false,
+            capturing_binding: None,
+            show_pause_menu: false,
+            confirm_abandon: false,
+            show_map: false,
+            map_view: None,
+            map_texture: MapTexture::default(),
             player_entity,
         }
     }
@@ -158,16 +242,39 @@ impl GameUiState {
         self.talking_to = None;
     }
 
+    /// Toggle the full message history window
+    pub fn toggle_message_history(&mut self) {
+        self.show_message_history = !self.show_message_history;
+    }
+
+    /// Toggle the key bindings screen (stops waiting for a key when closed)
+    pub fn toggle_key_bindings(&mut self) {
+        self.show_key_bindings = !self.show_key_bindings;
+        self.capturing_binding = None;
+    }
+
+    /// Toggle the pause menu (always opens on the main page)
+    pub fn toggle_pause_menu(&mut self) {
+        self.show_pause_menu = !self.show_pause_menu;
+        self.confirm_abandon = false;
+    }
+
+    /// Toggle the full-screen map (it opens centered on the player)
+    pub fn toggle_map(&mut self) {
+        self.show_map = !self.show_map;
+        self.map_view = None;
+    }
+
+    /// Toggle the wait prompt
+    pub fn toggle_wait_prompt(&mut self) {
+        self.show_wait_prompt = !self.show_wait_prompt;
+    }
+
     /// Toggle inventory visibility
     pub fn toggle_inventory(&mut self) {
         self.show_inventory = !self.show_inventory;
     }
 
-    /// Toggle grid lines visibility
-    pub fn toggle_grid_lines(&mut self) {
-        self.show_grid_lines = !self.show_grid_lines;
-    }
-
     /// Close the currently open chest
     pub fn close_chest(&mut self) {
         self.open_chest = None;
@@ -185,6 +292,49 @@ impl GameUiState {
     }
 }
 
+/// Screens that belong to the player's profile rather than a run, so they
+/// can be open on the start screen or mid-run. `None` means closed.
+#[derive(Clone, Copy, Default)]
+pub struct ProfileScreens<'a> {
+    pub options: Option<&'a Settings>,
+    pub bestiary: Option<&'a Bestiary>,
+}
+
+/// Everything the in-run screens and menus show. `None` means closed.
+#[derive(Clone, Copy)]
+pub struct RunScreens<'a> {
+    pub profile: ProfileScreens<'a>,
+    pub examine: Option<&'a Examine>,
+    pub command_menu: Option<&'a CommandMenu>,
+    /// Current bindings, for the key bindings screen (toggled in `GameUiState`)
+    pub bindings: &'a KeyBindings,
+}
+
+/// Where the player is aiming and the game clock, for the overlays that follow them
+#[derive(Clone, Copy)]
+pub struct RunInput<'a> {
+    pub targeting_mode: Option<&'a TargetingMode>,
+    pub ability_targeting_mode: Option<&'a AbilityTargetingMode>,
+    pub mouse_pos: (f32, f32),
+    pub game_time: f32,
+}
+
+/// Draw whichever profile screens are open
+fn draw_profile_screens(
+    ctx: &egui::Context,
+    screens: ProfileScreens,
+    tileset: &MultiTileset,
+    icons: &UiIcons,
+    actions: &mut UiActions,
+) {
+    if let Some(bestiary) = screens.bestiary {
+        draw_bestiary_window(ctx, bestiary, tileset, icons, actions);
+    }
+    if let Some(settings) = screens.options {
+        draw_options_window(ctx, settings, actions);
+    }
+}
+
 // =============================================================================
 // MAIN UI RUNNER
 // =============================================================================
@@ -206,11 +356,10 @@ pub fn run_ui(
     icons: &UiIcons,
     vfx_effects: &[VisualEffect],
     life_drain_beams: &[LifeDrainBeamData],
-    targeting_mode: Option<&TargetingMode>,
-    ability_targeting_mode: Option<&AbilityTargetingMode>,
-    mouse_pos: (f32, f32),
-    game_time: f32,
+    screens: RunScreens,
+    input: RunInput,
 ) -> UiActions {
+    let RunInput { targeting_mode, ability_targeting_mode, mouse_pos, game_time } = input;
     let mut actions = UiActions::default();
 
     // Get status bar data
@@ -323,7 +472,11 @@ pub fn run_ui(
     let targeting_data = get_ability_targeting_overlay_data(world, player_entity, ability_targeting_mode, mouse_pos, camera, Some(grid))
         .or_else(|| get_targeting_overlay_data(world, player_entity, targeting_mode, mouse_pos, camera));
     let enemy_status_data = get_enemy_status_data(world, grid);
+    let enemy_awareness_data = get_enemy_awareness_data(world, grid);
     let enemy_health_data = get_enemy_health_data(world, grid, player_entity);
+    let party_data = get_party_data(world, player_entity);
+    let map_markers = get_map_markers(world, grid, player_entity);
+    let examined = screens.examine.map(|examine| (examine.cursor, describe_tile(world, grid, player_entity, examine.cursor)));
 
     egui_glow.run(window, |ctx| {
         // Enemy health bars (draw early so they're behind other indicators)
@@ -340,6 +493,9 @@ pub fn run_ui(
         // Status bar (always visible)
         draw_status_bar(ctx, &status_data, icons);
 
+        // Companions (if any)
+        draw_party_panel(ctx, &party_data);
+
         // Ability bar (if player has a class ability)
         if let Some(ref data) = ability_data {
             if draw_ability_bar(ctx, data, icons) {
@@ -367,6 +523,9 @@ pub fn run_ui(
         // Alert indicators (enemy spotted player)
         draw_alert_indicators(ctx, vfx_effects, camera);
 
+        // Awareness indicators (sleeping / unaware enemies)
+        draw_awareness_indicators(ctx, camera, &enemy_awareness_data, game_time);
+
         // Enemy status effect indicators (fear, slow, confusion)
         draw_enemy_status_indicators(ctx, camera, &enemy_status_data, game_time);
 
@@ -379,6 +538,16 @@ pub fn run_ui(
         // Life drain beams
         draw_life_drain_beams(ctx, camera, life_drain_beams);
 
+        // Companion command menu (if open)
+        if let Some(menu) = screens.command_menu {
+            draw_command_menu(ctx, menu, grid, &mut actions);
+        }
+
+        // Look mode cursor and description (if looking around)
+        if let Some((cursor, description)) = &examined {
+            draw_examine(ctx, camera, *cursor, description.as_ref());
+        }
+
         // Developer menu
         draw_dev_menu(ctx, dev_menu, icons, tileset);
 
@@ -397,6 +566,43 @@ pub fn run_ui(
             draw_shop_window(ctx, data, icons, &mut actions);
         }
 
+        // Minimap (always visible; click it for the full-screen map)
+        let map_texture = update_map_texture(ctx, grid, &mut ui_state.map_texture);
+        if draw_minimap(ctx, grid, map_texture, &map_markers) {
+            ui_state.toggle_map();
+        }
+
+        // Recent messages (always visible) and full history (if toggled)
+        if draw_message_log(ctx, &ui_state.message_log, viewport_width, viewport_height) {
+            ui_state.toggle_message_history();
+        }
+        if ui_state.show_message_history {
+            draw_message_history(ctx, &ui_state.message_log, viewport_width, viewport_height, &mut ui_state.show_message_history);
+        }
+
+        // Wait prompt (if toggled)
+        if ui_state.show_wait_prompt {
+            draw_wait_prompt(ctx, viewport_width, viewport_height, &mut ui_state.show_wait_prompt, &mut actions);
+        }
+
+        // Full-screen map (if toggled)
+        if ui_state.show_map {
+            draw_map(ctx, grid, map_texture, &map_markers, &mut ui_state.map_view, &mut actions);
+        }
+
+        // Pause menu (if paused)
+        if ui_state.show_pause_menu {
+            draw_pause_menu(ctx, ui_state, &mut actions);
+        }
+
+        // Key bindings screen (if toggled)
+        if ui_state.show_key_bindings {
+            draw_key_bindings_window(ctx, screens.bindings, viewport_width, viewport_height, ui_state, &mut actions);
+        }
+
+        // Options screen and bestiary (if open)
+        draw_profile_screens(ctx, screens.profile, tileset, icons, &mut actions);
+
         // Inventory window (if toggled)
         if show_inventory {
             let inv_data = InventoryWindowData {
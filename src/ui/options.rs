@@ -0,0 +1,63 @@
+//! Options screen: volume, display and camera settings.
+
+use super::{style, UiActions};
+use crate::constants::{CAMERA_ZOOM_LIMIT_CEILING, CAMERA_ZOOM_LIMIT_FLOOR, UI_SCALE_MAX, UI_SCALE_MIN};
+use crate::settings::Settings;
+
+/// Draw the options window. Changes go to `actions.settings` as they're made;
+/// closing the window sets `actions.close_options`.
+pub fn draw_options_window(ctx: &egui::Context, settings: &Settings, actions: &mut UiActions) {
+    let mut edited = settings.clone();
+    let mut open = true;
+
+    egui::Window::new("Options")
+        .open(&mut open)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .resizable(false)
+        .collapsible(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            egui::Grid::new("options").num_columns(2).spacing([16.0, 8.0]).show(ui, |ui| {
+                ui.label("Volume");
+                ui.add(egui::Slider::new(&mut edited.volume, 0.0..=1.0).custom_formatter(|v, _| {
+                    format!("{:.0}%", v * 100.0)
+                }));
+                ui.end_row();
+
+                ui.label("Interface scale");
+                ui.add(egui::Slider::new(&mut edited.ui_scale, UI_SCALE_MIN..=UI_SCALE_MAX).step_by(0.05));
+                ui.end_row();
+
+                ui.label("Closest zoom");
+                ui.add(
+                    egui::Slider::new(&mut edited.max_zoom, CAMERA_ZOOM_LIMIT_FLOOR..=CAMERA_ZOOM_LIMIT_CEILING)
+                        .logarithmic(true)
+                        .integer(),
+                );
+                ui.end_row();
+
+                ui.label("Furthest zoom");
+                ui.add(
+                    egui::Slider::new(&mut edited.min_zoom, CAMERA_ZOOM_LIMIT_FLOOR..=CAMERA_ZOOM_LIMIT_CEILING)
+                        .logarithmic(true)
+                        .integer(),
+                );
+                ui.end_row();
+            });
+
+            ui.checkbox(&mut edited.fullscreen, "Fullscreen");
+            ui.checkbox(&mut edited.show_grid_lines, "Show grid lines");
+
+            ui.separator();
+            if ui.button("Reset to defaults").clicked() {
+                edited = Settings::default();
+            }
+        });
+
+    if edited != *settings {
+        actions.settings = Some(edited.validated());
+    }
+    if !open {
+        actions.close_options = true;
+    }
+}
@@ -0,0 +1,125 @@
+//! Party UI: the companion command menu and the party panel.
+//!
+//! The radial menu opens at the cursor (V) and lists the orders in hotkey
+//! order around a button picking who gets them (Tab). The party panel sits under the status bar and shows each
+//! companion's health, current order and what it is fighting.
+
+use super::style;
+use super::UiActions;
+use crate::components::{CompanionAI, Health, Name};
+use crate::grid::Grid;
+use crate::input::CommandMenu;
+use hecs::{Entity, World};
+
+/// Distance from the menu center to each button, in points
+const MENU_RADIUS: f32 = 60.0;
+
+/// One companion's row in the party panel
+pub struct PartyMemberData {
+    pub name: &'static str,
+    pub health_current: i32,
+    pub health_max: i32,
+    pub order: &'static str,
+    /// Name of the enemy it is fighting, if any
+    pub target: Option<&'static str>,
+}
+
+/// Extract the player's companions from the world
+pub fn get_party_data(world: &World, player_entity: Entity) -> Vec<PartyMemberData> {
+    let name_of = |entity: Entity| world.get::<&Name>(entity).map(|n| n.0).unwrap_or("Companion");
+
+    world
+        .query::<(&CompanionAI, &Health)>()
+        .iter()
+        .filter(|(_, (ai, _))| ai.owner == player_entity)
+        .map(|(entity, (ai, health))| PartyMemberData {
+            name: name_of(entity),
+            health_current: health.current,
+            health_max: health.max,
+            order: ai.order.label(),
+            target: ai.current_target.map(name_of),
+        })
+        .collect()
+}
+
+/// Render the party panel below the status bar
+pub fn draw_party_panel(ctx: &egui::Context, party: &[PartyMemberData]) {
+    if party.is_empty() {
+        return;
+    }
+
+    egui::Window::new("Party")
+        .fixed_pos([10.0, 150.0])
+        .fixed_size([220.0, 0.0])
+        .title_bar(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            for (i, member) in party.iter().enumerate() {
+                if i > 0 {
+                    ui.separator();
+                }
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new(member.name).color(style::colors::TEXT_PRIMARY));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        ui.label(egui::RichText::new(member.order).color(style::colors::TEXT_ACCENT).small());
+                    });
+                });
+
+                let health_percent = if member.health_max > 0 {
+                    member.health_current as f32 / member.health_max as f32
+                } else {
+                    0.0
+                };
+                ui.add_sized(
+                    [200.0, 12.0],
+                    egui::ProgressBar::new(health_percent)
+                        .fill(style::colors::HP_BAR)
+                        .text(egui::RichText::new(format!("{}/{}", member.health_current, member.health_max)).small()),
+                );
+
+                if let Some(target) = member.target {
+                    ui.label(
+                        egui::RichText::new(format!("Fighting: {}", target))
+                            .color(style::colors::TEXT_MUTED)
+                            .small(),
+                    );
+                }
+            }
+        });
+}
+
+/// Render the radial command menu around the point it was opened at.
+/// Picking an entry sets `actions.companion_order`; the middle button picks who it goes to.
+pub fn draw_command_menu(ctx: &egui::Context, menu: &CommandMenu, grid: &Grid, actions: &mut UiActions) {
+    let ppp = ctx.pixels_per_point();
+    let center = egui::pos2(menu.screen_pos.0 / ppp, menu.screen_pos.1 / ppp);
+    let options = menu.options(grid);
+
+    egui::Area::new(egui::Id::new("command_menu_recipient"))
+        .fixed_pos(center)
+        .pivot(egui::Align2::CENTER_CENTER)
+        .order(egui::Order::Foreground)
+        .show(ctx, |ui| {
+            let button = egui::Button::new(menu.recipient()).min_size(egui::vec2(70.0, 24.0));
+            if ui.add_enabled(menu.companions.len() > 1, button).on_hover_text("Pick who gets the order").clicked() {
+                actions.next_companion = true;
+            }
+        });
+
+    for (i, (label, order)) in options.iter().enumerate() {
+        // Start at the top and go clockwise
+        let angle = -std::f32::consts::FRAC_PI_2 + i as f32 * std::f32::consts::TAU / options.len() as f32;
+        let pos = center + egui::vec2(angle.cos(), angle.sin()) * MENU_RADIUS;
+
+        egui::Area::new(egui::Id::new(("command_menu", i)))
+            .fixed_pos(pos)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let button = egui::Button::new(format!("{} {}", i + 1, label)).min_size(egui::vec2(70.0, 24.0));
+                if ui.add_enabled(order.is_some(), button).clicked() {
+                    actions.companion_order = *order;
+                }
+            });
+    }
+}
@@ -0,0 +1,57 @@
+//! Pause menu: opened with Escape mid-run. Nothing happens in the dungeon while it's open.
+
+use super::{style, GameUiState, UiActions};
+
+/// Draw the pause menu over a dimmed screen
+pub fn draw_pause_menu(ctx: &egui::Context, ui_state: &mut GameUiState, actions: &mut UiActions) {
+    // Dim the dungeon (behind every window, so the HUD stays readable)
+    ctx.layer_painter(egui::LayerId::background())
+        .rect_filled(ctx.screen_rect(), 0.0, egui::Color32::from_black_alpha(140));
+
+    let button = |text: &str| egui::Button::new(egui::RichText::new(text).size(18.0)).min_size(egui::vec2(220.0, 32.0));
+
+    egui::Window::new("Paused")
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .resizable(false)
+        .collapsible(false)
+        .title_bar(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.label(egui::RichText::new("Paused").size(24.0).color(style::colors::DUNGEON_GOLD));
+                ui.add_space(10.0);
+
+                if ui_state.confirm_abandon {
+                    ui.label("Abandon this run? It can't be resumed.");
+                    ui.add_space(6.0);
+                    if ui.add(button("Abandon run")).clicked() {
+                        actions.abandon_run = true;
+                    }
+                    if ui.add(button("Cancel")).clicked() {
+                        ui_state.confirm_abandon = false;
+                    }
+                    return;
+                }
+
+                if ui.add(button("Resume")).clicked() {
+                    ui_state.toggle_pause_menu();
+                }
+                if ui.add(button("Options")).clicked() {
+                    actions.open_options = true;
+                }
+                if ui.add(button("Bestiary")).clicked() {
+                    actions.open_bestiary = true;
+                }
+                if ui.add(button("Key reference")).clicked() && !ui_state.show_key_bindings {
+                    ui_state.toggle_key_bindings();
+                }
+                ui.add_space(10.0);
+                if ui.add(button("Abandon run")).clicked() {
+                    ui_state.confirm_abandon = true;
+                }
+                if ui.add(button("Quit game")).on_hover_text("Runs can't be saved yet, so this run is lost").clicked() {
+                    actions.quit = true;
+                }
+            });
+        });
+}
@@ -3,22 +3,25 @@
 //! Displays class selection and game start interface.
 
 use super::icons::UiIcons;
-use super::style;
+use super::{draw_profile_screens, style, ProfileScreens, UiActions};
 use crate::components::PlayerClass;
+use crate::grid::FloorSize;
 use crate::multi_tileset::MultiTileset;
 use egui_glow::EguiGlow;
 use winit::window::Window;
 
 /// Run the start screen UI for class selection.
-/// Returns Some(PlayerClass) if the player clicked Start, None otherwise.
+/// Sets `start_game` if the player clicked Start; `screens` are the open options and bestiary screens.
 pub fn run_start_screen(
     egui_glow: &mut EguiGlow,
     window: &Window,
     tileset: &MultiTileset,
     icons: &UiIcons,
     selected_class: &mut Option<PlayerClass>,
-) -> Option<PlayerClass> {
-    let mut start_clicked = None;
+    floor_size: &mut FloorSize,
+    screens: ProfileScreens,
+) -> UiActions {
+    let mut actions = UiActions::default();
 
     egui_glow.run(window, |ctx| {
         // Center the window
@@ -111,7 +114,23 @@ pub fn run_start_screen(
                         }
                     });
 
-                    ui.add_space(40.0);
+                    ui.add_space(30.0);
+
+                    // Floor size option
+                    let mut huge_floors = *floor_size == FloorSize::Huge;
+                    if ui
+                        .checkbox(
+                            &mut huge_floors,
+                            egui::RichText::new("Huge floors (300x300 and up)")
+                                .size(16.0)
+                                .color(egui::Color32::LIGHT_GRAY),
+                        )
+                        .changed()
+                    {
+                        *floor_size = if huge_floors { FloorSize::Huge } else { FloorSize::Standard };
+                    }
+
+                    ui.add_space(30.0);
 
                     // Start button
                     let start_enabled = selected_class.is_some();
@@ -132,7 +151,22 @@ pub fn run_start_screen(
                     });
 
                     if ui.add_enabled(start_enabled, button).clicked() {
-                        start_clicked = *selected_class;
+                        actions.start_game = *selected_class;
+                    }
+
+                    ui.add_space(10.0);
+
+                    if ui
+                        .add(egui::Button::new(egui::RichText::new("Options").size(18.0)).min_size(egui::vec2(200.0, 32.0)))
+                        .clicked()
+                    {
+                        actions.open_options = true;
+                    }
+                    if ui
+                        .add(egui::Button::new(egui::RichText::new("Bestiary").size(18.0)).min_size(egui::vec2(200.0, 32.0)))
+                        .clicked()
+                    {
+                        actions.open_bestiary = true;
                     }
 
                     ui.add_space(20.0);
@@ -172,7 +206,9 @@ pub fn run_start_screen(
                     }
                 });
             });
+
+        draw_profile_screens(ctx, screens, tileset, icons, &mut actions);
     });
 
-    start_clicked
+    actions
 }
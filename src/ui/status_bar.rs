@@ -4,7 +4,8 @@
 
 use super::icons::UiIcons;
 use super::style;
-use crate::components::{EffectType as StatusEffectType, Health, Inventory, StatusEffects};
+use crate::components::{EffectType as StatusEffectType, Health, Inventory, Sneaking, StatusEffects};
+use crate::constants::{NOISE_SNEAK_FOOTSTEP, SNEAK_MOVE_DURATION_MULTIPLIER};
 use crate::systems;
 use hecs::World;
 
@@ -17,6 +18,8 @@ pub struct StatusBarData {
     pub gold: u32,
     /// Active status effects with remaining duration
     pub active_effects: Vec<(StatusEffectType, f32)>,
+    /// Player is in sneak mode
+    pub sneaking: bool,
 }
 
 /// Extract status bar data from the world
@@ -48,6 +51,8 @@ pub fn get_status_bar_data(world: &World, player_entity: hecs::Entity) -> Status
         })
         .unwrap_or_default();
 
+    let sneaking = world.get::<&Sneaking>(player_entity).is_ok();
+
     StatusBarData {
         health_current,
         health_max,
@@ -55,6 +60,7 @@ pub fn get_status_bar_data(world: &World, player_entity: hecs::Entity) -> Status
         xp_level,
         gold,
         active_effects,
+        sneaking,
     }
 }
 
@@ -126,6 +132,16 @@ pub fn draw_status_bar(ctx: &egui::Context, data: &StatusBarData, icons: &UiIcon
                 .uv(icons.coins_uv);
                 ui.add(coin_img);
                 ui.label(format!("{}", data.gold));
+
+                if data.sneaking {
+                    ui.add_space(20.0);
+                    ui.label(egui::RichText::new("Sneaking").color(style::colors::TEXT_MUTED).small())
+                        .on_hover_text(format!(
+                            "Sneaking [C]\nMoving {:.0}% slower\nFootsteps - {}",
+                            (SNEAK_MOVE_DURATION_MULTIPLIER - 1.0) * 100.0,
+                            systems::noise::describe_loudness(NOISE_SNEAK_FOOTSTEP)
+                        ));
+                }
             });
 
             // Active status effects
@@ -1,10 +1,10 @@
 //! Visual effects UI rendering.
 //!
-//! Handles rendering of damage numbers, alert indicators, explosions,
+//! Handles rendering of damage numbers, alert and awareness indicators, explosions,
 //! health bars, status indicators, and buff auras.
 
 use crate::camera::Camera;
-use crate::components::{ChaseAI, EffectType, Health, ItemType, StatusEffects, VisualPosition};
+use crate::components::{Awareness, ChaseAI, DamageType, EffectType, Health, ItemType, StatusEffects, VisualPosition};
 use crate::constants::{DAMAGE_NUMBER_RISE, POTION_SPLASH_RADIUS};
 use crate::grid::Grid;
 use crate::systems::effects;
@@ -20,6 +20,13 @@ pub struct EnemyStatusData {
     pub is_confused: bool,
 }
 
+/// Data for an enemy that is asleep or hasn't noticed anyone
+pub struct EnemyAwarenessData {
+    pub x: f32,
+    pub y: f32,
+    pub awareness: Awareness,
+}
+
 /// Data for an enemy's health bar
 pub struct EnemyHealthData {
     pub x: f32,
@@ -72,6 +79,26 @@ pub fn get_enemy_status_data(world: &World, grid: &Grid) -> Vec<EnemyStatusData>
         .collect()
 }
 
+/// Extract awareness data for visible enemies that are asleep or unaware
+pub fn get_enemy_awareness_data(world: &World, grid: &Grid) -> Vec<EnemyAwarenessData> {
+    world
+        .query::<(&VisualPosition, &ChaseAI)>()
+        .iter()
+        .filter(|(_, (pos, ai))| {
+            ai.is_unaware()
+                && grid
+                    .get(pos.x as i32, pos.y as i32)
+                    .map(|t| t.visible)
+                    .unwrap_or(false)
+        })
+        .map(|(_, (pos, ai))| EnemyAwarenessData {
+            x: pos.x,
+            y: pos.y,
+            awareness: ai.awareness,
+        })
+        .collect()
+}
+
 /// Extract health data for visible damaged enemies
 pub fn get_enemy_health_data(world: &World, grid: &Grid, player_entity: Entity) -> Vec<EnemyHealthData> {
     world
@@ -107,10 +134,11 @@ pub fn draw_damage_numbers(ctx: &egui::Context, effects: &[VisualEffect], camera
     let ppp = ctx.pixels_per_point();
 
     for effect in effects {
-        // Handle both damage and heal numbers
-        let (amount, is_heal) = match &effect.effect_type {
-            VfxType::DamageNumber { amount } => (*amount, false),
-            VfxType::HealNumber { amount } => (*amount, true),
+        // Handle damage and heal numbers, and "miss" text
+        let (amount, damage_type, is_heal, is_miss) = match &effect.effect_type {
+            VfxType::DamageNumber { amount, damage_type } => (*amount, Some(*damage_type), false, false),
+            VfxType::HealNumber { amount } => (*amount, None, true, false),
+            VfxType::Miss => (0, None, false, true),
             _ => continue,
         };
 
@@ -132,15 +160,20 @@ pub fn draw_damage_numbers(ctx: &egui::Context, effects: &[VisualEffect], camera
         // Fade out as progress increases
         let alpha = ((1.0 - progress) * 255.0) as u8;
 
-        // Color: red for damage, green for healing
-        let color = if is_heal {
-            egui::Color32::from_rgba_unmultiplied(80, 255, 80, alpha)
+        // Color: damage type colour for damage, green for healing, grey for misses
+        let (r, g, b) = if is_miss {
+            (190, 190, 190)
+        } else if is_heal {
+            (80, 255, 80)
         } else {
-            egui::Color32::from_rgba_unmultiplied(255, 80, 80, alpha)
+            damage_type_color(damage_type.unwrap_or(DamageType::Physical))
         };
+        let color = egui::Color32::from_rgba_unmultiplied(r, g, b, alpha);
 
         // Draw the number (with + prefix for healing)
-        let text = if is_heal {
+        let text = if is_miss {
+            "miss".to_string()
+        } else if is_heal {
             format!("+{}", amount)
         } else {
             format!("{}", amount)
@@ -157,6 +190,17 @@ pub fn draw_damage_numbers(ctx: &egui::Context, effects: &[VisualEffect], camera
     }
 }
 
+/// Damage number colour for each damage type
+fn damage_type_color(damage_type: DamageType) -> (u8, u8, u8) {
+    match damage_type {
+        DamageType::Physical => (255, 80, 80),
+        DamageType::Fire => (255, 160, 40),
+        DamageType::Necrotic => (190, 100, 255),
+        DamageType::Poison => (170, 210, 40),
+        DamageType::Cold => (120, 200, 255),
+    }
+}
+
 /// Render alert indicators ("!") when enemies spot the player
 pub fn draw_alert_indicators(ctx: &egui::Context, effects: &[VisualEffect], camera: &Camera) {
     let painter = ctx.layer_painter(egui::LayerId::new(
@@ -222,6 +266,60 @@ pub fn draw_alert_indicators(ctx: &egui::Context, effects: &[VisualEffect], came
     }
 }
 
+/// Render awareness indicators: drifting "z" over sleepers, a faint "?" over unaware enemies.
+/// Both disappear (and the alert "!" pops up) once the enemy notices someone.
+pub fn draw_awareness_indicators(
+    ctx: &egui::Context,
+    camera: &Camera,
+    enemies: &[EnemyAwarenessData],
+    time: f32,
+) {
+    if enemies.is_empty() {
+        return;
+    }
+
+    let painter = ctx.layer_painter(egui::LayerId::new(
+        egui::Order::Foreground,
+        egui::Id::new("awareness_indicators"),
+    ));
+
+    let ppp = ctx.pixels_per_point();
+
+    for enemy in enemies {
+        let (symbol, world_x, world_y, font_size, color) = match enemy.awareness {
+            Awareness::Asleep => {
+                // Loop a "z" drifting up and to the right, fading as it goes
+                let cycle = (time * 0.6 + enemy.x * 0.37).fract();
+                let alpha = ((1.0 - cycle) * 220.0) as u8;
+                (
+                    "z",
+                    enemy.x + 0.2 + cycle * 0.3,
+                    enemy.y + 0.7 + cycle * 0.4,
+                    14.0 + cycle * 6.0,
+                    egui::Color32::from_rgba_unmultiplied(170, 190, 255, alpha),
+                )
+            }
+            Awareness::Unaware => (
+                "?",
+                enemy.x,
+                enemy.y + 0.8,
+                18.0,
+                egui::Color32::from_rgba_unmultiplied(200, 200, 200, 150),
+            ),
+            Awareness::Alert => continue,
+        };
+
+        let screen_pos = camera.world_to_screen(world_x, world_y);
+        painter.text(
+            egui::pos2(screen_pos.0 / ppp, screen_pos.1 / ppp),
+            egui::Align2::CENTER_CENTER,
+            symbol,
+            egui::FontId::monospace(font_size),
+            color,
+        );
+    }
+}
+
 /// Render health bars above damaged enemies
 pub fn draw_enemy_health_bars(ctx: &egui::Context, camera: &Camera, enemies: &[EnemyHealthData]) {
     if enemies.is_empty() {
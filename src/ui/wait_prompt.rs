@@ -0,0 +1,35 @@
+//! Wait prompt: pick how long to wait for.
+
+use super::{style, UiActions};
+use crate::constants::WAIT_CHOICES;
+
+/// Draw the "wait how long?" window; a pick goes to `actions.wait_for`
+pub fn draw_wait_prompt(
+    ctx: &egui::Context,
+    viewport_width: f32,
+    viewport_height: f32,
+    open: &mut bool,
+    actions: &mut UiActions,
+) {
+    egui::Window::new("Wait")
+        .open(open)
+        .default_pos([viewport_width / 2.0 - 120.0, viewport_height / 2.0 - 40.0])
+        .resizable(false)
+        .collapsible(false)
+        .frame(style::dungeon_window_frame())
+        .show(ctx, |ui| {
+            ui.label("Wait how long? Anything coming into view will stop you.");
+            ui.horizontal(|ui| {
+                for seconds in WAIT_CHOICES {
+                    let label = if seconds >= 60.0 {
+                        format!("{} min", seconds / 60.0)
+                    } else {
+                        format!("{} s", seconds)
+                    };
+                    if ui.add(egui::Button::new(label).min_size(egui::vec2(50.0, 24.0))).clicked() {
+                        actions.wait_for = Some(seconds);
+                    }
+                }
+            });
+        });
+}
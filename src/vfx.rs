@@ -3,6 +3,7 @@
 //! These are separate from entity state - they're spawned, animated, and removed
 //! without affecting game logic.
 
+use crate::components::DamageType;
 use crate::constants::*;
 use crate::events::GameEvent;
 use crate::grid::Grid;
@@ -49,10 +50,12 @@ impl VisualEffect {
 pub enum VfxType {
     /// Diagonal slash mark (for melee hits)
     Slash { angle: f32 },
-    /// Floating damage number
-    DamageNumber { amount: i32 },
+    /// Floating damage number, coloured by damage type
+    DamageNumber { amount: i32, damage_type: DamageType },
     /// Floating heal number (green, positive)
     HealNumber { amount: i32 },
+    /// Floating "miss" text when an attack fails its hit roll
+    Miss,
     /// Fire particle effect (looping)
     #[allow(dead_code)] // Reserved for torch/fire terrain
     Fire { seed: f32 },
@@ -77,6 +80,7 @@ impl VfxType {
             VfxType::Slash { .. } => SLASH_VFX_DURATION,
             VfxType::DamageNumber { .. } => DAMAGE_NUMBER_DURATION,
             VfxType::HealNumber { .. } => DAMAGE_NUMBER_DURATION, // Same duration as damage
+            VfxType::Miss => DAMAGE_NUMBER_DURATION,
             VfxType::Fire { .. } => f32::INFINITY, // Fire loops forever
             VfxType::Alert => ALERT_DURATION,
             VfxType::Explosion { .. } => EXPLOSION_DURATION,
@@ -147,8 +151,8 @@ impl VfxManager {
     }
 
     /// Spawn a floating damage number
-    pub fn spawn_damage_number(&mut self, x: f32, y: f32, amount: i32) {
-        self.spawn(x, y, VfxType::DamageNumber { amount });
+    pub fn spawn_damage_number(&mut self, x: f32, y: f32, amount: i32, damage_type: DamageType) {
+        self.spawn(x, y, VfxType::DamageNumber { amount, damage_type });
     }
 
     /// Spawn an alert indicator "!" above an entity
@@ -201,20 +205,28 @@ impl VfxManager {
     /// Only spawns VFX for positions visible to the player (not in fog of war).
     pub fn handle_event(&mut self, event: &GameEvent, grid: &Grid) {
         match event {
-            GameEvent::AttackHit { target_pos, damage, .. } => {
+            GameEvent::AttackHit { target_pos, damage, damage_type, .. } => {
                 // Only show VFX if the position is visible to the player
                 let tile_x = target_pos.0 as i32;
                 let tile_y = target_pos.1 as i32;
                 if grid.get(tile_x, tile_y).map(|t| t.visible).unwrap_or(false) {
                     self.spawn_slash(target_pos.0, target_pos.1);
-                    self.spawn_damage_number(target_pos.0, target_pos.1, *damage);
+                    self.spawn_damage_number(target_pos.0, target_pos.1, *damage, *damage_type);
+                }
+            }
+            GameEvent::AttackMissed { target_pos, .. } => {
+                // Only show VFX if the position is visible to the player
+                let tile_x = target_pos.0 as i32;
+                let tile_y = target_pos.1 as i32;
+                if grid.get(tile_x, tile_y).map(|t| t.visible).unwrap_or(false) {
+                    self.spawn(target_pos.0, target_pos.1, VfxType::Miss);
                 }
             }
             GameEvent::ProjectileHit { position, damage, target, .. } => {
                 // Only show damage number if we hit an enemy (not a wall) AND position is visible
                 if target.is_some() {
                     if grid.get(position.0, position.1).map(|t| t.visible).unwrap_or(false) {
-                        self.spawn_damage_number(position.0 as f32, position.1 as f32, *damage);
+                        self.spawn_damage_number(position.0 as f32, position.1 as f32, *damage, DamageType::Physical);
                     }
                 }
             }
@@ -254,7 +266,7 @@ impl VfxManager {
                 let tile_x = position.0 as i32;
                 let tile_y = position.1 as i32;
                 if grid.get(tile_x, tile_y).map(|t| t.visible).unwrap_or(false) {
-                    self.spawn_damage_number(position.0, position.1, *damage);
+                    self.spawn_damage_number(position.0, position.1, *damage, DamageType::Fire);
                 }
             }
             GameEvent::LifeDrainStarted { caster, target } => {
@@ -270,7 +282,7 @@ impl VfxManager {
                 let tile_x = target_pos.0 as i32;
                 let tile_y = target_pos.1 as i32;
                 if grid.get(tile_x, tile_y).map(|t| t.visible).unwrap_or(false) {
-                    self.spawn_damage_number(target_pos.0, target_pos.1, *damage);
+                    self.spawn_damage_number(target_pos.0, target_pos.1, *damage, DamageType::Necrotic);
                 }
                 // Show heal number on caster (green/positive)
                 let caster_tile_x = caster_pos.0 as i32;
@@ -280,6 +292,13 @@ impl VfxManager {
                     self.spawn(caster_pos.0, caster_pos.1, VfxType::HealNumber { amount: *healed });
                 }
             }
+            GameEvent::HealCast { target_pos, amount, .. } => {
+                let tile_x = target_pos.0 as i32;
+                let tile_y = target_pos.1 as i32;
+                if *amount > 0 && grid.get(tile_x, tile_y).map(|t| t.visible).unwrap_or(false) {
+                    self.spawn(target_pos.0, target_pos.1, VfxType::HealNumber { amount: *amount });
+                }
+            }
             _ => {}
         }
     }